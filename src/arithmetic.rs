@@ -0,0 +1,907 @@
+use crate::context::{Context, OverflowPolicy, RoundingMode};
+use crate::error::{CustomError, Message};
+use crate::value::{eval_arg, ToValue};
+use bigdecimal::{BigDecimal, Signed, ToPrimitive, Zero};
+use serde_json::{json, Value};
+
+// Arithmetic Ops
+
+pub enum ArithmeticResultType {
+    Number,
+    Decimal,
+    Text,
+}
+
+pub enum ArithmeticResult {
+    Number(i32),
+    Decimal(BigDecimal),
+    Text(String),
+}
+
+// The first arg, and the rest
+pub type Operands<T> = (Box<dyn ToValue<T>>, Vec<Box<dyn ToValue<T>>>);
+
+pub enum ArithmeticOperator {
+    Add,
+    Multiply,
+    Subtract,
+    Divide,
+    Modulus,
+    Min,
+    Max,
+    Pow,
+    Abs,
+    Sqrt, // of Numbers, rounded down
+    Sign,
+    Floor,
+    Ceil,
+    Clamp,   // [value, low, high]
+    Percent, // [value, percent]
+}
+
+// Exponents larger than this would build unreasonably large decimals
+pub(crate) const MAX_EXPONENT: u32 = 1024;
+// Powers are out of range when the digits of the base, counting the zeros its scale
+// stands for, times the exponent pass this, as nested powers would grow without bound
+pub(crate) const MAX_DIGITS: u64 = 4096;
+// Rounding to a scale past this, in either direction, would likewise build huge decimals.
+// Decimals read from text are held to it too, as aligning or rescaling them would.
+pub(crate) const MAX_SCALE: i64 = 1024;
+
+pub(crate) fn within_scale(v: &BigDecimal) -> bool {
+    v.as_bigint_and_exponent().1.unsigned_abs() <= MAX_SCALE as u64
+}
+
+impl ArithmeticOperator {
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            ArithmeticOperator::Add => "+",
+            ArithmeticOperator::Multiply => "*",
+            ArithmeticOperator::Subtract => "-",
+            ArithmeticOperator::Divide => "/",
+            ArithmeticOperator::Modulus => "%",
+            ArithmeticOperator::Min => "min",
+            ArithmeticOperator::Max => "max",
+            ArithmeticOperator::Pow => "pow",
+            ArithmeticOperator::Abs => "abs",
+            ArithmeticOperator::Sqrt => "sqrt",
+            ArithmeticOperator::Sign => "sign",
+            ArithmeticOperator::Floor => "floor",
+            ArithmeticOperator::Ceil => "ceil",
+            ArithmeticOperator::Clamp => "clamp",
+            ArithmeticOperator::Percent => "percent",
+        }
+    }
+
+    // Operators that take a fixed number of args, the rest fold over one or more args
+    pub(crate) fn arity(&self) -> Option<usize> {
+        match self {
+            ArithmeticOperator::Abs
+            | ArithmeticOperator::Sqrt
+            | ArithmeticOperator::Sign
+            | ArithmeticOperator::Floor
+            | ArithmeticOperator::Ceil => Some(1),
+            ArithmeticOperator::Percent => Some(2),
+            ArithmeticOperator::Clamp => Some(3),
+            _ => None,
+        }
+    }
+
+    // Applies the operator to numbers, yielding a Decimal only when an overflow is promoted
+    pub(crate) fn apply_number(
+        &self,
+        v: i32,
+        v1: i32,
+        policy: OverflowPolicy,
+    ) -> Result<ArithmeticResult, CustomError> {
+        match self {
+            ArithmeticOperator::Divide | ArithmeticOperator::Modulus if v1 == 0 => {
+                return Err(CustomError::Message(Message::ErrDivideByZero))
+            }
+            ArithmeticOperator::Pow if v1 < 0 => {
+                return Err(CustomError::Message(Message::ErrInvalidArgument))
+            }
+            _ => {}
+        }
+        let result: Option<i32> = match (self, policy) {
+            (ArithmeticOperator::Add, OverflowPolicy::Saturate) => Some(v.saturating_add(v1)),
+            (ArithmeticOperator::Multiply, OverflowPolicy::Saturate) => Some(v.saturating_mul(v1)),
+            (ArithmeticOperator::Subtract, OverflowPolicy::Saturate) => Some(v.saturating_sub(v1)),
+            (ArithmeticOperator::Divide, OverflowPolicy::Saturate) => Some(v.saturating_div(v1)),
+            (ArithmeticOperator::Pow, OverflowPolicy::Saturate) => {
+                Some(v.saturating_pow(v1 as u32))
+            }
+            (ArithmeticOperator::Add, OverflowPolicy::Wrap) => Some(v.wrapping_add(v1)),
+            (ArithmeticOperator::Multiply, OverflowPolicy::Wrap) => Some(v.wrapping_mul(v1)),
+            (ArithmeticOperator::Subtract, OverflowPolicy::Wrap) => Some(v.wrapping_sub(v1)),
+            (ArithmeticOperator::Divide, OverflowPolicy::Wrap) => Some(v.wrapping_div(v1)),
+            (ArithmeticOperator::Pow, OverflowPolicy::Wrap) => Some(v.wrapping_pow(v1 as u32)),
+            // i32::MIN % -1 is 0, it only overflows in the intermediate division
+            (ArithmeticOperator::Modulus, _) => Some(v.wrapping_rem(v1)),
+            (ArithmeticOperator::Add, _) => v.checked_add(v1),
+            (ArithmeticOperator::Multiply, _) => v.checked_mul(v1),
+            (ArithmeticOperator::Subtract, _) => v.checked_sub(v1),
+            (ArithmeticOperator::Divide, _) => v.checked_div(v1),
+            (ArithmeticOperator::Pow, _) => v.checked_pow(v1 as u32),
+            (ArithmeticOperator::Min, _) => Some(v.min(v1)),
+            (ArithmeticOperator::Max, _) => Some(v.max(v1)),
+            _ => return Err(CustomError::Message(Message::ErrUnexpected)),
+        };
+        match (result, policy) {
+            (Some(v2), _) => Ok(ArithmeticResult::Number(v2)),
+            (None, OverflowPolicy::Promote) => {
+                let v2 = self.apply_decimal(&BigDecimal::from(v), &BigDecimal::from(v1))?;
+                Ok(ArithmeticResult::Decimal(v2))
+            }
+            (None, _) => Err(CustomError::Message(Message::ErrOverflow)),
+        }
+    }
+
+    pub(crate) fn apply_decimal(
+        &self,
+        v: &BigDecimal,
+        v1: &BigDecimal,
+    ) -> Result<BigDecimal, CustomError> {
+        match self {
+            ArithmeticOperator::Add => Ok(v + v1),
+            ArithmeticOperator::Multiply => Ok(v * v1),
+            ArithmeticOperator::Subtract => Ok(v - v1),
+            ArithmeticOperator::Divide | ArithmeticOperator::Modulus if v1.is_zero() => {
+                Err(CustomError::Message(Message::ErrDivideByZero))
+            }
+            ArithmeticOperator::Divide => Ok(v / v1),
+            ArithmeticOperator::Modulus => Ok(v % v1),
+            ArithmeticOperator::Min => Ok(v.min(v1).clone()),
+            ArithmeticOperator::Max => Ok(v.max(v1).clone()),
+            ArithmeticOperator::Pow => {
+                let exponent: i64 = match (v1.is_integer(), v1.to_i64()) {
+                    (true, Some(v2)) if v2.unsigned_abs() <= MAX_EXPONENT as u64 => v2,
+                    (true, _) => return Err(CustomError::Message(Message::ErrOutOfRange)),
+                    (false, _) => return Err(CustomError::Message(Message::ErrInvalidArgument)),
+                };
+                let digits = match v.as_bigint_and_exponent().1 {
+                    scale if scale < 0 => v.digits() + scale.unsigned_abs(),
+                    scale => v.digits().max(scale as u64),
+                };
+                if digits.saturating_mul(exponent.unsigned_abs()) > MAX_DIGITS {
+                    return Err(CustomError::Message(Message::ErrOutOfRange));
+                }
+                let mut result = BigDecimal::from(1);
+                for _ in 0..exponent.unsigned_abs() {
+                    result *= v;
+                }
+                match (exponent < 0, result.is_zero()) {
+                    (true, true) => Err(CustomError::Message(Message::ErrDivideByZero)),
+                    (true, false) => Ok(BigDecimal::from(1) / result),
+                    (false, _) => Ok(result),
+                }
+            }
+            _ => Err(CustomError::Message(Message::ErrUnexpected)),
+        }
+    }
+
+    // Applies an operator of fixed arity to numbers, computing in i64 where i32 could overflow
+    pub(crate) fn apply_number_function(
+        &self,
+        args: &[i32],
+        policy: OverflowPolicy,
+    ) -> Result<ArithmeticResult, CustomError> {
+        let v = args[0] as i64;
+        let result: i64 = match self {
+            ArithmeticOperator::Abs => v.abs(),
+            ArithmeticOperator::Sqrt if v < 0 => {
+                return Err(CustomError::Message(Message::ErrInvalidArgument).at("/args/0"))
+            }
+            ArithmeticOperator::Sqrt => {
+                let mut v1 = (v as f64).sqrt() as i64;
+                while v1 * v1 > v {
+                    v1 -= 1;
+                }
+                while (v1 + 1) * (v1 + 1) <= v {
+                    v1 += 1;
+                }
+                v1
+            }
+            ArithmeticOperator::Sign => v.signum(),
+            ArithmeticOperator::Clamp if args[1] > args[2] => {
+                return Err(CustomError::Message(Message::ErrInvalidArgument).at("/args/2"))
+            }
+            ArithmeticOperator::Clamp => v.clamp(args[1] as i64, args[2] as i64),
+            ArithmeticOperator::Percent => v * args[1] as i64 / 100,
+            _ => return Err(CustomError::Message(Message::ErrUnexpected)),
+        };
+        match (result.to_i32(), policy) {
+            (Some(v1), _) => Ok(ArithmeticResult::Number(v1)),
+            (None, OverflowPolicy::Saturate) => Ok(ArithmeticResult::Number(
+                result.clamp(i32::MIN as i64, i32::MAX as i64) as i32,
+            )),
+            (None, OverflowPolicy::Wrap) => Ok(ArithmeticResult::Number(result as i32)),
+            (None, OverflowPolicy::Promote) => {
+                Ok(ArithmeticResult::Decimal(BigDecimal::from(result)))
+            }
+            (None, OverflowPolicy::Error) => {
+                Err(CustomError::Message(Message::ErrOverflow).at("/args/0"))
+            }
+        }
+    }
+
+    pub(crate) fn apply_decimal_function(
+        &self,
+        args: &[BigDecimal],
+    ) -> Result<BigDecimal, CustomError> {
+        let v = &args[0];
+        match self {
+            ArithmeticOperator::Abs => Ok(v.abs()),
+            ArithmeticOperator::Sqrt => match v.sqrt() {
+                Some(v1) => Ok(v1),
+                None => Err(CustomError::Message(Message::ErrInvalidArgument).at("/args/0")),
+            },
+            ArithmeticOperator::Sign => Ok(v.signum()),
+            ArithmeticOperator::Floor => Ok(RoundingMode::Floor.round(v, 0)),
+            ArithmeticOperator::Ceil => Ok(RoundingMode::Ceiling.round(v, 0)),
+            ArithmeticOperator::Clamp if args[1] > args[2] => {
+                Err(CustomError::Message(Message::ErrInvalidArgument).at("/args/2"))
+            }
+            ArithmeticOperator::Clamp => Ok(v.clamp(&args[1], &args[2]).clone()),
+            ArithmeticOperator::Percent => Ok(v * &args[1] / BigDecimal::from(100)),
+            _ => Err(CustomError::Message(Message::ErrUnexpected)),
+        }
+    }
+}
+
+// NUMBER ARITHMETIC
+
+pub enum NumberArithmeticExpression {
+    Add(Operands<i32>),
+    Multiply(Operands<i32>),
+    Subtract(Operands<i32>),
+    Divide(Operands<i32>),
+    Modulus(Operands<i32>),
+    Min(Operands<i32>),
+    Max(Operands<i32>),
+    Pow(Operands<i32>),
+    Abs(Operands<i32>),
+    Sqrt(Operands<i32>),
+    Sign(Operands<i32>),
+    Clamp(Operands<i32>),
+    Percent(Operands<i32>),
+}
+
+impl NumberArithmeticExpression {
+    pub(crate) fn operator(&self) -> (&Operands<i32>, ArithmeticOperator) {
+        match self {
+            NumberArithmeticExpression::Add(v) => (v, ArithmeticOperator::Add),
+            NumberArithmeticExpression::Multiply(v) => (v, ArithmeticOperator::Multiply),
+            NumberArithmeticExpression::Subtract(v) => (v, ArithmeticOperator::Subtract),
+            NumberArithmeticExpression::Divide(v) => (v, ArithmeticOperator::Divide),
+            NumberArithmeticExpression::Modulus(v) => (v, ArithmeticOperator::Modulus),
+            NumberArithmeticExpression::Min(v) => (v, ArithmeticOperator::Min),
+            NumberArithmeticExpression::Max(v) => (v, ArithmeticOperator::Max),
+            NumberArithmeticExpression::Pow(v) => (v, ArithmeticOperator::Pow),
+            NumberArithmeticExpression::Abs(v) => (v, ArithmeticOperator::Abs),
+            NumberArithmeticExpression::Sqrt(v) => (v, ArithmeticOperator::Sqrt),
+            NumberArithmeticExpression::Sign(v) => (v, ArithmeticOperator::Sign),
+            NumberArithmeticExpression::Clamp(v) => (v, ArithmeticOperator::Clamp),
+            NumberArithmeticExpression::Percent(v) => (v, ArithmeticOperator::Percent),
+        }
+    }
+
+    pub(crate) fn eval(
+        &self,
+        result_type: ArithmeticResultType,
+        context: &Context,
+    ) -> Result<ArithmeticResult, CustomError> {
+        let (args, operator) = self.operator();
+        if operator.arity().is_some() {
+            let values = std::iter::once(&args.0)
+                .chain(&args.1)
+                .enumerate()
+                .map(|(index, val)| eval_arg(val.as_ref(), index, context))
+                .collect::<Result<Vec<i32>, CustomError>>()?;
+            let result = operator.apply_number_function(&values, context.overflow)?;
+            return Self::convert(result, result_type);
+        }
+        let init: Result<ArithmeticResult, CustomError> = args
+            .0
+            .get_value(context)
+            .map(ArithmeticResult::Number)
+            .map_err(|e| e.at("/args/0"));
+        let result: Result<ArithmeticResult, CustomError> =
+            args.1
+                .iter()
+                .enumerate()
+                .fold(init, |acc, (index, val)| match acc {
+                    Ok(v) => {
+                        let pointer = format!("/args/{}", index + 1);
+                        match val.get_value(context) {
+                            Ok(v1) => match v {
+                                ArithmeticResult::Number(v2) => operator
+                                    .apply_number(v2, v1, context.overflow)
+                                    .map_err(|e| e.at(&pointer)),
+                                // Promoted values keep integer division
+                                ArithmeticResult::Decimal(v2) => operator
+                                    .apply_decimal(&v2, &BigDecimal::from(v1))
+                                    .map(|v3| match operator {
+                                        ArithmeticOperator::Divide => v3.with_scale(0),
+                                        _ => v3,
+                                    })
+                                    .map(ArithmeticResult::Decimal)
+                                    .map_err(|e| e.at(&pointer)),
+                                ArithmeticResult::Text(_) => {
+                                    Err(CustomError::Message(Message::ErrUnexpected))
+                                }
+                            },
+                            Err(e) => Err(e.at(&pointer)),
+                        }
+                    }
+                    Err(e) => Err(e),
+                });
+        Self::convert(result?, result_type)
+    }
+
+    pub(crate) fn convert(
+        result: ArithmeticResult,
+        result_type: ArithmeticResultType,
+    ) -> Result<ArithmeticResult, CustomError> {
+        match result {
+            ArithmeticResult::Number(v) => match result_type {
+                ArithmeticResultType::Number => Ok(ArithmeticResult::Number(v)),
+                ArithmeticResultType::Decimal => Ok(ArithmeticResult::Decimal(BigDecimal::from(v))),
+                ArithmeticResultType::Text => Ok(ArithmeticResult::Text(v.to_string())),
+            },
+            ArithmeticResult::Decimal(v) => match result_type {
+                ArithmeticResultType::Number => match v.to_i32() {
+                    Some(v1) => Ok(ArithmeticResult::Number(v1)),
+                    None => Err(CustomError::Message(Message::ErrOverflow)),
+                },
+                ArithmeticResultType::Decimal => Ok(ArithmeticResult::Decimal(v)),
+                ArithmeticResultType::Text => Ok(ArithmeticResult::Text(v.to_string())),
+            },
+            ArithmeticResult::Text(_) => Err(CustomError::Message(Message::ErrUnexpected)),
+        }
+    }
+
+    pub(crate) fn serialize(&self) -> Result<Value, CustomError> {
+        let (v, operator) = self.operator();
+        let operator: &str = operator.name();
+        let mut err: Option<CustomError> = None;
+        let result: Vec<Result<Value, CustomError>> = std::iter::once(&v.0)
+            .chain(&v.1)
+            .map(|val| match val.serialize() {
+                Ok(v) => Ok(v),
+                Err(e) => {
+                    err = Some(e.clone());
+                    Err(e)
+                }
+            })
+            .collect();
+        match err {
+            Some(e) => Err(e),
+            None => {
+                let args: Vec<Value> = result
+                    .iter()
+                    .map(|val| match val {
+                        Ok(v) => v.clone(),
+                        Err(_) => panic!(),
+                    })
+                    .collect();
+                Ok(json!({
+                    "op": operator,
+                    "type": "Number",
+                    "args": args
+                }))
+            }
+        }
+    }
+}
+
+impl ToValue<i32> for NumberArithmeticExpression {
+    fn get_value(&self, context: &Context) -> Result<i32, CustomError> {
+        match self.eval(ArithmeticResultType::Number, context)? {
+            ArithmeticResult::Number(v) => Ok(v),
+            _ => Err(CustomError::Message(Message::ErrUnexpected)),
+        }
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        self.serialize()
+    }
+}
+
+impl ToValue<BigDecimal> for NumberArithmeticExpression {
+    fn get_value(&self, context: &Context) -> Result<BigDecimal, CustomError> {
+        match self.eval(ArithmeticResultType::Decimal, context)? {
+            ArithmeticResult::Decimal(v) => Ok(v),
+            _ => Err(CustomError::Message(Message::ErrUnexpected)),
+        }
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        self.serialize()
+    }
+}
+
+impl ToValue<String> for NumberArithmeticExpression {
+    fn get_value(&self, context: &Context) -> Result<String, CustomError> {
+        match self.eval(ArithmeticResultType::Text, context)? {
+            ArithmeticResult::Text(v) => Ok(v),
+            _ => Err(CustomError::Message(Message::ErrUnexpected)),
+        }
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        self.serialize()
+    }
+}
+
+// DECIMAL ARITHMETIC
+
+pub enum DecimalArithmeticExpression {
+    Add(Operands<BigDecimal>),
+    Multiply(Operands<BigDecimal>),
+    Subtract(Operands<BigDecimal>),
+    Divide(Operands<BigDecimal>),
+    Modulus(Operands<BigDecimal>),
+    Min(Operands<BigDecimal>),
+    Max(Operands<BigDecimal>),
+    Pow(Operands<BigDecimal>),
+    Abs(Operands<BigDecimal>),
+    Sqrt(Operands<BigDecimal>),
+    Sign(Operands<BigDecimal>),
+    Floor(Operands<BigDecimal>),
+    Ceil(Operands<BigDecimal>),
+    Clamp(Operands<BigDecimal>),
+    Percent(Operands<BigDecimal>),
+}
+
+impl DecimalArithmeticExpression {
+    pub(crate) fn operator(&self) -> (&Operands<BigDecimal>, ArithmeticOperator) {
+        match self {
+            DecimalArithmeticExpression::Add(v) => (v, ArithmeticOperator::Add),
+            DecimalArithmeticExpression::Multiply(v) => (v, ArithmeticOperator::Multiply),
+            DecimalArithmeticExpression::Subtract(v) => (v, ArithmeticOperator::Subtract),
+            DecimalArithmeticExpression::Divide(v) => (v, ArithmeticOperator::Divide),
+            DecimalArithmeticExpression::Modulus(v) => (v, ArithmeticOperator::Modulus),
+            DecimalArithmeticExpression::Min(v) => (v, ArithmeticOperator::Min),
+            DecimalArithmeticExpression::Max(v) => (v, ArithmeticOperator::Max),
+            DecimalArithmeticExpression::Pow(v) => (v, ArithmeticOperator::Pow),
+            DecimalArithmeticExpression::Abs(v) => (v, ArithmeticOperator::Abs),
+            DecimalArithmeticExpression::Sqrt(v) => (v, ArithmeticOperator::Sqrt),
+            DecimalArithmeticExpression::Sign(v) => (v, ArithmeticOperator::Sign),
+            DecimalArithmeticExpression::Floor(v) => (v, ArithmeticOperator::Floor),
+            DecimalArithmeticExpression::Ceil(v) => (v, ArithmeticOperator::Ceil),
+            DecimalArithmeticExpression::Clamp(v) => (v, ArithmeticOperator::Clamp),
+            DecimalArithmeticExpression::Percent(v) => (v, ArithmeticOperator::Percent),
+        }
+    }
+
+    pub(crate) fn eval(
+        &self,
+        result_type: ArithmeticResultType,
+        context: &Context,
+    ) -> Result<ArithmeticResult, CustomError> {
+        let (args, operator) = self.operator();
+        let result: Result<BigDecimal, CustomError> = match operator.arity() {
+            Some(_) => std::iter::once(&args.0)
+                .chain(&args.1)
+                .enumerate()
+                .map(|(index, val)| eval_arg(val.as_ref(), index, context))
+                .collect::<Result<Vec<BigDecimal>, CustomError>>()
+                .and_then(|v| operator.apply_decimal_function(&v))
+                .map(|v| context.rescale(v)),
+            // only results are rescaled, so that operands count in full whatever their order
+            None => args.1.iter().enumerate().fold(
+                args.0.get_value(context).map_err(|e| e.at("/args/0")),
+                |acc, (index, val)| match &acc {
+                    Ok(v) => match val.get_value(context) {
+                        Ok(v1) => operator
+                            .apply_decimal(v, &v1)
+                            .map(|v2| context.rescale(v2))
+                            .map_err(|e| e.at(&format!("/args/{}", index + 1))),
+                        Err(e) => Err(e.at(&format!("/args/{}", index + 1))),
+                    },
+                    Err(_) => acc,
+                },
+            ),
+        };
+        match result_type {
+            ArithmeticResultType::Number => match result {
+                Ok(v) => Ok(ArithmeticResult::Number(context.to_number(&v)?)),
+                Err(e) => Err(e),
+            },
+            ArithmeticResultType::Decimal => match result {
+                Ok(v) => Ok(ArithmeticResult::Decimal(v)),
+                Err(e) => Err(e),
+            },
+            ArithmeticResultType::Text => match result {
+                Ok(v) => Ok(ArithmeticResult::Text(v.to_string())),
+                Err(e) => Err(e),
+            },
+        }
+    }
+
+    pub(crate) fn serialize(&self) -> Result<Value, CustomError> {
+        let (v, operator) = self.operator();
+        let operator: &str = operator.name();
+        let mut err: Option<CustomError> = None;
+        let result: Vec<Result<Value, CustomError>> = std::iter::once(&v.0)
+            .chain(&v.1)
+            .map(|val| match val.serialize() {
+                Ok(v) => Ok(v),
+                Err(e) => {
+                    err = Some(e.clone());
+                    Err(e)
+                }
+            })
+            .collect();
+        match err {
+            Some(e) => Err(e),
+            None => {
+                let args: Vec<Value> = result
+                    .iter()
+                    .map(|val| match val {
+                        Ok(v) => v.clone(),
+                        Err(_) => panic!(),
+                    })
+                    .collect();
+                Ok(json!({
+                    "op": operator,
+                    "type": "Decimal",
+                    "args": args
+                }))
+            }
+        }
+    }
+}
+
+impl ToValue<i32> for DecimalArithmeticExpression {
+    fn get_value(&self, context: &Context) -> Result<i32, CustomError> {
+        match self.eval(ArithmeticResultType::Number, context)? {
+            ArithmeticResult::Number(v) => Ok(v),
+            _ => Err(CustomError::Message(Message::ErrUnexpected)),
+        }
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        self.serialize()
+    }
+}
+
+impl ToValue<BigDecimal> for DecimalArithmeticExpression {
+    fn get_value(&self, context: &Context) -> Result<BigDecimal, CustomError> {
+        match self.eval(ArithmeticResultType::Decimal, context)? {
+            ArithmeticResult::Decimal(v) => Ok(v),
+            _ => Err(CustomError::Message(Message::ErrUnexpected)),
+        }
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        self.serialize()
+    }
+}
+
+impl ToValue<String> for DecimalArithmeticExpression {
+    fn get_value(&self, context: &Context) -> Result<String, CustomError> {
+        match self.eval(ArithmeticResultType::Text, context)? {
+            ArithmeticResult::Text(v) => Ok(v),
+            _ => Err(CustomError::Message(Message::ErrUnexpected)),
+        }
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        self.serialize()
+    }
+}
+
+// DECIMAL ROUNDING
+
+// Rounds to a fixed scale, using the rounding mode of the context when none is given
+pub struct RoundExpression {
+    pub(crate) value: Box<dyn ToValue<BigDecimal>>,
+    pub(crate) scale: i64,
+    pub(crate) mode: Option<RoundingMode>,
+}
+
+impl RoundExpression {
+    pub(crate) fn eval(
+        &self,
+        result_type: ArithmeticResultType,
+        context: &Context,
+    ) -> Result<ArithmeticResult, CustomError> {
+        let v = self.value.get_value(context).map_err(|e| e.at("/args/0"))?;
+        let v1 = self.mode.unwrap_or(context.rounding).round(&v, self.scale);
+        match result_type {
+            ArithmeticResultType::Number => Ok(ArithmeticResult::Number(context.to_number(&v1)?)),
+            ArithmeticResultType::Decimal => Ok(ArithmeticResult::Decimal(v1)),
+            ArithmeticResultType::Text => Ok(ArithmeticResult::Text(v1.to_string())),
+        }
+    }
+
+    pub(crate) fn serialize(&self) -> Result<Value, CustomError> {
+        let mut args: Vec<Value> = vec![self.value.serialize()?, json!(self.scale)];
+        if let Some(v) = self.mode {
+            args.push(v.serialize());
+        }
+        Ok(json!({
+            "op": "round",
+            "args": args
+        }))
+    }
+}
+
+impl ToValue<i32> for RoundExpression {
+    fn get_value(&self, context: &Context) -> Result<i32, CustomError> {
+        match self.eval(ArithmeticResultType::Number, context)? {
+            ArithmeticResult::Number(v) => Ok(v),
+            _ => Err(CustomError::Message(Message::ErrUnexpected)),
+        }
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        self.serialize()
+    }
+}
+
+impl ToValue<BigDecimal> for RoundExpression {
+    fn get_value(&self, context: &Context) -> Result<BigDecimal, CustomError> {
+        match self.eval(ArithmeticResultType::Decimal, context)? {
+            ArithmeticResult::Decimal(v) => Ok(v),
+            _ => Err(CustomError::Message(Message::ErrUnexpected)),
+        }
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        self.serialize()
+    }
+}
+
+impl ToValue<String> for RoundExpression {
+    fn get_value(&self, context: &Context) -> Result<String, CustomError> {
+        match self.eval(ArithmeticResultType::Text, context)? {
+            ArithmeticResult::Text(v) => Ok(v),
+            _ => Err(CustomError::Message(Message::ErrUnexpected)),
+        }
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        self.serialize()
+    }
+}
+
+#[cfg(test)]
+mod arithmetic_tests {
+    use std::collections::HashMap;
+    use std::str::FromStr;
+
+    use super::*;
+    use crate::error::Language;
+    use crate::expression::LispExpression;
+    use crate::symbol::Symbol;
+
+    #[test]
+    fn test_number_arithmetic_expression() {
+        let symbols: HashMap<String, Symbol> = HashMap::new();
+        let expr = NumberArithmeticExpression::Add((
+            Box::new(2),
+            vec![Box::new(BigDecimal::from_str("2.3").unwrap()), Box::new(7)],
+        ));
+        let res: i32 = (&expr).get_value(&Context::new(&symbols)).unwrap();
+        assert_eq!(11, res);
+        // eval == serialize.deserialize.eval
+        assert_eq!(
+            res,
+            (match LispExpression::deserialize((&expr as &dyn ToValue<i32>).serialize().unwrap()) {
+                Ok(v) => match v {
+                    LispExpression::NumberArithmeticExpression(v) =>
+                        (&v as &dyn ToValue<i32>).get_value(&Context::new(&symbols)),
+                    _ => Err(CustomError::Message(Message::ErrUnexpected)),
+                },
+                Err(_) => Err(CustomError::Message(Message::ErrUnexpected)),
+            })
+            .unwrap()
+        );
+        // serialize == serialize.deserialize.serialize
+        assert_eq!(
+            (&expr as &dyn ToValue<i32>).serialize().unwrap(),
+            match LispExpression::deserialize(
+                (&expr as &dyn ToValue<BigDecimal>).serialize().unwrap()
+            ) {
+                Ok(v) => match v {
+                    LispExpression::NumberArithmeticExpression(v) =>
+                        (&v as &dyn ToValue<i32>).serialize().unwrap(),
+                    _ => Value::Null,
+                },
+                Err(_) => Value::Null,
+            }
+        );
+    }
+
+    #[test]
+    fn test_decimal_arithmetic_expression() {
+        let symbols: HashMap<String, Symbol> = HashMap::new();
+        let expr = DecimalArithmeticExpression::Add((
+            Box::new(2),
+            vec![Box::new(BigDecimal::from_str("2.3").unwrap())],
+        ));
+        let res: BigDecimal = (&expr).get_value(&Context::new(&symbols)).unwrap();
+        assert_eq!(BigDecimal::from_str("4.3").unwrap(), res);
+        // eval == serialize.deserialize.eval
+        assert_eq!(
+            res,
+            (match LispExpression::deserialize(
+                (&expr as &dyn ToValue<BigDecimal>).serialize().unwrap()
+            ) {
+                Ok(v) => match v {
+                    LispExpression::DecimalArithmeticExpression(v) =>
+                        (&v as &dyn ToValue<BigDecimal>).get_value(&Context::new(&symbols)),
+                    _ => Err(CustomError::Message(Message::ErrUnexpected)),
+                },
+                Err(_) => Err(CustomError::Message(Message::ErrUnexpected)),
+            })
+            .unwrap()
+        );
+        // serialize == serialize.deserialize.serialize
+        assert_eq!(
+            (&expr as &dyn ToValue<BigDecimal>).serialize().unwrap(),
+            match LispExpression::deserialize(
+                (&expr as &dyn ToValue<BigDecimal>).serialize().unwrap()
+            ) {
+                Ok(v) => match v {
+                    LispExpression::DecimalArithmeticExpression(v) =>
+                        (&v as &dyn ToValue<BigDecimal>).serialize().unwrap(),
+                    _ => Value::Null,
+                },
+                Err(_) => Value::Null,
+            }
+        );
+    }
+
+    #[test]
+    fn test_decimal_serialization() {
+        let symbols: HashMap<String, Symbol> = HashMap::new();
+        let values = [
+            "0.1",
+            "123.4500",
+            "-98765432109876543210.000000000000000001",
+            "0.000000000000000000000000000001",
+        ];
+        let expr = DecimalArithmeticExpression::Add((
+            Box::new(BigDecimal::from_str(values[0]).unwrap()),
+            values[1..]
+                .iter()
+                .map(|val| {
+                    Box::new(BigDecimal::from_str(val).unwrap()) as Box<dyn ToValue<BigDecimal>>
+                })
+                .collect(),
+        ));
+        let res: BigDecimal = expr.get_value(&Context::new(&symbols)).unwrap();
+        assert_eq!(
+            BigDecimal::from_str("-98765432109876543086.450000000000000000999999999999").unwrap(),
+            res
+        );
+        // serialize == serialize.deserialize.serialize, byte for byte
+        let text = serde_json::to_string(&expr.serialize().unwrap()).unwrap();
+        assert_eq!(
+            text,
+            serde_json::to_string(
+                &LispExpression::deserialize(serde_json::from_str(&text).unwrap())
+                    .unwrap()
+                    .serialize()
+                    .unwrap()
+            )
+            .unwrap()
+        );
+        for val in values.iter() {
+            assert!(text.contains(&format!("\"{}\"", val)));
+        }
+        // JSON numbers are read from their text, not through f64
+        let expr = LispExpression::deserialize(json!({
+            "op": "+",
+            "type": "Decimal",
+            "args": [0.1, 0.2, 30000000000i64]
+        }))
+        .unwrap();
+        assert_eq!(
+            BigDecimal::from_str("30000000000.3").unwrap(),
+            expr.as_decimal(&Context::new(&symbols)).unwrap()
+        );
+        assert_eq!(
+            json!({"op": "+", "type": "Decimal", "args": ["0.1", "0.2", "30000000000"]}),
+            expr.serialize().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_round_expression() {
+        let symbols: HashMap<String, Symbol> = HashMap::new();
+        let round = |v: &str, mode: RoundingMode| {
+            mode.round(&BigDecimal::from_str(v).unwrap(), 2).to_string()
+        };
+        let cases = [
+            ("2.345", "2.34", "2.35", "2.34", "2.35", "2.34"),
+            ("2.355", "2.36", "2.36", "2.35", "2.36", "2.35"),
+            ("-2.345", "-2.34", "-2.35", "-2.35", "-2.34", "-2.34"),
+            ("-2.3451", "-2.35", "-2.35", "-2.35", "-2.34", "-2.34"),
+            ("2.3", "2.30", "2.30", "2.30", "2.30", "2.30"),
+        ];
+        for (v, half_even, half_up, floor, ceiling, truncate) in cases.iter() {
+            assert_eq!(*half_even, round(v, RoundingMode::HalfEven));
+            assert_eq!(*half_up, round(v, RoundingMode::HalfUp));
+            assert_eq!(*floor, round(v, RoundingMode::Floor));
+            assert_eq!(*ceiling, round(v, RoundingMode::Ceiling));
+            assert_eq!(*truncate, round(v, RoundingMode::Truncate));
+        }
+        let expr = RoundExpression {
+            value: Box::new(DecimalArithmeticExpression::Divide((
+                Box::new(BigDecimal::from_str("1").unwrap()),
+                vec![Box::new(BigDecimal::from_str("8").unwrap())],
+            ))),
+            scale: 2,
+            mode: Some(RoundingMode::Ceiling),
+        };
+        let res: BigDecimal = expr.get_value(&Context::new(&symbols)).unwrap();
+        assert_eq!(BigDecimal::from_str("0.13").unwrap(), res);
+        // eval == serialize.deserialize.eval
+        assert_eq!(
+            res,
+            (match LispExpression::deserialize(
+                (&expr as &dyn ToValue<BigDecimal>).serialize().unwrap()
+            ) {
+                Ok(v) => match v {
+                    LispExpression::RoundExpression(v) =>
+                        (&v as &dyn ToValue<BigDecimal>).get_value(&Context::new(&symbols)),
+                    _ => Err(CustomError::Message(Message::ErrUnexpected)),
+                },
+                Err(_) => Err(CustomError::Message(Message::ErrUnexpected)),
+            })
+            .unwrap()
+        );
+        // serialize == serialize.deserialize.serialize
+        assert_eq!(
+            (&expr as &dyn ToValue<BigDecimal>).serialize().unwrap(),
+            match LispExpression::deserialize(
+                (&expr as &dyn ToValue<BigDecimal>).serialize().unwrap()
+            ) {
+                Ok(v) => match v {
+                    LispExpression::RoundExpression(v) =>
+                        (&v as &dyn ToValue<BigDecimal>).serialize().unwrap(),
+                    _ => Value::Null,
+                },
+                Err(_) => Value::Null,
+            }
+        );
+        // The context scale applies to every Decimal operation, and its mode to round and to Numbers
+        let context = Context {
+            scale: Some(2),
+            rounding: RoundingMode::HalfUp,
+            ..Context::new(&symbols)
+        };
+        let eval = |text: &str| {
+            LispExpression::parse(text)
+                .unwrap()
+                .as_text(&context)
+                .unwrap()
+        };
+        assert_eq!("3.33", eval("(/:Decimal 10 3)"));
+        assert_eq!("9.99", eval("(*:Decimal (/:Decimal 10 3) 3)"));
+        assert_eq!("10.04", eval("(*:Decimal 1.004 10)"));
+        assert_eq!("10.04", eval("(*:Decimal 10 1.004)"));
+        assert_eq!("1.01", eval("(+:Decimal 1.004 0.002)"));
+        assert_eq!("1.01", eval("(+:Decimal 0.002 1.004)"));
+        assert_eq!("3.3", eval("(round (/:Decimal 10 3) 1)"));
+        assert_eq!("2", eval("(round 2.5 0 HalfEven)"));
+        assert!(LispExpression::parse("(round:Decimal 1.5 -1024)").is_ok());
+        for text in ["(round:Decimal 1.5 100000000)", "(round:Decimal 1.5 -1025)"] {
+            assert_eq!(
+                json!({"/args/1": "Out of range"}),
+                match LispExpression::parse(text) {
+                    Ok(_) => Value::Null,
+                    Err(e) => e.serialize(&Language::English),
+                }
+            );
+        }
+        let expr = LispExpression::parse("(+:Decimal 2.5)").unwrap();
+        assert_eq!(3, expr.as_number(&context).unwrap());
+        assert_eq!(2, expr.as_number(&Context::new(&symbols)).unwrap());
+    }
+}
@@ -0,0 +1,344 @@
+use crate::context::Context;
+use crate::error::CustomError;
+use crate::symbol::{Leaf, LeafType, Symbol};
+use crate::value::ToValue;
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, Duration, FixedOffset, NaiveDate};
+use serde_json::{json, Value};
+
+// LET OPS
+
+// A sub-expression evaluated as its declared type, bound to a name or passed to a function
+pub enum Binding {
+    Number(Box<dyn ToValue<i32>>),
+    Decimal(Box<dyn ToValue<BigDecimal>>),
+    Text(Box<dyn ToValue<String>>),
+    Boolean(Box<dyn ToValue<bool>>),
+    Date(Box<dyn ToValue<NaiveDate>>),
+    DateTime(Box<dyn ToValue<DateTime<FixedOffset>>>),
+    Duration(Box<dyn ToValue<Duration>>),
+}
+
+impl Binding {
+    pub(crate) fn leaf_type(&self) -> LeafType {
+        match self {
+            Binding::Number(_) => LeafType::Number,
+            Binding::Decimal(_) => LeafType::Decimal,
+            Binding::Text(_) => LeafType::Text,
+            Binding::Boolean(_) => LeafType::Boolean,
+            Binding::Date(_) => LeafType::Date,
+            Binding::DateTime(_) => LeafType::DateTime,
+            Binding::Duration(_) => LeafType::Duration,
+        }
+    }
+
+    // Nulls are kept as values, so that they can be bound, passed and tested
+    pub(crate) fn eval(&self, context: &Context) -> Result<Leaf, CustomError> {
+        let result = match self {
+            Binding::Number(v) => v.get_value(context).map(Leaf::Number),
+            Binding::Decimal(v) => v.get_value(context).map(Leaf::Decimal),
+            Binding::Text(v) => v.get_value(context).map(Leaf::Text),
+            Binding::Boolean(v) => v.get_value(context).map(Leaf::Boolean),
+            Binding::Date(v) => v.get_value(context).map(Leaf::Date),
+            Binding::DateTime(v) => v.get_value(context).map(Leaf::DateTime),
+            Binding::Duration(v) => v.get_value(context).map(Leaf::Duration),
+        };
+        match result {
+            Err(e) if e.is_null() => Ok(Leaf::Null),
+            v => v,
+        }
+    }
+
+    pub(crate) fn serialize(&self) -> Result<(LeafType, Value), CustomError> {
+        match self {
+            Binding::Number(v) => Ok((LeafType::Number, v.serialize()?)),
+            Binding::Decimal(v) => Ok((LeafType::Decimal, v.serialize()?)),
+            Binding::Text(v) => Ok((LeafType::Text, v.serialize()?)),
+            Binding::Boolean(v) => Ok((LeafType::Boolean, v.serialize()?)),
+            Binding::Date(v) => Ok((LeafType::Date, v.serialize()?)),
+            Binding::DateTime(v) => Ok((LeafType::DateTime, v.serialize()?)),
+            Binding::Duration(v) => Ok((LeafType::Duration, v.serialize()?)),
+        }
+    }
+}
+
+// Evaluates each binding once, in order, then the body in a scope holding them.
+// A binding sees the ones before it, and is read back with a single segment dot expression.
+// [[[name, type, value], ...], body]
+pub struct LetExpression<T> {
+    pub(crate) bindings: Vec<(String, Binding)>,
+    pub(crate) body: Box<dyn ToValue<T>>,
+}
+
+impl<T> LetExpression<T> {
+    pub(crate) fn eval(&self, context: &Context) -> Result<T, CustomError> {
+        let mut scope = context.scope();
+        for (index, (name, binding)) in self.bindings.iter().enumerate() {
+            let v = binding
+                .eval(&scope)
+                .map_err(|e| e.at(&format!("/args/0/{}/2", index)))?;
+            scope
+                .bindings
+                .insert(name.to_string(), Symbol::from_leaf(v));
+        }
+        self.body.get_value(&scope).map_err(|e| e.at("/args/1"))
+    }
+
+    pub(crate) fn serialize_as(&self, result_type: LeafType) -> Result<Value, CustomError> {
+        let bindings: Vec<Value> = self
+            .bindings
+            .iter()
+            .map(|(name, binding)| {
+                let (binding_type, v) = binding.serialize()?;
+                Ok(json!([name, binding_type.serialize(), v]))
+            })
+            .collect::<Result<Vec<Value>, CustomError>>()?;
+        Ok(json!({
+            "op": "let",
+            "type": result_type.serialize(),
+            "args": [bindings, self.body.serialize()?]
+        }))
+    }
+}
+
+impl ToValue<i32> for LetExpression<i32> {
+    fn get_value(&self, context: &Context) -> Result<i32, CustomError> {
+        self.eval(context)
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        self.serialize_as(LeafType::Number)
+    }
+}
+
+impl ToValue<BigDecimal> for LetExpression<i32> {
+    fn get_value(&self, context: &Context) -> Result<BigDecimal, CustomError> {
+        Ok(BigDecimal::from(self.eval(context)?))
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        self.serialize_as(LeafType::Number)
+    }
+}
+
+impl ToValue<String> for LetExpression<i32> {
+    fn get_value(&self, context: &Context) -> Result<String, CustomError> {
+        Ok(self.eval(context)?.to_string())
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        self.serialize_as(LeafType::Number)
+    }
+}
+
+impl ToValue<i32> for LetExpression<BigDecimal> {
+    fn get_value(&self, context: &Context) -> Result<i32, CustomError> {
+        context.to_number(&self.eval(context)?)
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        self.serialize_as(LeafType::Decimal)
+    }
+}
+
+impl ToValue<BigDecimal> for LetExpression<BigDecimal> {
+    fn get_value(&self, context: &Context) -> Result<BigDecimal, CustomError> {
+        self.eval(context)
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        self.serialize_as(LeafType::Decimal)
+    }
+}
+
+impl ToValue<String> for LetExpression<BigDecimal> {
+    fn get_value(&self, context: &Context) -> Result<String, CustomError> {
+        Ok(self.eval(context)?.to_string())
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        self.serialize_as(LeafType::Decimal)
+    }
+}
+
+impl ToValue<String> for LetExpression<String> {
+    fn get_value(&self, context: &Context) -> Result<String, CustomError> {
+        self.eval(context)
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        self.serialize_as(LeafType::Text)
+    }
+}
+
+impl ToValue<bool> for LetExpression<bool> {
+    fn get_value(&self, context: &Context) -> Result<bool, CustomError> {
+        self.eval(context)
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        self.serialize_as(LeafType::Boolean)
+    }
+}
+
+impl ToValue<String> for LetExpression<bool> {
+    fn get_value(&self, context: &Context) -> Result<String, CustomError> {
+        Ok(self.eval(context)?.to_string())
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        self.serialize_as(LeafType::Boolean)
+    }
+}
+
+#[cfg(test)]
+mod binding_tests {
+    use std::collections::HashMap;
+    use std::str::FromStr;
+
+    use super::*;
+    use crate::arithmetic::DecimalArithmeticExpression;
+    use crate::dot::DotExpression;
+    use crate::error::{Language, Message};
+    use crate::expression::LispExpression;
+
+    #[test]
+    fn test_let_expression() {
+        let symbols: HashMap<String, Symbol> = vec![
+            (
+                "price".to_string(),
+                Symbol {
+                    value: Some(Leaf::Decimal(BigDecimal::from_str("12.50").unwrap())),
+                    values: HashMap::new(),
+                },
+            ),
+            (
+                "quantity".to_string(),
+                Symbol {
+                    value: Some(Leaf::Number(4)),
+                    values: HashMap::new(),
+                },
+            ),
+        ]
+        .into_iter()
+        .collect();
+        let expr: LetExpression<BigDecimal> = LetExpression {
+            bindings: vec![
+                (
+                    "subtotal".to_string(),
+                    Binding::Decimal(Box::new(DecimalArithmeticExpression::Multiply((
+                        Box::new(DotExpression {
+                            path: vec!["price".to_string()],
+                        }),
+                        vec![Box::new(DotExpression {
+                            path: vec!["quantity".to_string()],
+                        })],
+                    )))),
+                ),
+                (
+                    "tax".to_string(),
+                    Binding::Decimal(Box::new(DecimalArithmeticExpression::Multiply((
+                        Box::new(DotExpression {
+                            path: vec!["subtotal".to_string()],
+                        }),
+                        vec![Box::new(BigDecimal::from_str("0.1").unwrap())],
+                    )))),
+                ),
+            ],
+            body: Box::new(DecimalArithmeticExpression::Add((
+                Box::new(DotExpression {
+                    path: vec!["subtotal".to_string()],
+                }),
+                vec![Box::new(DotExpression {
+                    path: vec!["tax".to_string()],
+                })],
+            ))),
+        };
+        let res: BigDecimal = expr.get_value(&Context::new(&symbols)).unwrap();
+        assert_eq!(BigDecimal::from_str("55").unwrap(), res);
+        // eval == serialize.deserialize.eval
+        assert_eq!(
+            res,
+            (match LispExpression::deserialize(
+                (&expr as &dyn ToValue<BigDecimal>).serialize().unwrap()
+            ) {
+                Ok(v) => match v {
+                    LispExpression::DecimalLetExpression(v) =>
+                        (&v as &dyn ToValue<BigDecimal>).get_value(&Context::new(&symbols)),
+                    _ => Err(CustomError::Message(Message::ErrUnexpected)),
+                },
+                Err(_) => Err(CustomError::Message(Message::ErrUnexpected)),
+            })
+            .unwrap()
+        );
+        // serialize == serialize.deserialize.serialize
+        assert_eq!(
+            (&expr as &dyn ToValue<BigDecimal>).serialize().unwrap(),
+            match LispExpression::deserialize(
+                (&expr as &dyn ToValue<BigDecimal>).serialize().unwrap()
+            ) {
+                Ok(v) => match v {
+                    LispExpression::DecimalLetExpression(v) =>
+                        (&v as &dyn ToValue<BigDecimal>).serialize().unwrap(),
+                    _ => Value::Null,
+                },
+                Err(_) => Value::Null,
+            }
+        );
+        let eval = |text: &str| match LispExpression::parse(text)
+            .unwrap()
+            .as_text(&Context::new(&symbols))
+        {
+            Ok(v) => json!(v),
+            Err(e) => e.serialize(&Language::English),
+        };
+        // bindings shadow symbols, and inner scopes shadow outer ones
+        assert_eq!(
+            json!("3"),
+            eval("(let:Number [[quantity Number 3]] (. quantity))")
+        );
+        assert_eq!(
+            json!("b"),
+            eval("(let:Text [[x Text a]] (let:Text [[x Text b]] (. x)))")
+        );
+        assert_eq!(
+            json!("true"),
+            eval("(let:Boolean [[x Number 2] [y Boolean (==:Number (. x) 2)]] (. y))")
+        );
+        assert_eq!(
+            json!("7"),
+            eval("(let:Number [[x Number 3]] (+:Number (let:Number [[y Number 4]] (. y)) (. x)))")
+        );
+        // bindings are only visible within the let that made them
+        assert_eq!(
+            json!({"/args/1/args/0": "Symbol not found"}),
+            eval("(+:Number (let:Number [[x Number 1]] (. x)) (. x))")
+        );
+        assert_eq!(
+            json!({"/args/0/0/2/args/0": "Symbol not found"}),
+            eval("(let:Number [[x Number (. y)] [y Number 1]] (. x))")
+        );
+        assert_eq!(
+            json!({"/args/1/args/0": "Symbol not found"}),
+            eval("(let:Number [] (. x))")
+        );
+        assert_eq!(
+            json!({"/args/0/0/1": "Type mismatch"}),
+            match LispExpression::deserialize(
+                json!({"op": "let", "type": "Text", "args": [[["x", "Time", "a"]], "b"]})
+            ) {
+                Ok(_) => Value::Null,
+                Err(e) => e.serialize(&Language::English),
+            }
+        );
+        assert_eq!(
+            json!({"/args/0/0": "Wrong number of arguments"}),
+            match LispExpression::deserialize(
+                json!({"op": "let", "type": "Text", "args": [[["x", "a"]], "b"]})
+            ) {
+                Ok(_) => Value::Null,
+                Err(e) => e.serialize(&Language::English),
+            }
+        );
+    }
+}
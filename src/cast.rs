@@ -0,0 +1,352 @@
+use crate::arithmetic::within_scale;
+use crate::binding::Binding;
+use crate::context::{Context, RoundingMode};
+use crate::error::{CustomError, Message};
+use crate::symbol::Leaf;
+use crate::temporal::Temporal;
+use crate::value::{eval_arg, ToValue};
+use bigdecimal::{BigDecimal, ToPrimitive, Zero};
+use serde_json::{json, Value};
+use std::str::FromStr;
+
+// CAST OPS
+
+// Explicit conversions between leaf types, which fail instead of guessing, and keep nulls.
+// {"op": "to_number", "args": [[type, value], mode]}, the mode rounding decimals when given.
+// {"op": "parse_number", "args": [text, default]}, the default replacing text that does not parse.
+pub enum CastExpression {
+    ToNumber {
+        value: Binding,
+        mode: Option<RoundingMode>,
+    },
+    ToDecimal(Binding),
+    ToText(Binding),
+    ToBoolean(Binding),
+    ParseNumber {
+        text: Box<dyn ToValue<String>>,
+        default: Option<Box<dyn ToValue<i32>>>,
+    },
+    ParseDecimal {
+        text: Box<dyn ToValue<String>>,
+        default: Option<Box<dyn ToValue<BigDecimal>>>,
+    },
+}
+
+impl CastExpression {
+    // Texts convert as the decimal they spell, ignoring surrounding whitespace.
+    // Like literals, they must be within MAX_SCALE, as they come from symbol data.
+    pub(crate) fn parse(text: &str) -> Result<BigDecimal, CustomError> {
+        match BigDecimal::from_str(text.trim()) {
+            Ok(v) if within_scale(&v) => Ok(v),
+            _ => Err(CustomError::Message(Message::ErrConversion)),
+        }
+    }
+
+    pub(crate) fn eval(&self, context: &Context) -> Result<Leaf, CustomError> {
+        match self {
+            CastExpression::ToNumber { value, mode } => {
+                let v: BigDecimal = match value.eval(context).map_err(|e| e.at("/args/0/1"))? {
+                    Leaf::Number(v) => return Ok(Leaf::Number(v)),
+                    Leaf::Decimal(v) => v,
+                    Leaf::Text(v) => Self::parse(&v).map_err(|e| e.at("/args/0"))?,
+                    Leaf::Boolean(v) => return Ok(Leaf::Number(v as i32)),
+                    Leaf::Null => return Ok(Leaf::Null),
+                    Leaf::Date(_) | Leaf::DateTime(_) | Leaf::Duration(_) | Leaf::List(_) => {
+                        return Err(CustomError::Message(Message::ErrTypeMismatch).at("/args/0"))
+                    }
+                };
+                match mode.unwrap_or(context.rounding).round(&v, 0).to_i32() {
+                    Some(v1) => Ok(Leaf::Number(v1)),
+                    None => Err(CustomError::Message(Message::ErrOverflow).at("/args/0")),
+                }
+            }
+            CastExpression::ToDecimal(value) => {
+                match value.eval(context).map_err(|e| e.at("/args/0/1"))? {
+                    Leaf::Number(v) => Ok(Leaf::Decimal(BigDecimal::from(v))),
+                    Leaf::Decimal(v) => Ok(Leaf::Decimal(v)),
+                    Leaf::Text(v) => {
+                        Ok(Leaf::Decimal(Self::parse(&v).map_err(|e| e.at("/args/0"))?))
+                    }
+                    Leaf::Boolean(v) => Ok(Leaf::Decimal(BigDecimal::from(v as i32))),
+                    Leaf::Null => Ok(Leaf::Null),
+                    Leaf::Date(_) | Leaf::DateTime(_) | Leaf::Duration(_) | Leaf::List(_) => {
+                        Err(CustomError::Message(Message::ErrTypeMismatch).at("/args/0"))
+                    }
+                }
+            }
+            CastExpression::ToText(value) => {
+                match value.eval(context).map_err(|e| e.at("/args/0/1"))? {
+                    Leaf::Number(v) => Ok(Leaf::Text(v.to_string())),
+                    Leaf::Decimal(v) => Ok(Leaf::Text(v.to_string())),
+                    Leaf::Text(v) => Ok(Leaf::Text(v)),
+                    Leaf::Boolean(v) => Ok(Leaf::Text(v.to_string())),
+                    Leaf::Date(v) => Ok(Leaf::Text(v.to_text())),
+                    Leaf::DateTime(v) => Ok(Leaf::Text(v.to_text())),
+                    Leaf::Duration(v) => Ok(Leaf::Text(v.to_text())),
+                    Leaf::Null => Ok(Leaf::Null),
+                    Leaf::List(_) => {
+                        Err(CustomError::Message(Message::ErrTypeMismatch).at("/args/0"))
+                    }
+                }
+            }
+            // Numbers are true unless zero, and texts have to spell true or false
+            CastExpression::ToBoolean(value) => {
+                match value.eval(context).map_err(|e| e.at("/args/0/1"))? {
+                    Leaf::Number(v) => Ok(Leaf::Boolean(v != 0)),
+                    Leaf::Decimal(v) => Ok(Leaf::Boolean(!v.is_zero())),
+                    Leaf::Text(v) => match v.trim().parse::<bool>() {
+                        Ok(v1) => Ok(Leaf::Boolean(v1)),
+                        Err(_) => Err(CustomError::Message(Message::ErrConversion).at("/args/0")),
+                    },
+                    Leaf::Boolean(v) => Ok(Leaf::Boolean(v)),
+                    Leaf::Null => Ok(Leaf::Null),
+                    Leaf::Date(_) | Leaf::DateTime(_) | Leaf::Duration(_) | Leaf::List(_) => {
+                        Err(CustomError::Message(Message::ErrTypeMismatch).at("/args/0"))
+                    }
+                }
+            }
+            CastExpression::ParseNumber { text, default } => {
+                match (
+                    eval_arg(text.as_ref(), 0, context)?.trim().parse::<i32>(),
+                    default,
+                ) {
+                    (Ok(v), _) => Ok(Leaf::Number(v)),
+                    (Err(_), Some(v)) => Ok(Leaf::Number(eval_arg(v.as_ref(), 1, context)?)),
+                    (Err(_), None) => {
+                        Err(CustomError::Message(Message::ErrConversion).at("/args/0"))
+                    }
+                }
+            }
+            CastExpression::ParseDecimal { text, default } => {
+                match (Self::parse(&eval_arg(text.as_ref(), 0, context)?), default) {
+                    (Ok(v), _) => Ok(Leaf::Decimal(v)),
+                    (Err(_), Some(v)) => Ok(Leaf::Decimal(eval_arg(v.as_ref(), 1, context)?)),
+                    (Err(e), None) => Err(e.at("/args/0")),
+                }
+            }
+        }
+    }
+
+    pub(crate) fn serialize(&self) -> Result<Value, CustomError> {
+        let typed = |value: &Binding| -> Result<Value, CustomError> {
+            let (value_type, v) = value.serialize()?;
+            Ok(json!([value_type.serialize(), v]))
+        };
+        let (op, args): (&str, Vec<Value>) = match self {
+            CastExpression::ToNumber { value, mode } => {
+                let mut args = vec![typed(value)?];
+                if let Some(v) = mode {
+                    args.push(v.serialize());
+                }
+                ("to_number", args)
+            }
+            CastExpression::ToDecimal(value) => ("to_decimal", vec![typed(value)?]),
+            CastExpression::ToText(value) => ("to_text", vec![typed(value)?]),
+            CastExpression::ToBoolean(value) => ("to_boolean", vec![typed(value)?]),
+            CastExpression::ParseNumber { text, default } => {
+                let mut args = vec![text.serialize()?];
+                if let Some(v) = default {
+                    args.push(v.serialize()?);
+                }
+                ("parse_number", args)
+            }
+            CastExpression::ParseDecimal { text, default } => {
+                let mut args = vec![text.serialize()?];
+                if let Some(v) = default {
+                    args.push(v.serialize()?);
+                }
+                ("parse_decimal", args)
+            }
+        };
+        Ok(json!({
+            "op": op,
+            "args": args
+        }))
+    }
+}
+
+impl ToValue<i32> for CastExpression {
+    fn get_value(&self, context: &Context) -> Result<i32, CustomError> {
+        match self.eval(context)? {
+            Leaf::Number(v) => Ok(v),
+            Leaf::Null => Err(CustomError::Message(Message::ErrNull)),
+            _ => Err(CustomError::Message(Message::ErrTypeMismatch)),
+        }
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        self.serialize()
+    }
+}
+
+impl ToValue<BigDecimal> for CastExpression {
+    fn get_value(&self, context: &Context) -> Result<BigDecimal, CustomError> {
+        match self.eval(context)? {
+            Leaf::Number(v) => Ok(BigDecimal::from(v)),
+            Leaf::Decimal(v) => Ok(v),
+            Leaf::Null => Err(CustomError::Message(Message::ErrNull)),
+            _ => Err(CustomError::Message(Message::ErrTypeMismatch)),
+        }
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        self.serialize()
+    }
+}
+
+impl ToValue<String> for CastExpression {
+    fn get_value(&self, context: &Context) -> Result<String, CustomError> {
+        match self.eval(context)? {
+            Leaf::Number(v) => Ok(v.to_string()),
+            Leaf::Decimal(v) => Ok(v.to_string()),
+            Leaf::Text(v) => Ok(v),
+            Leaf::Boolean(v) => Ok(v.to_string()),
+            Leaf::Date(v) => Ok(v.to_text()),
+            Leaf::DateTime(v) => Ok(v.to_text()),
+            Leaf::Duration(v) => Ok(v.to_text()),
+            Leaf::Null => Err(CustomError::Message(Message::ErrNull)),
+            Leaf::List(_) => Err(CustomError::Message(Message::ErrTypeMismatch)),
+        }
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        self.serialize()
+    }
+}
+
+impl ToValue<bool> for CastExpression {
+    fn get_value(&self, context: &Context) -> Result<bool, CustomError> {
+        match self.eval(context)? {
+            Leaf::Boolean(v) => Ok(v),
+            Leaf::Null => Err(CustomError::Message(Message::ErrNull)),
+            _ => Err(CustomError::Message(Message::ErrTypeMismatch)),
+        }
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        self.serialize()
+    }
+}
+
+#[cfg(test)]
+mod cast_tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::dot::DotExpression;
+    use crate::error::Language;
+    use crate::expression::LispExpression;
+    use crate::symbol::Symbol;
+
+    #[test]
+    fn test_cast_expression() {
+        let symbols: HashMap<String, Symbol> = vec![(
+            "quantity".to_string(),
+            Symbol {
+                value: Some(Leaf::Text(" 12.5 ".to_string())),
+                values: HashMap::new(),
+            },
+        )]
+        .into_iter()
+        .collect();
+        let expr = CastExpression::ToNumber {
+            value: Binding::Text(Box::new(DotExpression {
+                path: vec!["quantity".to_string()],
+            })),
+            mode: Some(RoundingMode::HalfUp),
+        };
+        let res: i32 = expr.get_value(&Context::new(&symbols)).unwrap();
+        assert_eq!(13, res);
+        // eval == serialize.deserialize.eval
+        assert_eq!(
+            res,
+            (match LispExpression::deserialize((&expr as &dyn ToValue<i32>).serialize().unwrap()) {
+                Ok(v) => match v {
+                    LispExpression::CastExpression(v) =>
+                        (&v as &dyn ToValue<i32>).get_value(&Context::new(&symbols)),
+                    _ => Err(CustomError::Message(Message::ErrUnexpected)),
+                },
+                Err(_) => Err(CustomError::Message(Message::ErrUnexpected)),
+            })
+            .unwrap()
+        );
+        // serialize == serialize.deserialize.serialize
+        assert_eq!(
+            (&expr as &dyn ToValue<i32>).serialize().unwrap(),
+            match LispExpression::deserialize((&expr as &dyn ToValue<i32>).serialize().unwrap()) {
+                Ok(v) => match v {
+                    LispExpression::CastExpression(v) =>
+                        (&v as &dyn ToValue<i32>).serialize().unwrap(),
+                    _ => Value::Null,
+                },
+                Err(_) => Value::Null,
+            }
+        );
+        let eval = |text: &str| match LispExpression::parse(text)
+            .unwrap()
+            .as_text(&Context::new(&symbols))
+        {
+            Ok(v) => json!(v),
+            Err(e) => e.serialize(&Language::English),
+        };
+        // Decimals are rounded using the rounding mode of the context unless one is given
+        assert_eq!(json!("12"), eval("(to_number [Text (. quantity)])"));
+        assert_eq!(json!("12"), eval("(to_number [Decimal 12.5] Truncate)"));
+        assert_eq!(json!("1"), eval("(to_number [Boolean true])"));
+        assert_eq!(json!("12.5"), eval("(to_decimal [Text (. quantity)])"));
+        assert_eq!(json!("7"), eval("(to_decimal [Number 7])"));
+        assert_eq!(json!("0"), eval("(to_decimal [Boolean false])"));
+        assert_eq!(json!("2.50"), eval("(to_text [Decimal 2.50])"));
+        assert_eq!(json!("false"), eval("(to_text [Boolean false])"));
+        assert_eq!(json!("true"), eval("(to_boolean [Decimal 0.01])"));
+        assert_eq!(json!("false"), eval("(to_boolean [Number 0])"));
+        assert_eq!(json!("true"), eval("(to_boolean [Text \" true\"])"));
+        assert_eq!(json!("42"), eval("(parse_number \" 42 \")"));
+        assert_eq!(json!("-1"), eval("(parse_number (. quantity) -1)"));
+        assert_eq!(json!("12.5"), eval("(parse_decimal (. quantity) 0)"));
+        assert_eq!(json!("0"), eval("(parse_decimal abc 0)"));
+        assert_eq!(
+            json!({"/args/0": "Conversion failed"}),
+            eval("(to_number [Text abc])")
+        );
+        assert_eq!(
+            json!({"/args/0": "Conversion failed"}),
+            eval("(to_number [Text 1e999999999])")
+        );
+        assert_eq!(
+            json!({"/args/0/args/0": "Conversion failed"}),
+            eval("(+:Decimal (parse_decimal \"1e-999999999\") 1)")
+        );
+        assert_eq!(json!("0"), eval("(parse_decimal 1e-1025 0)"));
+        assert_eq!(
+            json!({"/args/0": "Conversion failed"}),
+            eval("(to_boolean [Text yes])")
+        );
+        assert_eq!(
+            json!({"/args/0": "Conversion failed"}),
+            eval("(parse_number 2.5)")
+        );
+        assert_eq!(
+            json!({"/args/0": "Arithmetic overflow"}),
+            eval("(to_number [Decimal 2147483647.5])")
+        );
+        assert_eq!(
+            json!({"/args/0/1/args/0": "Symbol not found"}),
+            eval("(to_text [Number (. missing)])")
+        );
+        assert_eq!(
+            json!({"/args/1": "Invalid literal"}),
+            match LispExpression::parse("(to_number [Decimal 1.5] Sideways)") {
+                Ok(_) => Value::Null,
+                Err(e) => e.serialize(&Language::English),
+            }
+        );
+        assert_eq!(
+            json!({"/args": "Wrong number of arguments"}),
+            match LispExpression::parse("(to_text [Number 1] [Number 2])") {
+                Ok(_) => Value::Null,
+                Err(e) => e.serialize(&Language::English),
+            }
+        );
+    }
+}
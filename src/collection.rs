@@ -0,0 +1,580 @@
+use crate::arithmetic::{ArithmeticOperator, ArithmeticResult};
+use crate::binding::Binding;
+use crate::context::Context;
+use crate::error::{CustomError, Message};
+use crate::list::List;
+use crate::symbol::{Leaf, LeafType, Symbol};
+use crate::temporal::Temporal;
+use crate::value::{eval_arg, ToValue};
+use bigdecimal::{BigDecimal, ToPrimitive};
+use serde_json::{json, Value};
+
+// COLLECTION OPS
+
+// A body evaluated once for each item of a list, with the item bound to the name.
+// The bound item is read with dot expressions, as in (. l qty), like any other symbol.
+pub struct Lambda {
+    pub(crate) name: String,
+    pub(crate) body: Binding,
+}
+
+impl Lambda {
+    pub(crate) fn apply(&self, item: &Symbol, context: &Context) -> Result<Leaf, CustomError> {
+        let mut scope = context.scope();
+        scope.bindings.insert(self.name.to_string(), item.clone());
+        self.body.eval(&scope)
+    }
+
+    pub(crate) fn serialize(&self) -> Result<Vec<Value>, CustomError> {
+        Ok(vec![json!(self.name), self.body.serialize()?.1])
+    }
+}
+
+// Operations over the items of a list, each lambda being [name, body].
+// {"op": "map", "type": T, "args": [list, name, body]}, a list of the results
+// {"op": "filter", "args": [list, name, predicate]}, the items satisfying the predicate
+// {"op": "any", "args": [list, name, predicate]}, and likewise all
+// {"op": "count", "args": [list, name?, predicate?]}, items satisfying the predicate or not null
+// {"op": "sum", "type": Number | Decimal, "args": [list, name?, body?]}, and likewise avg
+// {"op": "reduce", "type": T, "args": [list, item, accumulator, initial, body]}
+// Body errors are located at the body, whichever item they occurred for.
+pub enum CollectionExpression {
+    Map {
+        result_type: LeafType,
+        list: Box<dyn ToValue<List>>,
+        lambda: Lambda,
+    },
+    Filter {
+        list: Box<dyn ToValue<List>>,
+        lambda: Lambda,
+    },
+    Any {
+        list: Box<dyn ToValue<List>>,
+        lambda: Lambda,
+    },
+    All {
+        list: Box<dyn ToValue<List>>,
+        lambda: Lambda,
+    },
+    Count {
+        list: Box<dyn ToValue<List>>,
+        lambda: Option<Lambda>,
+    },
+    Sum {
+        result_type: LeafType,
+        list: Box<dyn ToValue<List>>,
+        lambda: Option<Lambda>,
+    },
+    Avg {
+        result_type: LeafType,
+        list: Box<dyn ToValue<List>>,
+        lambda: Option<Lambda>,
+    },
+    Reduce {
+        result_type: LeafType,
+        list: Box<dyn ToValue<List>>,
+        item: String,
+        accumulator: String,
+        initial: Binding,
+        body: Binding,
+    },
+}
+
+impl CollectionExpression {
+    // The value of each item, or the result of the lambda applied to it
+    pub(crate) fn values(
+        items: &[Symbol],
+        lambda: Option<&Lambda>,
+        context: &Context,
+    ) -> Result<Vec<Leaf>, CustomError> {
+        items
+            .iter()
+            .map(|val| match lambda {
+                Some(v) => v.apply(val, context).map_err(|e| e.at("/args/2")),
+                None => Ok(val.value.clone().unwrap_or(Leaf::Null)),
+            })
+            .collect()
+    }
+
+    // Nulls are skipped, so that the sum of an empty list is 0
+    pub(crate) fn sum(
+        values: &[Leaf],
+        result_type: LeafType,
+        context: &Context,
+    ) -> Result<(Leaf, usize), CustomError> {
+        let mut result = match result_type {
+            LeafType::Number => ArithmeticResult::Number(0),
+            _ => ArithmeticResult::Decimal(BigDecimal::from(0)),
+        };
+        let mut count: usize = 0;
+        for val in values.iter().filter(|val| **val != Leaf::Null) {
+            result = match (result, val) {
+                (ArithmeticResult::Number(v), Leaf::Number(v1)) => {
+                    ArithmeticOperator::Add.apply_number(v, *v1, context.overflow)?
+                }
+                (ArithmeticResult::Number(v), Leaf::Decimal(v1)) => ArithmeticOperator::Add
+                    .apply_number(v, context.to_number(v1)?, context.overflow)?,
+                (ArithmeticResult::Decimal(v), Leaf::Number(v1)) => {
+                    ArithmeticResult::Decimal(v + BigDecimal::from(*v1))
+                }
+                (ArithmeticResult::Decimal(v), Leaf::Decimal(v1)) => {
+                    ArithmeticResult::Decimal(v + v1)
+                }
+                _ => return Err(CustomError::Message(Message::ErrTypeMismatch)),
+            };
+            count += 1;
+        }
+        match result {
+            ArithmeticResult::Number(v) => Ok((Leaf::Number(v), count)),
+            ArithmeticResult::Decimal(v) => Ok((Leaf::Decimal(v), count)),
+            ArithmeticResult::Text(_) => Err(CustomError::Message(Message::ErrUnexpected)),
+        }
+    }
+
+    // Predicates over items are three valued, a null result leaves any and all undecided
+    pub(crate) fn eval(&self, context: &Context) -> Result<Leaf, CustomError> {
+        match self {
+            CollectionExpression::Map { list, lambda, .. } => {
+                let items = eval_arg(list.as_ref(), 0, context)?;
+                Ok(Leaf::List(
+                    Self::values(&items, Some(lambda), context)?
+                        .into_iter()
+                        .map(Symbol::from_leaf)
+                        .collect(),
+                ))
+            }
+            CollectionExpression::Filter { list, lambda } => {
+                let items = eval_arg(list.as_ref(), 0, context)?;
+                let values = Self::values(&items, Some(lambda), context)?;
+                Ok(Leaf::List(
+                    items
+                        .into_iter()
+                        .zip(values)
+                        .filter(|(_, v)| *v == Leaf::Boolean(true))
+                        .map(|(v, _)| v)
+                        .collect(),
+                ))
+            }
+            CollectionExpression::Any { list, lambda }
+            | CollectionExpression::All { list, lambda } => {
+                let expected = matches!(self, CollectionExpression::Any { .. });
+                let items = eval_arg(list.as_ref(), 0, context)?;
+                let mut result = Leaf::Boolean(!expected);
+                for val in items.iter() {
+                    match lambda.apply(val, context).map_err(|e| e.at("/args/2"))? {
+                        Leaf::Boolean(v) if v == expected => return Ok(Leaf::Boolean(v)),
+                        Leaf::Null => result = Leaf::Null,
+                        _ => {}
+                    }
+                }
+                Ok(result)
+            }
+            // Without a predicate, items count unless they are null, items holding symbols count
+            CollectionExpression::Count { list, lambda } => {
+                let items = eval_arg(list.as_ref(), 0, context)?;
+                let count = match lambda {
+                    Some(v) => Self::values(&items, Some(v), context)?
+                        .iter()
+                        .filter(|val| **val == Leaf::Boolean(true))
+                        .count(),
+                    None => items
+                        .iter()
+                        .filter(|val| match &val.value {
+                            Some(Leaf::Null) | None => !val.values.is_empty(),
+                            Some(_) => true,
+                        })
+                        .count(),
+                };
+                match count.to_i32() {
+                    Some(v) => Ok(Leaf::Number(v)),
+                    None => Err(CustomError::Message(Message::ErrOverflow)),
+                }
+            }
+            CollectionExpression::Sum {
+                result_type,
+                list,
+                lambda,
+            } => {
+                let items = eval_arg(list.as_ref(), 0, context)?;
+                let values = Self::values(&items, lambda.as_ref(), context)?;
+                match Self::sum(&values, *result_type, context)?.0 {
+                    Leaf::Decimal(v) => Ok(Leaf::Decimal(context.rescale(v))),
+                    v => Ok(v),
+                }
+            }
+            // The average of a list without values is null
+            CollectionExpression::Avg {
+                result_type,
+                list,
+                lambda,
+            } => {
+                let items = eval_arg(list.as_ref(), 0, context)?;
+                let values = Self::values(&items, lambda.as_ref(), context)?;
+                let (total, count) = Self::sum(&values, LeafType::Decimal, context)?;
+                let result = match (total, count) {
+                    (_, 0) => return Ok(Leaf::Null),
+                    (Leaf::Decimal(v), _) => v / BigDecimal::from(count as u64),
+                    _ => return Err(CustomError::Message(Message::ErrUnexpected)),
+                };
+                match result_type {
+                    LeafType::Number => Ok(Leaf::Number(context.to_number(&result)?)),
+                    _ => Ok(Leaf::Decimal(context.rescale(result))),
+                }
+            }
+            CollectionExpression::Reduce {
+                list,
+                item,
+                accumulator,
+                initial,
+                body,
+                ..
+            } => {
+                let items = eval_arg(list.as_ref(), 0, context)?;
+                let mut result = initial.eval(context).map_err(|e| e.at("/args/3"))?;
+                for val in items.into_iter() {
+                    let mut scope = context.scope();
+                    scope.bindings.insert(item.to_string(), val);
+                    scope
+                        .bindings
+                        .insert(accumulator.to_string(), Symbol::from_leaf(result));
+                    result = body.eval(&scope).map_err(|e| e.at("/args/4"))?;
+                }
+                Ok(result)
+            }
+        }
+    }
+
+    pub(crate) fn serialize(&self) -> Result<Value, CustomError> {
+        let (op, result_type, list, mut args): (&str, Option<LeafType>, _, Vec<Value>) = match self
+        {
+            CollectionExpression::Map {
+                result_type,
+                list,
+                lambda,
+            } => ("map", Some(*result_type), list, lambda.serialize()?),
+            CollectionExpression::Filter { list, lambda } => {
+                ("filter", None, list, lambda.serialize()?)
+            }
+            CollectionExpression::Any { list, lambda } => ("any", None, list, lambda.serialize()?),
+            CollectionExpression::All { list, lambda } => ("all", None, list, lambda.serialize()?),
+            CollectionExpression::Count { list, lambda } => match lambda {
+                Some(v) => ("count", None, list, v.serialize()?),
+                None => ("count", None, list, vec![]),
+            },
+            CollectionExpression::Sum {
+                result_type,
+                list,
+                lambda,
+            } => match lambda {
+                Some(v) => ("sum", Some(*result_type), list, v.serialize()?),
+                None => ("sum", Some(*result_type), list, vec![]),
+            },
+            CollectionExpression::Avg {
+                result_type,
+                list,
+                lambda,
+            } => match lambda {
+                Some(v) => ("avg", Some(*result_type), list, v.serialize()?),
+                None => ("avg", Some(*result_type), list, vec![]),
+            },
+            CollectionExpression::Reduce {
+                result_type,
+                list,
+                item,
+                accumulator,
+                initial,
+                body,
+            } => (
+                "reduce",
+                Some(*result_type),
+                list,
+                vec![
+                    json!(item),
+                    json!(accumulator),
+                    initial.serialize()?.1,
+                    body.serialize()?.1,
+                ],
+            ),
+        };
+        args.insert(0, list.serialize()?);
+        match result_type {
+            Some(v) => Ok(json!({
+                "op": op,
+                "type": v.serialize(),
+                "args": args
+            })),
+            None => Ok(json!({
+                "op": op,
+                "args": args
+            })),
+        }
+    }
+}
+
+impl ToValue<i32> for CollectionExpression {
+    fn get_value(&self, context: &Context) -> Result<i32, CustomError> {
+        match self.eval(context)? {
+            Leaf::Number(v) => Ok(v),
+            Leaf::Decimal(v) => context.to_number(&v),
+            Leaf::Null => Err(CustomError::Message(Message::ErrNull)),
+            _ => Err(CustomError::Message(Message::ErrTypeMismatch)),
+        }
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        self.serialize()
+    }
+}
+
+impl ToValue<BigDecimal> for CollectionExpression {
+    fn get_value(&self, context: &Context) -> Result<BigDecimal, CustomError> {
+        match self.eval(context)? {
+            Leaf::Number(v) => Ok(BigDecimal::from(v)),
+            Leaf::Decimal(v) => Ok(v),
+            Leaf::Null => Err(CustomError::Message(Message::ErrNull)),
+            _ => Err(CustomError::Message(Message::ErrTypeMismatch)),
+        }
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        self.serialize()
+    }
+}
+
+impl ToValue<String> for CollectionExpression {
+    fn get_value(&self, context: &Context) -> Result<String, CustomError> {
+        match self.eval(context)? {
+            Leaf::Number(v) => Ok(v.to_string()),
+            Leaf::Decimal(v) => Ok(v.to_string()),
+            Leaf::Text(v) => Ok(v),
+            Leaf::Boolean(v) => Ok(v.to_string()),
+            Leaf::Date(v) => Ok(v.to_text()),
+            Leaf::DateTime(v) => Ok(v.to_text()),
+            Leaf::Duration(v) => Ok(v.to_text()),
+            Leaf::Null => Err(CustomError::Message(Message::ErrNull)),
+            Leaf::List(_) => Err(CustomError::Message(Message::ErrTypeMismatch)),
+        }
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        self.serialize()
+    }
+}
+
+impl ToValue<bool> for CollectionExpression {
+    fn get_value(&self, context: &Context) -> Result<bool, CustomError> {
+        match self.eval(context)? {
+            Leaf::Boolean(v) => Ok(v),
+            Leaf::Null => Err(CustomError::Message(Message::ErrNull)),
+            _ => Err(CustomError::Message(Message::ErrTypeMismatch)),
+        }
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        self.serialize()
+    }
+}
+
+impl ToValue<List> for CollectionExpression {
+    fn get_value(&self, context: &Context) -> Result<List, CustomError> {
+        match self.eval(context)? {
+            Leaf::List(v) => Ok(v),
+            Leaf::Null => Err(CustomError::Message(Message::ErrNull)),
+            _ => Err(CustomError::Message(Message::ErrTypeMismatch)),
+        }
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        self.serialize()
+    }
+}
+
+#[cfg(test)]
+mod collection_tests {
+    use std::collections::HashMap;
+    use std::str::FromStr;
+
+    use super::*;
+    use crate::dot::DotExpression;
+    use crate::error::Language;
+    use crate::expression::LispExpression;
+
+    #[test]
+    fn test_collection_expression() {
+        let line = |qty: Option<i32>, price: &str| Symbol {
+            value: None,
+            values: vec![
+                (
+                    "qty".to_string(),
+                    Symbol {
+                        value: qty.map(Leaf::Number),
+                        values: HashMap::new(),
+                    },
+                ),
+                (
+                    "price".to_string(),
+                    Symbol {
+                        value: Some(Leaf::Decimal(BigDecimal::from_str(price).unwrap())),
+                        values: HashMap::new(),
+                    },
+                ),
+            ]
+            .into_iter()
+            .collect(),
+        };
+        let symbols: HashMap<String, Symbol> = vec![
+            (
+                "lines".to_string(),
+                Symbol {
+                    value: Some(Leaf::List(vec![
+                        line(Some(2), "1.50"),
+                        line(Some(150), "0.10"),
+                        line(None, "3"),
+                    ])),
+                    values: HashMap::new(),
+                },
+            ),
+            (
+                "empty".to_string(),
+                Symbol {
+                    value: Some(Leaf::List(vec![])),
+                    values: HashMap::new(),
+                },
+            ),
+            (
+                "limit".to_string(),
+                Symbol {
+                    value: Some(Leaf::Number(100)),
+                    values: HashMap::new(),
+                },
+            ),
+        ]
+        .into_iter()
+        .collect();
+        let expr = CollectionExpression::Count {
+            list: Box::new(DotExpression {
+                path: vec!["lines".to_string()],
+            }),
+            lambda: None,
+        };
+        let res: i32 = expr.get_value(&Context::new(&symbols)).unwrap();
+        assert_eq!(3, res);
+        // eval == serialize.deserialize.eval
+        assert_eq!(
+            res,
+            (match LispExpression::deserialize((&expr as &dyn ToValue<i32>).serialize().unwrap()) {
+                Ok(v) => match v {
+                    LispExpression::CollectionExpression(v) =>
+                        (&v as &dyn ToValue<i32>).get_value(&Context::new(&symbols)),
+                    _ => Err(CustomError::Message(Message::ErrUnexpected)),
+                },
+                Err(_) => Err(CustomError::Message(Message::ErrUnexpected)),
+            })
+            .unwrap()
+        );
+        // serialize == serialize.deserialize.serialize
+        assert_eq!(
+            (&expr as &dyn ToValue<i32>).serialize().unwrap(),
+            match LispExpression::deserialize((&expr as &dyn ToValue<i32>).serialize().unwrap()) {
+                Ok(v) => match v {
+                    LispExpression::CollectionExpression(v) =>
+                        (&v as &dyn ToValue<i32>).serialize().unwrap(),
+                    _ => Value::Null,
+                },
+                Err(_) => Value::Null,
+            }
+        );
+        let eval = |text: &str| match LispExpression::parse(text)
+            .unwrap()
+            .as_text(&Context::new(&symbols))
+        {
+            Ok(v) => json!(v),
+            Err(e) => e.serialize(&Language::English),
+        };
+        assert_eq!(
+            json!("18.00"),
+            eval("(sum:Decimal (map:Decimal (. lines) l (*:Decimal (. l qty) (. l price))))")
+        );
+        assert_eq!(
+            json!("true"),
+            eval("(any (. lines) l (==:Number (. l qty) 150))")
+        );
+        assert_eq!(
+            json!("1"),
+            eval("(count (. lines) l (==:Number (. l qty) 150))")
+        );
+        assert_eq!(
+            json!("2"),
+            eval("(length:Number (filter (. lines) l (not (==:Decimal (. l price) 3))))")
+        );
+        assert_eq!(
+            json!("0.10"),
+            eval("(first:Decimal (map:Decimal (filter (. lines) l (==:Number (. l qty) 150)) l (. l price)))")
+        );
+        // Nulls are skipped by count, sum and avg, and leave any and all undecided
+        assert_eq!(json!("152"), eval("(sum:Number (. lines) l (. l qty))"));
+        assert_eq!(json!("76"), eval("(avg:Number (. lines) l (. l qty))"));
+        assert_eq!(
+            json!("2"),
+            eval("(count (map:Number (. lines) l (. l qty)))")
+        );
+        assert_eq!(
+            json!("Value is null"),
+            eval("(all (. lines) l (not (==:Number (. l qty) 0)))")
+        );
+        assert_eq!(
+            json!("false"),
+            eval("(all (. lines) l (==:Number (. l qty) 2))")
+        );
+        assert_eq!(json!("0"), eval("(sum:Number (. empty))"));
+        assert_eq!(json!("Value is null"), eval("(avg:Decimal (. empty))"));
+        assert_eq!(json!("false"), eval("(any (. empty) l true)"));
+        assert_eq!(json!("true"), eval("(all (. empty) l false)"));
+        assert_eq!(
+            json!("4.60"),
+            eval("(reduce:Decimal (. lines) l total 0 (+:Decimal (. total) (. l price)))")
+        );
+        assert_eq!(
+            json!("3"),
+            eval("(reduce:Decimal (. lines) l total 0 (max:Decimal (. total) (. l price)))")
+        );
+        // The item shadows symbols of the same name, only within the body
+        assert_eq!(
+            json!("102"),
+            eval("(+:Number (sum:Number (. empty) limit 1) (reduce:Number (. lines) limit n 2 (. n)) (. limit))")
+        );
+        assert_eq!(
+            json!({"/args/2/args/0": "Symbol not found"}),
+            eval("(sum:Number (. lines) l (. x qty))")
+        );
+        assert_eq!(
+            json!({"/args/0": "Type mismatch"}),
+            eval("(count (. limit))")
+        );
+        assert_eq!(
+            json!("Type mismatch"),
+            eval("(map:Number (. lines) l (. l qty))")
+        );
+        assert_eq!(
+            json!({"/args": "Wrong number of arguments"}),
+            match LispExpression::parse("(count (. lines) l)") {
+                Ok(_) => Value::Null,
+                Err(e) => e.serialize(&Language::English),
+            }
+        );
+        assert_eq!(
+            json!({"/type": "Type mismatch"}),
+            match LispExpression::parse("(sum:Text (. lines))") {
+                Ok(_) => Value::Null,
+                Err(e) => e.serialize(&Language::English),
+            }
+        );
+        assert_eq!(
+            json!({"/args/1": "Type mismatch"}),
+            match LispExpression::parse("(any (. lines) 1 true)") {
+                Ok(_) => Value::Null,
+                Err(e) => e.serialize(&Language::English),
+            }
+        );
+    }
+}
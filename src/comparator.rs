@@ -0,0 +1,680 @@
+use crate::context::Context;
+use crate::error::{CustomError, Message};
+use crate::value::{eval_arg, ToValue};
+use bigdecimal::BigDecimal;
+use serde_json::{json, Value};
+use std::cmp::Ordering;
+
+// COMPARATOR OPS
+
+pub enum ComparatorResultType {
+    Boolean,
+    Text,
+}
+
+pub enum ComparatorResult {
+    Boolean(bool),
+    Text(String),
+}
+
+pub enum ComparatorOperator {
+    Equals,
+    NotEquals,
+    GreaterThan,
+    LessThan,
+    GreaterThanEquals,
+    LessThanEquals,
+}
+
+impl ComparatorOperator {
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            ComparatorOperator::Equals => "==",
+            ComparatorOperator::NotEquals => "!=",
+            ComparatorOperator::GreaterThan => ">",
+            ComparatorOperator::LessThan => "<",
+            ComparatorOperator::GreaterThanEquals => ">=",
+            ComparatorOperator::LessThanEquals => "<=",
+        }
+    }
+
+    // Whether the operator holds between a left and right operand ordered as given
+    pub(crate) fn holds(&self, ordering: Ordering) -> bool {
+        match self {
+            ComparatorOperator::Equals => ordering == Ordering::Equal,
+            ComparatorOperator::NotEquals => ordering != Ordering::Equal,
+            ComparatorOperator::GreaterThan => ordering == Ordering::Greater,
+            ComparatorOperator::LessThan => ordering == Ordering::Less,
+            ComparatorOperator::GreaterThanEquals => ordering != Ordering::Less,
+            ComparatorOperator::LessThanEquals => ordering != Ordering::Greater,
+        }
+    }
+
+    // Whether the operator holds between each operand and the next, as in a < b < c.
+    // Operands are evaluated in order, up to the first pair it does not hold for.
+    pub(crate) fn chain<'a, T: Ord + 'a>(
+        &self,
+        mut operands: impl Iterator<Item = &'a Box<dyn ToValue<T>>>,
+        context: &Context,
+    ) -> Result<bool, CustomError> {
+        let mut previous = match operands.next() {
+            Some(v) => eval_arg(v.as_ref(), 0, context)?,
+            None => return Err(CustomError::Message(Message::ErrWrongArity)),
+        };
+        for (index, val) in operands.enumerate() {
+            let current = eval_arg(val.as_ref(), index + 1, context)?;
+            if !self.holds(previous.cmp(&current)) {
+                return Ok(false);
+            }
+            previous = current;
+        }
+        Ok(true)
+    }
+}
+
+// The first two args, and the rest
+pub type ComparatorOperands<T> = (
+    Box<dyn ToValue<T>>,
+    Box<dyn ToValue<T>>,
+    Vec<Box<dyn ToValue<T>>>,
+);
+
+// NUMBER COMPARATOR
+
+pub enum NumberComparatorExpression {
+    Equals(ComparatorOperands<i32>),
+    NotEquals(ComparatorOperands<i32>),
+    GreaterThan(ComparatorOperands<i32>),
+    LessThan(ComparatorOperands<i32>),
+    GreaterThanEquals(ComparatorOperands<i32>),
+    LessThanEquals(ComparatorOperands<i32>),
+}
+
+impl NumberComparatorExpression {
+    pub(crate) fn eval(
+        &self,
+        result_type: ComparatorResultType,
+        context: &Context,
+    ) -> Result<ComparatorResult, CustomError> {
+        let (args, operator) = match self {
+            NumberComparatorExpression::Equals(v) => (v, ComparatorOperator::Equals),
+            NumberComparatorExpression::NotEquals(v) => (v, ComparatorOperator::NotEquals),
+            NumberComparatorExpression::GreaterThan(v) => (v, ComparatorOperator::GreaterThan),
+            NumberComparatorExpression::LessThan(v) => (v, ComparatorOperator::LessThan),
+            NumberComparatorExpression::GreaterThanEquals(v) => {
+                (v, ComparatorOperator::GreaterThanEquals)
+            }
+            NumberComparatorExpression::LessThanEquals(v) => {
+                (v, ComparatorOperator::LessThanEquals)
+            }
+        };
+        operator
+            .chain(
+                std::iter::once(&args.0)
+                    .chain(std::iter::once(&args.1))
+                    .chain(&args.2),
+                context,
+            )
+            .map(|v| match result_type {
+                ComparatorResultType::Boolean => ComparatorResult::Boolean(v),
+                ComparatorResultType::Text => ComparatorResult::Text(v.to_string()),
+            })
+    }
+
+    pub(crate) fn serialize(&self) -> Result<Value, CustomError> {
+        let operator: &str = match self {
+            NumberComparatorExpression::Equals(_) => "==",
+            NumberComparatorExpression::NotEquals(_) => "!=",
+            NumberComparatorExpression::GreaterThanEquals(_) => ">=",
+            NumberComparatorExpression::LessThanEquals(_) => "<=",
+            NumberComparatorExpression::GreaterThan(_) => ">",
+            NumberComparatorExpression::LessThan(_) => "<",
+        };
+        match self {
+            NumberComparatorExpression::Equals(v)
+            | NumberComparatorExpression::NotEquals(v)
+            | NumberComparatorExpression::GreaterThanEquals(v)
+            | NumberComparatorExpression::LessThanEquals(v)
+            | NumberComparatorExpression::GreaterThan(v)
+            | NumberComparatorExpression::LessThan(v) => {
+                let mut err: Option<CustomError> = None;
+                let result: Vec<Result<Value, CustomError>> = std::iter::once(&v.0)
+                    .chain(std::iter::once(&v.1))
+                    .chain(&v.2)
+                    .map(|val| match val.serialize() {
+                        Ok(v) => Ok(v),
+                        Err(e) => {
+                            err = Some(e.clone());
+                            Err(e)
+                        }
+                    })
+                    .collect();
+                match err {
+                    Some(e) => Err(e),
+                    None => {
+                        let args: Vec<Value> = result
+                            .iter()
+                            .map(|val| match val {
+                                Ok(v) => v.clone(),
+                                Err(_) => panic!(),
+                            })
+                            .collect();
+                        Ok(json!({
+                            "op": operator,
+                            "type": "Number",
+                            "args": args
+                        }))
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl ToValue<String> for NumberComparatorExpression {
+    fn get_value(&self, context: &Context) -> Result<String, CustomError> {
+        match self.eval(ComparatorResultType::Text, context)? {
+            ComparatorResult::Text(v) => Ok(v),
+            _ => Err(CustomError::Message(Message::ErrUnexpected)),
+        }
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        self.serialize()
+    }
+}
+
+impl ToValue<bool> for NumberComparatorExpression {
+    fn get_value(&self, context: &Context) -> Result<bool, CustomError> {
+        match self.eval(ComparatorResultType::Boolean, context)? {
+            ComparatorResult::Boolean(v) => Ok(v),
+            _ => Err(CustomError::Message(Message::ErrUnexpected)),
+        }
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        self.serialize()
+    }
+}
+
+// DECIMAL COMPARATOR
+
+pub enum DecimalComparatorExpression {
+    Equals(ComparatorOperands<BigDecimal>),
+    NotEquals(ComparatorOperands<BigDecimal>),
+    GreaterThan(ComparatorOperands<BigDecimal>),
+    LessThan(ComparatorOperands<BigDecimal>),
+    GreaterThanEquals(ComparatorOperands<BigDecimal>),
+    LessThanEquals(ComparatorOperands<BigDecimal>),
+}
+
+impl DecimalComparatorExpression {
+    pub(crate) fn eval(
+        &self,
+        result_type: ComparatorResultType,
+        context: &Context,
+    ) -> Result<ComparatorResult, CustomError> {
+        let (args, operator) = match self {
+            DecimalComparatorExpression::Equals(v) => (v, ComparatorOperator::Equals),
+            DecimalComparatorExpression::NotEquals(v) => (v, ComparatorOperator::NotEquals),
+            DecimalComparatorExpression::GreaterThan(v) => (v, ComparatorOperator::GreaterThan),
+            DecimalComparatorExpression::LessThan(v) => (v, ComparatorOperator::LessThan),
+            DecimalComparatorExpression::GreaterThanEquals(v) => {
+                (v, ComparatorOperator::GreaterThanEquals)
+            }
+            DecimalComparatorExpression::LessThanEquals(v) => {
+                (v, ComparatorOperator::LessThanEquals)
+            }
+        };
+        operator
+            .chain(
+                std::iter::once(&args.0)
+                    .chain(std::iter::once(&args.1))
+                    .chain(&args.2),
+                context,
+            )
+            .map(|v| match result_type {
+                ComparatorResultType::Boolean => ComparatorResult::Boolean(v),
+                ComparatorResultType::Text => ComparatorResult::Text(v.to_string()),
+            })
+    }
+
+    pub(crate) fn serialize(&self) -> Result<Value, CustomError> {
+        let operator: &str = match self {
+            DecimalComparatorExpression::Equals(_) => "==",
+            DecimalComparatorExpression::NotEquals(_) => "!=",
+            DecimalComparatorExpression::GreaterThanEquals(_) => ">=",
+            DecimalComparatorExpression::LessThanEquals(_) => "<=",
+            DecimalComparatorExpression::GreaterThan(_) => ">",
+            DecimalComparatorExpression::LessThan(_) => "<",
+        };
+        match self {
+            DecimalComparatorExpression::Equals(v)
+            | DecimalComparatorExpression::NotEquals(v)
+            | DecimalComparatorExpression::GreaterThanEquals(v)
+            | DecimalComparatorExpression::LessThanEquals(v)
+            | DecimalComparatorExpression::GreaterThan(v)
+            | DecimalComparatorExpression::LessThan(v) => {
+                let mut err: Option<CustomError> = None;
+                let result: Vec<Result<Value, CustomError>> = std::iter::once(&v.0)
+                    .chain(std::iter::once(&v.1))
+                    .chain(&v.2)
+                    .map(|val| match val.serialize() {
+                        Ok(v) => Ok(v),
+                        Err(e) => {
+                            err = Some(e.clone());
+                            Err(e)
+                        }
+                    })
+                    .collect();
+                match err {
+                    Some(e) => Err(e),
+                    None => {
+                        let args: Vec<Value> = result
+                            .iter()
+                            .map(|val| match val {
+                                Ok(v) => v.clone(),
+                                Err(_) => panic!(),
+                            })
+                            .collect();
+                        Ok(json!({
+                            "op": operator,
+                            "type": "Decimal",
+                            "args": args
+                        }))
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl ToValue<String> for DecimalComparatorExpression {
+    fn get_value(&self, context: &Context) -> Result<String, CustomError> {
+        match self.eval(ComparatorResultType::Text, context)? {
+            ComparatorResult::Text(v) => Ok(v),
+            _ => Err(CustomError::Message(Message::ErrUnexpected)),
+        }
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        self.serialize()
+    }
+}
+
+impl ToValue<bool> for DecimalComparatorExpression {
+    fn get_value(&self, context: &Context) -> Result<bool, CustomError> {
+        match self.eval(ComparatorResultType::Boolean, context)? {
+            ComparatorResult::Boolean(v) => Ok(v),
+            _ => Err(CustomError::Message(Message::ErrUnexpected)),
+        }
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        self.serialize()
+    }
+}
+
+// TEXT COMPARATOR
+
+pub enum TextComparatorExpression {
+    Equals(ComparatorOperands<String>),
+    NotEquals(ComparatorOperands<String>),
+    GreaterThan(ComparatorOperands<String>),
+    LessThan(ComparatorOperands<String>),
+    GreaterThanEquals(ComparatorOperands<String>),
+    LessThanEquals(ComparatorOperands<String>),
+}
+
+impl TextComparatorExpression {
+    pub(crate) fn eval(
+        &self,
+        result_type: ComparatorResultType,
+        context: &Context,
+    ) -> Result<ComparatorResult, CustomError> {
+        let (args, operator) = match self {
+            TextComparatorExpression::Equals(v) => (v, ComparatorOperator::Equals),
+            TextComparatorExpression::NotEquals(v) => (v, ComparatorOperator::NotEquals),
+            TextComparatorExpression::GreaterThan(v) => (v, ComparatorOperator::GreaterThan),
+            TextComparatorExpression::LessThan(v) => (v, ComparatorOperator::LessThan),
+            TextComparatorExpression::GreaterThanEquals(v) => {
+                (v, ComparatorOperator::GreaterThanEquals)
+            }
+            TextComparatorExpression::LessThanEquals(v) => (v, ComparatorOperator::LessThanEquals),
+        };
+        operator
+            .chain(
+                std::iter::once(&args.0)
+                    .chain(std::iter::once(&args.1))
+                    .chain(&args.2),
+                context,
+            )
+            .map(|v| match result_type {
+                ComparatorResultType::Boolean => ComparatorResult::Boolean(v),
+                ComparatorResultType::Text => ComparatorResult::Text(v.to_string()),
+            })
+    }
+
+    pub(crate) fn serialize(&self) -> Result<Value, CustomError> {
+        let operator: &str = match self {
+            TextComparatorExpression::Equals(_) => "==",
+            TextComparatorExpression::NotEquals(_) => "!=",
+            TextComparatorExpression::GreaterThanEquals(_) => ">=",
+            TextComparatorExpression::LessThanEquals(_) => "<=",
+            TextComparatorExpression::GreaterThan(_) => ">",
+            TextComparatorExpression::LessThan(_) => "<",
+        };
+        match self {
+            TextComparatorExpression::Equals(v)
+            | TextComparatorExpression::NotEquals(v)
+            | TextComparatorExpression::GreaterThanEquals(v)
+            | TextComparatorExpression::LessThanEquals(v)
+            | TextComparatorExpression::GreaterThan(v)
+            | TextComparatorExpression::LessThan(v) => {
+                let mut err: Option<CustomError> = None;
+                let result: Vec<Result<Value, CustomError>> = std::iter::once(&v.0)
+                    .chain(std::iter::once(&v.1))
+                    .chain(&v.2)
+                    .map(|val| match val.serialize() {
+                        Ok(v) => Ok(v),
+                        Err(e) => {
+                            err = Some(e.clone());
+                            Err(e)
+                        }
+                    })
+                    .collect();
+                match err {
+                    Some(e) => Err(e),
+                    None => {
+                        let args: Vec<Value> = result
+                            .iter()
+                            .map(|val| match val {
+                                Ok(v) => v.clone(),
+                                Err(_) => panic!(),
+                            })
+                            .collect();
+                        Ok(json!({
+                            "op": operator,
+                            "type": "Text",
+                            "args": args
+                        }))
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl ToValue<String> for TextComparatorExpression {
+    fn get_value(&self, context: &Context) -> Result<String, CustomError> {
+        match self.eval(ComparatorResultType::Text, context)? {
+            ComparatorResult::Text(v) => Ok(v),
+            _ => Err(CustomError::Message(Message::ErrUnexpected)),
+        }
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        self.serialize()
+    }
+}
+
+impl ToValue<bool> for TextComparatorExpression {
+    fn get_value(&self, context: &Context) -> Result<bool, CustomError> {
+        match self.eval(ComparatorResultType::Boolean, context)? {
+            ComparatorResult::Boolean(v) => Ok(v),
+            _ => Err(CustomError::Message(Message::ErrUnexpected)),
+        }
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        self.serialize()
+    }
+}
+
+#[cfg(test)]
+mod comparator_tests {
+    use std::collections::HashMap;
+    use std::str::FromStr;
+
+    use super::*;
+    use crate::error::Language;
+    use crate::expression::LispExpression;
+    use crate::symbol::Symbol;
+
+    #[test]
+    fn test_number_comparator_expression() {
+        let symbols: HashMap<String, Symbol> = HashMap::new();
+        let expr = NumberComparatorExpression::Equals((
+            Box::new(2),
+            Box::new(BigDecimal::from_str("2.3").unwrap()),
+            vec![Box::new(2)],
+        ));
+        let res: bool = (&expr).get_value(&Context::new(&symbols)).unwrap();
+        assert_eq!(true, res);
+        // eval == serialize.deserialize.eval
+        assert_eq!(
+            res,
+            (match LispExpression::deserialize(
+                (&expr as &dyn ToValue<bool>).serialize().unwrap()
+            ) {
+                Ok(v) => match v {
+                    LispExpression::NumberComparatorExpression(v) =>
+                        (&v as &dyn ToValue<bool>).get_value(&Context::new(&symbols)),
+                    _ => Err(CustomError::Message(Message::ErrUnexpected)),
+                },
+                Err(_) => Err(CustomError::Message(Message::ErrUnexpected)),
+            })
+            .unwrap()
+        );
+        // serialize == serialize.deserialize.serialize
+        assert_eq!(
+            (&expr as &dyn ToValue<bool>).serialize().unwrap(),
+            match LispExpression::deserialize((&expr as &dyn ToValue<bool>).serialize().unwrap()) {
+                Ok(v) => match v {
+                    LispExpression::NumberComparatorExpression(v) =>
+                        (&v as &dyn ToValue<bool>).serialize().unwrap(),
+                    _ => Value::Null,
+                },
+                Err(_) => Value::Null,
+            }
+        );
+        let res: bool = NumberComparatorExpression::LessThanEquals((
+            Box::new(2),
+            Box::new(BigDecimal::from_str("3.3").unwrap()),
+            vec![Box::new(4)],
+        ))
+        .get_value(&Context::new(&symbols))
+        .unwrap();
+        assert_eq!(true, res);
+        let res: bool = NumberComparatorExpression::GreaterThan((
+            Box::new(5),
+            Box::new(BigDecimal::from_str("3.3").unwrap()),
+            vec![Box::new(2)],
+        ))
+        .get_value(&Context::new(&symbols))
+        .unwrap();
+        assert_eq!(true, res);
+        let res: bool = NumberComparatorExpression::LessThanEquals((
+            Box::new(2),
+            Box::new(BigDecimal::from_str("2.3").unwrap()),
+            vec![Box::new(2)],
+        ))
+        .get_value(&Context::new(&symbols))
+        .unwrap();
+        assert_eq!(true, res);
+        let res: bool = NumberComparatorExpression::GreaterThanEquals((
+            Box::new(2),
+            Box::new(BigDecimal::from_str("2.3").unwrap()),
+            vec![Box::new(2)],
+        ))
+        .get_value(&Context::new(&symbols))
+        .unwrap();
+        assert_eq!(true, res);
+    }
+
+    #[test]
+    fn test_decimal_comparator_expression() {
+        let symbols: HashMap<String, Symbol> = HashMap::new();
+        let expr = DecimalComparatorExpression::Equals((
+            Box::new(BigDecimal::from_str("2.3").unwrap()),
+            Box::new(BigDecimal::from_str("2.3").unwrap()),
+            vec![Box::new(BigDecimal::from_str("2.3").unwrap())],
+        ));
+        let res: bool = (&expr).get_value(&Context::new(&symbols)).unwrap();
+        assert_eq!(true, res);
+        // eval == serialize.deserialize.eval
+        assert_eq!(
+            res,
+            (match LispExpression::deserialize(
+                (&expr as &dyn ToValue<bool>).serialize().unwrap()
+            ) {
+                Ok(v) => match v {
+                    LispExpression::DecimalComparatorExpression(v) =>
+                        (&v as &dyn ToValue<bool>).get_value(&Context::new(&symbols)),
+                    _ => Err(CustomError::Message(Message::ErrUnexpected)),
+                },
+                Err(_) => Err(CustomError::Message(Message::ErrUnexpected)),
+            })
+            .unwrap()
+        );
+        // serialize == serialize.deserialize.serialize
+        assert_eq!(
+            (&expr as &dyn ToValue<bool>).serialize().unwrap(),
+            match LispExpression::deserialize((&expr as &dyn ToValue<bool>).serialize().unwrap()) {
+                Ok(v) => match v {
+                    LispExpression::DecimalComparatorExpression(v) =>
+                        (&v as &dyn ToValue<bool>).serialize().unwrap(),
+                    _ => Value::Null,
+                },
+                Err(_) => Value::Null,
+            }
+        );
+        let res: bool = DecimalComparatorExpression::LessThanEquals((
+            Box::new(2),
+            Box::new(BigDecimal::from_str("3.3").unwrap()),
+            vec![Box::new(4)],
+        ))
+        .get_value(&Context::new(&symbols))
+        .unwrap();
+        assert_eq!(true, res);
+        let res: bool = DecimalComparatorExpression::GreaterThan((
+            Box::new(5),
+            Box::new(BigDecimal::from_str("3.3").unwrap()),
+            vec![Box::new(2)],
+        ))
+        .get_value(&Context::new(&symbols))
+        .unwrap();
+        assert_eq!(true, res);
+        let res: bool = DecimalComparatorExpression::LessThanEquals((
+            Box::new(2),
+            Box::new(BigDecimal::from_str("2.3").unwrap()),
+            vec![Box::new(3)],
+        ))
+        .get_value(&Context::new(&symbols))
+        .unwrap();
+        assert_eq!(true, res);
+        let res: bool = DecimalComparatorExpression::GreaterThanEquals((
+            Box::new(4),
+            Box::new(BigDecimal::from_str("2.3").unwrap()),
+            vec![Box::new(1)],
+        ))
+        .get_value(&Context::new(&symbols))
+        .unwrap();
+        assert_eq!(true, res);
+    }
+
+    #[test]
+    fn test_text_comparator_expression() {
+        let symbols: HashMap<String, Symbol> = HashMap::new();
+        let res: bool = TextComparatorExpression::Equals((
+            Box::new(BigDecimal::from_str("2.3").unwrap()),
+            Box::new(BigDecimal::from_str("2.3").unwrap()),
+            vec![Box::new(BigDecimal::from_str("2.3").unwrap())],
+        ))
+        .get_value(&Context::new(&symbols))
+        .unwrap();
+        assert_eq!(true, res);
+    }
+
+    #[test]
+    fn test_comparator_conformance() {
+        // Whether each operator holds for a left operand less than, equal to and greater than the right
+        let table: [(&str, [bool; 3]); 6] = [
+            ("==", [false, true, false]),
+            ("!=", [true, false, true]),
+            ("<", [true, false, false]),
+            ("<=", [true, true, false]),
+            (">", [false, false, true]),
+            (">=", [false, true, true]),
+        ];
+        // Three ascending operands of each type, decimals being written at different scales
+        let families: [(&str, [[&str; 2]; 3]); 4] = [
+            ("Number", [["1", "1"], ["2", "2"], ["3", "3"]]),
+            (
+                "Decimal",
+                [["-1.5", "-1.50"], ["2.5", "2.50"], ["3", "3.0"]],
+            ),
+            ("Text", [["a", "a"], ["b", "b"], ["c", "c"]]),
+            (
+                "Date",
+                [
+                    ["2024-01-01", "2024-01-01"],
+                    ["2024-01-02", "2024-01-02"],
+                    ["2024-02-01", "2024-02-01"],
+                ],
+            ),
+        ];
+        let symbols: HashMap<String, Symbol> = HashMap::new();
+        for (value_type, operands) in families.iter() {
+            for (op, holds) in table.iter() {
+                for length in 2..5 {
+                    for combination in 0..3usize.pow(length) {
+                        let indices: Vec<usize> = (0..length)
+                            .map(|v| combination / 3usize.pow(v) % 3)
+                            .collect();
+                        let expected = indices
+                            .windows(2)
+                            .all(|v| holds[(v[0].cmp(&v[1]) as i32 + 1) as usize]);
+                        let args: Vec<&str> = indices
+                            .iter()
+                            .enumerate()
+                            .map(|(index, val)| operands[*val][index % 2])
+                            .collect();
+                        let text = format!("({}:{} {})", op, value_type, args.join(" "));
+                        let expr = LispExpression::parse(&text).unwrap();
+                        assert_eq!(
+                            expected,
+                            expr.as_boolean(&Context::new(&symbols)).unwrap(),
+                            "{}",
+                            text
+                        );
+                        assert_eq!(
+                            expected.to_string(),
+                            expr.as_text(&Context::new(&symbols)).unwrap(),
+                            "{}",
+                            text
+                        );
+                        assert_eq!(json!(op), expr.serialize().unwrap()["op"], "{}", text);
+                    }
+                }
+            }
+        }
+        // Operands are evaluated up to the first pair the operator does not hold for
+        let eval = |text: &str| match LispExpression::parse(text)
+            .unwrap()
+            .as_boolean(&Context::new(&symbols))
+        {
+            Ok(v) => json!(v),
+            Err(e) => e.serialize(&Language::English),
+        };
+        assert_eq!(json!(false), eval("(<:Number 2 1 (. x))"));
+        assert_eq!(
+            json!({"/args/2/args/0": "Symbol not found"}),
+            eval("(<:Number 1 2 (. x))")
+        );
+        assert_eq!(
+            json!({"/args/0/args/0": "Symbol not found"}),
+            eval("(!=:Text (. x) a)")
+        );
+    }
+}
@@ -0,0 +1,292 @@
+use crate::context::Context;
+use crate::error::{CustomError, Message};
+use crate::symbol::LeafType;
+use crate::value::ToValue;
+use bigdecimal::BigDecimal;
+use serde_json::{json, Value};
+
+// CONDITIONAL OPS
+
+pub enum ConditionalOperator {
+    If,   // [guard, result, otherwise]
+    Cond, // [[[guard, result], ...], otherwise]
+}
+
+// A guard and the result it selects
+pub type Branch<T> = (Box<dyn ToValue<bool>>, Box<dyn ToValue<T>>);
+
+// Evaluates to the result of the first guard that holds, or to otherwise
+pub struct ConditionalExpression<T> {
+    pub(crate) operator: ConditionalOperator,
+    pub(crate) guards: Vec<Branch<T>>,
+    pub(crate) otherwise: Box<dyn ToValue<T>>,
+}
+
+impl<T> ConditionalExpression<T> {
+    pub(crate) fn eval(&self, context: &Context) -> Result<T, CustomError> {
+        let pointer = |index: usize, part: usize| match self.operator {
+            ConditionalOperator::If => format!("/args/{}", part),
+            ConditionalOperator::Cond => format!("/args/0/{}/{}", index, part),
+        };
+        for (index, (guard, result)) in self.guards.iter().enumerate() {
+            // a null guard does not hold
+            let holds: bool = match guard.get_value(context) {
+                Ok(v) => v,
+                Err(e) if e.is_null() => false,
+                Err(e) => return Err(e.at(&pointer(index, 0))),
+            };
+            if holds {
+                return result
+                    .get_value(context)
+                    .map_err(|e| e.at(&pointer(index, 1)));
+            }
+        }
+        let pointer: &str = match self.operator {
+            ConditionalOperator::If => "/args/2",
+            ConditionalOperator::Cond => "/args/1",
+        };
+        self.otherwise.get_value(context).map_err(|e| e.at(pointer))
+    }
+
+    pub(crate) fn serialize_as(&self, result_type: LeafType) -> Result<Value, CustomError> {
+        let guards: Vec<Value> = self
+            .guards
+            .iter()
+            .map(|(guard, result)| Ok(json!([guard.serialize()?, result.serialize()?])))
+            .collect::<Result<Vec<Value>, CustomError>>()?;
+        let otherwise: Value = self.otherwise.serialize()?;
+        match self.operator {
+            ConditionalOperator::If => match guards.first() {
+                Some(Value::Array(v)) => Ok(json!({
+                    "op": "if",
+                    "type": result_type.serialize(),
+                    "args": [v[0], v[1], otherwise]
+                })),
+                _ => Err(CustomError::Message(Message::ErrUnexpected)),
+            },
+            ConditionalOperator::Cond => Ok(json!({
+                "op": "cond",
+                "type": result_type.serialize(),
+                "args": [guards, otherwise]
+            })),
+        }
+    }
+}
+
+impl ToValue<i32> for ConditionalExpression<i32> {
+    fn get_value(&self, context: &Context) -> Result<i32, CustomError> {
+        self.eval(context)
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        self.serialize_as(LeafType::Number)
+    }
+}
+
+impl ToValue<BigDecimal> for ConditionalExpression<i32> {
+    fn get_value(&self, context: &Context) -> Result<BigDecimal, CustomError> {
+        Ok(BigDecimal::from(self.eval(context)?))
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        self.serialize_as(LeafType::Number)
+    }
+}
+
+impl ToValue<String> for ConditionalExpression<i32> {
+    fn get_value(&self, context: &Context) -> Result<String, CustomError> {
+        Ok(self.eval(context)?.to_string())
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        self.serialize_as(LeafType::Number)
+    }
+}
+
+impl ToValue<i32> for ConditionalExpression<BigDecimal> {
+    fn get_value(&self, context: &Context) -> Result<i32, CustomError> {
+        context.to_number(&self.eval(context)?)
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        self.serialize_as(LeafType::Decimal)
+    }
+}
+
+impl ToValue<BigDecimal> for ConditionalExpression<BigDecimal> {
+    fn get_value(&self, context: &Context) -> Result<BigDecimal, CustomError> {
+        self.eval(context)
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        self.serialize_as(LeafType::Decimal)
+    }
+}
+
+impl ToValue<String> for ConditionalExpression<BigDecimal> {
+    fn get_value(&self, context: &Context) -> Result<String, CustomError> {
+        Ok(self.eval(context)?.to_string())
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        self.serialize_as(LeafType::Decimal)
+    }
+}
+
+impl ToValue<String> for ConditionalExpression<String> {
+    fn get_value(&self, context: &Context) -> Result<String, CustomError> {
+        self.eval(context)
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        self.serialize_as(LeafType::Text)
+    }
+}
+
+impl ToValue<bool> for ConditionalExpression<bool> {
+    fn get_value(&self, context: &Context) -> Result<bool, CustomError> {
+        self.eval(context)
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        self.serialize_as(LeafType::Boolean)
+    }
+}
+
+impl ToValue<String> for ConditionalExpression<bool> {
+    fn get_value(&self, context: &Context) -> Result<String, CustomError> {
+        Ok(self.eval(context)?.to_string())
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        self.serialize_as(LeafType::Boolean)
+    }
+}
+
+#[cfg(test)]
+mod conditional_tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::comparator::NumberComparatorExpression;
+    use crate::dot::DotExpression;
+    use crate::error::Language;
+    use crate::expression::LispExpression;
+    use crate::symbol::{Leaf, Symbol};
+
+    #[test]
+    fn test_conditional_expression() {
+        let symbols: HashMap<String, Symbol> = vec![
+            (
+                "age".to_string(),
+                Symbol {
+                    value: Some(Leaf::Number(18)),
+                    values: HashMap::new(),
+                },
+            ),
+            (
+                "member".to_string(),
+                Symbol {
+                    value: Some(Leaf::Boolean(false)),
+                    values: HashMap::new(),
+                },
+            ),
+        ]
+        .into_iter()
+        .collect();
+        let expr: ConditionalExpression<String> = ConditionalExpression {
+            operator: ConditionalOperator::Cond,
+            guards: vec![
+                (
+                    Box::new(DotExpression {
+                        path: vec!["member".to_string()],
+                    }),
+                    Box::new("member".to_string()),
+                ),
+                (
+                    Box::new(NumberComparatorExpression::Equals((
+                        Box::new(DotExpression {
+                            path: vec!["age".to_string()],
+                        }),
+                        Box::new(18),
+                        vec![],
+                    ))),
+                    Box::new("adult".to_string()),
+                ),
+                (Box::new(true), Box::new("any".to_string())),
+            ],
+            otherwise: Box::new("minor".to_string()),
+        };
+        let res: String = expr.get_value(&Context::new(&symbols)).unwrap();
+        assert_eq!("adult", res);
+        // eval == serialize.deserialize.eval
+        assert_eq!(
+            res,
+            (match LispExpression::deserialize(
+                (&expr as &dyn ToValue<String>).serialize().unwrap()
+            ) {
+                Ok(v) => match v {
+                    LispExpression::TextConditionalExpression(v) =>
+                        (&v as &dyn ToValue<String>).get_value(&Context::new(&symbols)),
+                    _ => Err(CustomError::Message(Message::ErrUnexpected)),
+                },
+                Err(_) => Err(CustomError::Message(Message::ErrUnexpected)),
+            })
+            .unwrap()
+        );
+        // serialize == serialize.deserialize.serialize
+        assert_eq!(
+            (&expr as &dyn ToValue<String>).serialize().unwrap(),
+            match LispExpression::deserialize((&expr as &dyn ToValue<String>).serialize().unwrap())
+            {
+                Ok(v) => match v {
+                    LispExpression::TextConditionalExpression(v) =>
+                        (&v as &dyn ToValue<String>).serialize().unwrap(),
+                    _ => Value::Null,
+                },
+                Err(_) => Value::Null,
+            }
+        );
+        let eval = |text: &str| match LispExpression::parse(text)
+            .unwrap()
+            .as_text(&Context::new(&symbols))
+        {
+            Ok(v) => json!(v),
+            Err(e) => e.serialize(&Language::English),
+        };
+        assert_eq!(
+            json!("minor"),
+            eval("(cond:Text [[(. member) member]] minor)")
+        );
+        assert_eq!(json!("minor"), eval("(cond:Text [] minor)"));
+        assert_eq!(
+            json!("19.5"),
+            eval("(if:Decimal (not (. member)) 19.5 (. age))")
+        );
+        assert_eq!(json!("18"), eval("(if:Number (. member) 19 (. age))"));
+        assert_eq!(json!("true"), eval("(if:Boolean (. member) false true)"));
+        // guards after the first that holds, and branches not taken, are not evaluated
+        assert_eq!(
+            json!("1"),
+            eval("(cond:Number [[true 1] [(. x) (. y)]] (. z))")
+        );
+        assert_eq!(json!("2"), eval("(if:Number false (. x) 2)"));
+        assert_eq!(
+            json!({"/args/0/1/0/args/0": "Symbol not found"}),
+            eval("(cond:Number [[false 1] [(. x) 2]] 3)")
+        );
+        assert_eq!(
+            json!({"/args/1/args/0/args/0": "Symbol not found"}),
+            eval("(if:Number true (+:Number (. x)) 2)")
+        );
+        assert_eq!(
+            json!({"/args": "Wrong number of arguments"}),
+            match LispExpression::deserialize(
+                json!({"op": "if", "type": "Text", "args": [true, "a"]})
+            ) {
+                Ok(_) => Value::Null,
+                Err(e) => e.serialize(&Language::English),
+            }
+        );
+    }
+}
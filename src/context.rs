@@ -0,0 +1,207 @@
+use crate::error::{CustomError, Message};
+use crate::function::Function;
+use crate::symbol::Symbol;
+use bigdecimal::{BigDecimal, Signed, ToPrimitive, Zero};
+use serde_json::{json, Value};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+// Context
+
+// What Number arithmetic does when a result does not fit in i32
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OverflowPolicy {
+    Error,
+    Saturate,
+    Wrap,
+    Promote, // continue in Decimal, failing only if a Number result is required
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RoundingMode {
+    HalfEven,
+    HalfUp, // ties away from zero
+    Floor,
+    Ceiling,
+    Truncate,
+}
+
+impl RoundingMode {
+    pub(crate) fn round(&self, v: &BigDecimal, scale: i64) -> BigDecimal {
+        let truncated = v.with_scale(scale);
+        let remainder = (v - &truncated).abs();
+        if remainder.is_zero() {
+            return truncated;
+        }
+        let unit = BigDecimal::new(1.into(), scale);
+        let away = match v.is_negative() {
+            true => &truncated - &unit,
+            false => &truncated + &unit,
+        };
+        let half = remainder * BigDecimal::from(2);
+        match self {
+            RoundingMode::HalfEven => match half.cmp(&unit) {
+                Ordering::Less => truncated,
+                Ordering::Greater => away,
+                Ordering::Equal => match ((&truncated / &unit) % BigDecimal::from(2)).is_zero() {
+                    true => truncated,
+                    false => away,
+                },
+            },
+            RoundingMode::HalfUp => match half < unit {
+                true => truncated,
+                false => away,
+            },
+            RoundingMode::Floor => match v.is_negative() {
+                true => away,
+                false => truncated,
+            },
+            RoundingMode::Ceiling => match v.is_negative() {
+                true => truncated,
+                false => away,
+            },
+            RoundingMode::Truncate => truncated,
+        }
+    }
+
+    pub(crate) fn serialize(&self) -> Value {
+        match self {
+            RoundingMode::HalfEven => json!("HalfEven"),
+            RoundingMode::HalfUp => json!("HalfUp"),
+            RoundingMode::Floor => json!("Floor"),
+            RoundingMode::Ceiling => json!("Ceiling"),
+            RoundingMode::Truncate => json!("Truncate"),
+        }
+    }
+
+    pub(crate) fn deserialize(val: &Value) -> Option<RoundingMode> {
+        match val {
+            Value::String(v) => match v.as_str() {
+                "HalfEven" => Some(RoundingMode::HalfEven),
+                "HalfUp" => Some(RoundingMode::HalfUp),
+                "Floor" => Some(RoundingMode::Floor),
+                "Ceiling" => Some(RoundingMode::Ceiling),
+                "Truncate" => Some(RoundingMode::Truncate),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+// State shared by every node during a single evaluation
+pub struct Context<'a> {
+    pub(crate) symbols: &'a HashMap<String, Symbol>,
+    pub(crate) overflow: OverflowPolicy,
+    pub(crate) scale: Option<i64>, // applied to the result of every Decimal arithmetic operation
+    pub(crate) rounding: RoundingMode,
+    pub(crate) bindings: HashMap<String, Symbol>, // local variables, shadowing those of the parent and symbols
+    pub(crate) parent: Option<&'a Context<'a>>,
+    pub(crate) functions: Option<&'a HashMap<String, Function>>,
+    pub(crate) depth: usize,     // number of function calls being evaluated
+    pub(crate) max_depth: usize, // calls nested deeper than this fail, stopping runaway recursion
+}
+
+impl<'a> Context<'a> {
+    pub fn new(symbols: &'a HashMap<String, Symbol>) -> Context<'a> {
+        Context {
+            symbols,
+            overflow: OverflowPolicy::Error,
+            scale: None,
+            rounding: RoundingMode::HalfEven,
+            bindings: HashMap::new(),
+            parent: None,
+            functions: None,
+            depth: 0,
+            max_depth: 64,
+        }
+    }
+
+    // Settings, for callers outside the crate
+
+    pub fn with_overflow(self, overflow: OverflowPolicy) -> Context<'a> {
+        Context { overflow, ..self }
+    }
+
+    // Decimal arithmetic results are rounded to this many places
+    pub fn with_scale(self, scale: i64) -> Context<'a> {
+        Context {
+            scale: Some(scale),
+            ..self
+        }
+    }
+
+    pub fn with_rounding(self, rounding: RoundingMode) -> Context<'a> {
+        Context { rounding, ..self }
+    }
+
+    pub fn with_functions(self, functions: &'a HashMap<String, Function>) -> Context<'a> {
+        Context {
+            functions: Some(functions),
+            ..self
+        }
+    }
+
+    pub fn with_max_depth(self, max_depth: usize) -> Context<'a> {
+        Context { max_depth, ..self }
+    }
+
+    // Opens a nested scope, with the same settings and no bindings of its own
+    pub(crate) fn scope(&self) -> Context<'_> {
+        Context {
+            symbols: self.symbols,
+            overflow: self.overflow,
+            scale: self.scale,
+            rounding: self.rounding,
+            bindings: HashMap::new(),
+            parent: Some(self),
+            functions: self.functions,
+            depth: self.depth,
+            max_depth: self.max_depth,
+        }
+    }
+
+    // Opens the scope of a function body, which sees its arguments but not the caller's bindings
+    pub(crate) fn call(
+        &self,
+        bindings: HashMap<String, Symbol>,
+    ) -> Result<Context<'a>, CustomError> {
+        match self.depth < self.max_depth {
+            true => Ok(Context {
+                symbols: self.symbols,
+                overflow: self.overflow,
+                scale: self.scale,
+                rounding: self.rounding,
+                bindings,
+                parent: None,
+                functions: self.functions,
+                depth: self.depth + 1,
+                max_depth: self.max_depth,
+            }),
+            false => Err(CustomError::Message(Message::ErrCallDepth)),
+        }
+    }
+
+    // Resolves a local variable, searching from the innermost scope outwards
+    pub(crate) fn lookup(&self, name: &str) -> Option<&Symbol> {
+        match self.bindings.get(name) {
+            Some(v) => Some(v),
+            None => self.parent.and_then(|v| v.lookup(name)),
+        }
+    }
+
+    pub(crate) fn rescale(&self, v: BigDecimal) -> BigDecimal {
+        match self.scale {
+            Some(v1) => self.rounding.round(&v, v1),
+            None => v,
+        }
+    }
+
+    // Decimals are rounded rather than truncated when a Number is required
+    pub(crate) fn to_number(&self, v: &BigDecimal) -> Result<i32, CustomError> {
+        match self.rounding.round(v, 0).to_i32() {
+            Some(v1) => Ok(v1),
+            None => Err(CustomError::Message(Message::ErrOverflow)),
+        }
+    }
+}
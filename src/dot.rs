@@ -0,0 +1,227 @@
+use crate::context::Context;
+use crate::error::{CustomError, Message};
+use crate::list::List;
+use crate::symbol::{Leaf, Symbol};
+use crate::temporal::Temporal;
+use crate::value::ToValue;
+use bigdecimal::{BigDecimal, FromPrimitive};
+use serde_json::{json, Value};
+
+// DOT OPERATOR
+
+pub enum DotResult {
+    Number(i32),
+    Decimal(BigDecimal),
+    Boolean(bool),
+    Text(String),
+}
+
+#[derive(Clone)]
+pub struct DotExpression {
+    pub(crate) path: Vec<String>,
+}
+
+impl DotExpression {
+    // The first segment names a local variable, or failing that a symbol.
+    // Errors are located at the path segment that could not be resolved.
+    pub(crate) fn resolve(&self, context: &Context) -> Result<Leaf, CustomError> {
+        let mut symbol: &Symbol = match self.path.first() {
+            Some(v) => match context.lookup(v).or_else(|| context.symbols.get(v)) {
+                Some(v1) => v1,
+                None => return Err(CustomError::Message(Message::ErrMissingSymbol).at("/args/0")),
+            },
+            None => return Err(CustomError::Message(Message::ErrWrongArity).at("/args")),
+        };
+        for (index, val) in self.path.iter().enumerate().skip(1) {
+            symbol = match symbol.get(val) {
+                Some(v) => v,
+                None => {
+                    return Err(CustomError::Message(Message::ErrMissingSymbol)
+                        .at(&format!("/args/{}", index)))
+                }
+            };
+        }
+        Ok(symbol.value.clone().unwrap_or(Leaf::Null))
+    }
+
+    pub(crate) fn eval(&self, context: &Context) -> Result<DotResult, CustomError> {
+        match self.resolve(context)? {
+            Leaf::Number(v) => Ok(DotResult::Number(v)),
+            Leaf::Decimal(v) => Ok(DotResult::Decimal(v)),
+            Leaf::Text(v) => Ok(DotResult::Text(v)),
+            Leaf::Boolean(v) => Ok(DotResult::Boolean(v)),
+            // temporal values are only read as such where they are expected, else as their text
+            Leaf::Date(v) => Ok(DotResult::Text(v.to_text())),
+            Leaf::DateTime(v) => Ok(DotResult::Text(v.to_text())),
+            Leaf::Duration(v) => Ok(DotResult::Text(v.to_text())),
+            Leaf::Null => Err(CustomError::Message(Message::ErrNull)),
+            Leaf::List(_) => Err(CustomError::Message(Message::ErrTypeMismatch)),
+        }
+    }
+
+    pub(crate) fn serialize(&self) -> Result<Value, CustomError> {
+        Ok(json!({
+            "op": ".",
+            "args": json!(self.path)
+        }))
+    }
+}
+
+impl ToValue<i32> for DotExpression {
+    fn get_value(&self, context: &Context) -> Result<i32, CustomError> {
+        match self.eval(context)? {
+            DotResult::Number(v) => Ok(v),
+            DotResult::Decimal(v) => context.to_number(&v),
+            _ => Err(CustomError::Message(Message::ErrTypeMismatch)),
+        }
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        self.serialize()
+    }
+}
+
+impl ToValue<BigDecimal> for DotExpression {
+    fn get_value(&self, context: &Context) -> Result<BigDecimal, CustomError> {
+        match self.eval(context)? {
+            DotResult::Number(v) => match BigDecimal::from_i32(v) {
+                Some(v1) => Ok(v1),
+                None => Err(CustomError::Message(Message::ErrUnexpected)),
+            },
+            DotResult::Decimal(v) => Ok(v),
+            _ => Err(CustomError::Message(Message::ErrTypeMismatch)),
+        }
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        self.serialize()
+    }
+}
+
+impl ToValue<String> for DotExpression {
+    fn get_value(&self, context: &Context) -> Result<String, CustomError> {
+        match self.eval(context)? {
+            DotResult::Number(v) => Ok(v.to_string()),
+            DotResult::Decimal(v) => Ok(v.to_string()),
+            DotResult::Text(v) => Ok(v),
+            DotResult::Boolean(v) => Ok(v.to_string()),
+        }
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        self.serialize()
+    }
+}
+
+impl ToValue<List> for DotExpression {
+    fn get_value(&self, context: &Context) -> Result<List, CustomError> {
+        match self.resolve(context)? {
+            Leaf::List(v) => Ok(v),
+            Leaf::Null => Err(CustomError::Message(Message::ErrNull)),
+            _ => Err(CustomError::Message(Message::ErrTypeMismatch)),
+        }
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        self.serialize()
+    }
+}
+
+impl ToValue<bool> for DotExpression {
+    fn get_value(&self, context: &Context) -> Result<bool, CustomError> {
+        match self.eval(context)? {
+            DotResult::Boolean(v) => Ok(v),
+            _ => Err(CustomError::Message(Message::ErrTypeMismatch)),
+        }
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        self.serialize()
+    }
+}
+
+#[cfg(test)]
+mod dot_tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::arithmetic::DecimalArithmeticExpression;
+    use crate::expression::LispExpression;
+
+    #[test]
+    fn test_dot_expression() {
+        let symbols: HashMap<String, Symbol> = vec![
+            (
+                "x".to_string(),
+                Symbol {
+                    value: Some(Leaf::Number(2)),
+                    values: HashMap::new(),
+                },
+            ),
+            (
+                "y".to_string(),
+                Symbol {
+                    value: Some(Leaf::Number(3)),
+                    values: HashMap::new(),
+                },
+            ),
+            (
+                "z".to_string(),
+                Symbol {
+                    value: None,
+                    values: vec![(
+                        "z".to_string(),
+                        Symbol {
+                            value: Some(Leaf::Number(6)),
+                            values: HashMap::new(),
+                        },
+                    )]
+                    .into_iter()
+                    .collect(),
+                },
+            ),
+        ]
+        .into_iter()
+        .collect();
+        let expr = DecimalArithmeticExpression::Add((
+            Box::new(DotExpression {
+                path: vec![String::from("x")],
+            }),
+            vec![
+                Box::new(DotExpression {
+                    path: vec![String::from("y")],
+                }),
+                Box::new(DotExpression {
+                    path: vec![String::from("z"), String::from("z")],
+                }),
+            ],
+        ));
+        let res: i32 = (&expr).get_value(&Context::new(&symbols)).unwrap();
+        assert_eq!(11, res);
+        // eval == serialize.deserialize.eval
+        assert_eq!(
+            res,
+            (match LispExpression::deserialize((&expr as &dyn ToValue<i32>).serialize().unwrap()) {
+                Ok(v) => match v {
+                    LispExpression::DecimalArithmeticExpression(v) =>
+                        (&v as &dyn ToValue<i32>).get_value(&Context::new(&symbols)),
+                    _ => Err(CustomError::Message(Message::ErrUnexpected)),
+                },
+                Err(_) => Err(CustomError::Message(Message::ErrUnexpected)),
+            })
+            .unwrap()
+        );
+        // serialize == serialize.deserialize.serialize
+        assert_eq!(
+            (&expr as &dyn ToValue<i32>).serialize().unwrap(),
+            match LispExpression::deserialize((&expr as &dyn ToValue<i32>).serialize().unwrap()) {
+                Ok(v) => match v {
+                    LispExpression::DecimalArithmeticExpression(v) =>
+                        (&v as &dyn ToValue<i32>).serialize().unwrap(),
+                    _ => Value::Null,
+                },
+                Err(_) => Value::Null,
+            }
+        );
+    }
+}
@@ -0,0 +1,115 @@
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+pub enum Language {
+    English,
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    ErrUnexpected,
+    ErrMissingSymbol,
+    ErrSerialization,
+    ErrDeserialization,
+    ErrSyntax,
+    ErrUnknownOperator,
+    ErrWrongArity,
+    ErrTypeMismatch,
+    ErrInvalidLiteral,
+    ErrOverflow,
+    ErrDivideByZero,
+    ErrMissingFunction,
+    ErrCallDepth,
+    ErrOutOfRange,
+    ErrInvalidArgument,
+    ErrInvalidPattern,
+    ErrNoMatch,
+    ErrConversion,
+    ErrNull,
+    SymbolType,
+    SymbolValue,
+    SymbolTypeNumber,
+    SymbolTypeDecimal,
+    SymbolTypeText,
+    SymbolTypeBoolean,
+}
+
+impl Message {
+    pub(crate) fn to_string(&self, lang: &Language) -> String {
+        let result: &str = match lang {
+            Language::English => match self {
+                Message::ErrUnexpected => "Unexpected Error",
+                Message::ErrMissingSymbol => "Symbol not found",
+                Message::ErrSerialization => "Unable to serialize",
+                Message::ErrDeserialization => "Unable to deserialize",
+                Message::ErrSyntax => "Invalid syntax",
+                Message::ErrUnknownOperator => "Unknown operator",
+                Message::ErrWrongArity => "Wrong number of arguments",
+                Message::ErrTypeMismatch => "Type mismatch",
+                Message::ErrInvalidLiteral => "Invalid literal",
+                Message::ErrOverflow => "Arithmetic overflow",
+                Message::ErrDivideByZero => "Division by zero",
+                Message::ErrMissingFunction => "Function not found",
+                Message::ErrCallDepth => "Call depth exceeded",
+                Message::ErrOutOfRange => "Out of range",
+                Message::ErrInvalidArgument => "Invalid argument",
+                Message::ErrInvalidPattern => "Invalid pattern",
+                Message::ErrNoMatch => "No match",
+                Message::ErrConversion => "Conversion failed",
+                Message::ErrNull => "Value is null",
+                Message::SymbolType => "type",
+                Message::SymbolValue => "value",
+                Message::SymbolTypeNumber => "Number",
+                Message::SymbolTypeDecimal => "Decimal",
+                Message::SymbolTypeText => "Text",
+                Message::SymbolTypeBoolean => "Boolean",
+            },
+        };
+        result.to_string()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum CustomError {
+    Message(Message),
+    Messages(HashMap<String, CustomError>),
+}
+
+impl CustomError {
+    // Locates an error under a JSON pointer, relative to the expression that reported it.
+    // Errors are located by each enclosing expression in turn, so the keys of Messages end up
+    // as JSON pointers into the serialized root expression.
+    pub(crate) fn at(self, pointer: &str) -> CustomError {
+        match self {
+            CustomError::Message(v) => CustomError::Messages(
+                vec![(pointer.to_string(), CustomError::Message(v))]
+                    .into_iter()
+                    .collect(),
+            ),
+            CustomError::Messages(v) => CustomError::Messages(
+                v.into_iter()
+                    .map(|(key, val)| (format!("{}{}", pointer, key), val))
+                    .collect(),
+            ),
+        }
+    }
+
+    // Whether the error only reports null values, wherever they were located
+    pub(crate) fn is_null(&self) -> bool {
+        match self {
+            CustomError::Message(v) => matches!(v, Message::ErrNull),
+            CustomError::Messages(v) => v.values().all(|val| val.is_null()),
+        }
+    }
+
+    pub fn serialize(self, lang: &Language) -> Value {
+        match self {
+            CustomError::Message(v) => json!(v.to_string(lang)),
+            CustomError::Messages(v) => Value::Object(
+                v.into_iter()
+                    .map(|(key, val)| (key, val.serialize(lang)))
+                    .collect(),
+            ),
+        }
+    }
+}
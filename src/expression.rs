@@ -0,0 +1,2806 @@
+use crate::arithmetic::{
+    within_scale, ArithmeticOperator, DecimalArithmeticExpression, NumberArithmeticExpression,
+    RoundExpression, MAX_SCALE,
+};
+use crate::binding::{Binding, LetExpression};
+use crate::cast::CastExpression;
+use crate::collection::{CollectionExpression, Lambda};
+use crate::comparator::{
+    ComparatorOperator, DecimalComparatorExpression, NumberComparatorExpression,
+    TextComparatorExpression,
+};
+use crate::conditional::{ConditionalExpression, ConditionalOperator};
+use crate::context::{Context, RoundingMode};
+use crate::dot::DotExpression;
+use crate::error::{CustomError, Message};
+use crate::function::CallExpression;
+use crate::list::{List, ListExpression};
+use crate::logical::{
+    LogicalBinaryExpression, LogicalQuantifier, LogicalUnaryExpression, LogicalVariadicExpression,
+};
+use crate::matching::{Guard, Guards, Match, MatchExpression, Matchable};
+use crate::membership::{Membership, MembershipExpression};
+use crate::null::{Null, NullExpression};
+use crate::symbol::LeafType;
+use crate::temporal::{Temporal, TemporalComparatorExpression, TemporalExpression};
+use crate::text::{Pattern, RegexExpression, TextExpression};
+use crate::value::ToValue;
+use bigdecimal::{BigDecimal, ToPrimitive};
+use chrono::{DateTime, Duration, FixedOffset, NaiveDate};
+use serde_json::Value;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::str::FromStr;
+
+pub enum LispExpression {
+    NumberArithmeticExpression(NumberArithmeticExpression),
+    DecimalArithmeticExpression(DecimalArithmeticExpression),
+    RoundExpression(RoundExpression),
+    NumberComparatorExpression(NumberComparatorExpression),
+    DecimalComparatorExpression(DecimalComparatorExpression),
+    TextComparatorExpression(TextComparatorExpression),
+    LogicalBinaryExpression(LogicalBinaryExpression),
+    LogicalUnaryExpression(LogicalUnaryExpression),
+    LogicalVariadicExpression(LogicalVariadicExpression),
+    TextExpression(TextExpression),
+    RegexExpression(RegexExpression),
+    MatchExpression(MatchExpression),
+    NumberConditionalExpression(ConditionalExpression<i32>),
+    DecimalConditionalExpression(ConditionalExpression<BigDecimal>),
+    TextConditionalExpression(ConditionalExpression<String>),
+    BooleanConditionalExpression(ConditionalExpression<bool>),
+    NumberLetExpression(LetExpression<i32>),
+    DecimalLetExpression(LetExpression<BigDecimal>),
+    TextLetExpression(LetExpression<String>),
+    BooleanLetExpression(LetExpression<bool>),
+    CallExpression(CallExpression),
+    CastExpression(CastExpression),
+    NullExpression(NullExpression),
+    ListExpression(ListExpression),
+    CollectionExpression(CollectionExpression),
+    TemporalExpression(TemporalExpression),
+    TemporalComparatorExpression(TemporalComparatorExpression),
+    MembershipExpression(MembershipExpression),
+    DotExpression(DotExpression),
+}
+
+// Reads an expression from its op, type and args
+pub type Deserializer = fn(&str, Option<&Value>, &[Value]) -> Result<LispExpression, CustomError>;
+
+// Reads an operand that evaluates to T
+pub type OperandDeserializer<T> = fn(&Value) -> Result<Box<dyn ToValue<T>>, CustomError>;
+
+impl LispExpression {
+    // Every op emitted by the serializers, with the deserializer that reads it back
+    pub(crate) const OPERATORS: &'static [(&'static str, Deserializer)] = &[
+        ("+", Self::deserialize_arithmetic),
+        ("*", Self::deserialize_arithmetic),
+        ("-", Self::deserialize_arithmetic),
+        ("/", Self::deserialize_arithmetic),
+        ("%", Self::deserialize_arithmetic),
+        ("min", Self::deserialize_arithmetic),
+        ("max", Self::deserialize_arithmetic),
+        ("pow", Self::deserialize_arithmetic),
+        ("abs", Self::deserialize_arithmetic),
+        ("sqrt", Self::deserialize_arithmetic),
+        ("sign", Self::deserialize_arithmetic),
+        ("floor", Self::deserialize_arithmetic),
+        ("ceil", Self::deserialize_arithmetic),
+        ("clamp", Self::deserialize_arithmetic),
+        ("percent", Self::deserialize_arithmetic),
+        ("round", Self::deserialize_round),
+        ("==", Self::deserialize_comparator),
+        ("!=", Self::deserialize_comparator),
+        (">=", Self::deserialize_comparator),
+        ("<=", Self::deserialize_comparator),
+        (">", Self::deserialize_comparator),
+        ("<", Self::deserialize_comparator),
+        ("in", Self::deserialize_membership),
+        ("not_in", Self::deserialize_membership),
+        ("between", Self::deserialize_membership),
+        ("between_exclusive", Self::deserialize_membership),
+        ("and", Self::deserialize_logical_binary),
+        ("or", Self::deserialize_logical_binary),
+        ("xor", Self::deserialize_logical_binary),
+        ("implies", Self::deserialize_logical_binary),
+        ("iff", Self::deserialize_logical_binary),
+        ("nand", Self::deserialize_logical_binary),
+        ("nor", Self::deserialize_logical_binary),
+        ("not", Self::deserialize_logical_unary),
+        ("all_of", Self::deserialize_logical_variadic),
+        ("any_of", Self::deserialize_logical_variadic),
+        ("none_of", Self::deserialize_logical_variadic),
+        ("exactly_n_of", Self::deserialize_logical_variadic),
+        ("concat", Self::deserialize_text),
+        ("length", Self::deserialize_text),
+        ("substring", Self::deserialize_text),
+        ("upper", Self::deserialize_text),
+        ("lower", Self::deserialize_text),
+        ("trim", Self::deserialize_text),
+        ("starts_with", Self::deserialize_text),
+        ("ends_with", Self::deserialize_text),
+        ("contains", Self::deserialize_text),
+        ("replace", Self::deserialize_text),
+        ("split", Self::deserialize_text),
+        ("join", Self::deserialize_text),
+        ("pad_start", Self::deserialize_text),
+        ("pad_end", Self::deserialize_text),
+        ("regex_match", Self::deserialize_regex),
+        ("regex_find", Self::deserialize_regex),
+        ("regex_replace", Self::deserialize_regex),
+        ("match", Self::deserialize_match),
+        ("if", Self::deserialize_conditional),
+        ("cond", Self::deserialize_conditional),
+        ("let", Self::deserialize_let),
+        ("call", Self::deserialize_call),
+        ("to_number", Self::deserialize_cast),
+        ("to_decimal", Self::deserialize_cast),
+        ("to_text", Self::deserialize_cast),
+        ("to_boolean", Self::deserialize_cast),
+        ("parse_number", Self::deserialize_cast),
+        ("parse_decimal", Self::deserialize_cast),
+        ("exists", Self::deserialize_null),
+        ("is_null", Self::deserialize_null),
+        ("coalesce", Self::deserialize_null),
+        ("first", Self::deserialize_list),
+        ("last", Self::deserialize_list),
+        ("map", Self::deserialize_collection),
+        ("filter", Self::deserialize_collection),
+        ("reduce", Self::deserialize_collection),
+        ("any", Self::deserialize_collection),
+        ("all", Self::deserialize_collection),
+        ("count", Self::deserialize_collection),
+        ("sum", Self::deserialize_collection),
+        ("avg", Self::deserialize_collection),
+        ("add_days", Self::deserialize_temporal),
+        ("add_months", Self::deserialize_temporal),
+        ("diff_days", Self::deserialize_temporal),
+        ("start_of_month", Self::deserialize_temporal),
+        ("end_of_month", Self::deserialize_temporal),
+        ("weekday", Self::deserialize_temporal),
+        ("age", Self::deserialize_temporal),
+        ("add_duration", Self::deserialize_temporal),
+        ("duration_between", Self::deserialize_temporal),
+        (".", Self::deserialize_dot),
+    ];
+
+    pub fn as_number(&self, context: &Context) -> Result<i32, CustomError> {
+        let err = Err(CustomError::Message(Message::ErrTypeMismatch));
+        match self {
+            LispExpression::NumberArithmeticExpression(v) => v.get_value(context),
+            LispExpression::DecimalArithmeticExpression(v) => v.get_value(context),
+            LispExpression::RoundExpression(v) => v.get_value(context),
+            LispExpression::NumberComparatorExpression(_) => err,
+            LispExpression::DecimalComparatorExpression(_) => err,
+            LispExpression::TextComparatorExpression(_) => err,
+            LispExpression::LogicalBinaryExpression(_) => err,
+            LispExpression::LogicalUnaryExpression(_) => err,
+            LispExpression::LogicalVariadicExpression(_) => err,
+            LispExpression::MatchExpression(v) => v.get_value(context),
+            LispExpression::NumberConditionalExpression(v) => v.get_value(context),
+            LispExpression::DecimalConditionalExpression(v) => v.get_value(context),
+            LispExpression::TextConditionalExpression(_) => err,
+            LispExpression::BooleanConditionalExpression(_) => err,
+            LispExpression::NumberLetExpression(v) => v.get_value(context),
+            LispExpression::DecimalLetExpression(v) => v.get_value(context),
+            LispExpression::TextLetExpression(_) => err,
+            LispExpression::BooleanLetExpression(_) => err,
+            LispExpression::CallExpression(v) => v.get_value(context),
+            LispExpression::CastExpression(v) => v.get_value(context),
+            LispExpression::NullExpression(v) => v.get_value(context),
+            LispExpression::ListExpression(v) => v.get_value(context),
+            LispExpression::CollectionExpression(v) => v.get_value(context),
+            LispExpression::TemporalExpression(v) => v.get_value(context),
+            LispExpression::TemporalComparatorExpression(_) => err,
+            LispExpression::MembershipExpression(_) => err,
+            LispExpression::TextExpression(v) => v.get_value(context),
+            LispExpression::RegexExpression(_) => err,
+            LispExpression::DotExpression(v) => v.get_value(context),
+        }
+    }
+
+    pub fn as_decimal(&self, context: &Context) -> Result<BigDecimal, CustomError> {
+        let err = Err(CustomError::Message(Message::ErrTypeMismatch));
+        match self {
+            LispExpression::NumberArithmeticExpression(v) => v.get_value(context),
+            LispExpression::DecimalArithmeticExpression(v) => v.get_value(context),
+            LispExpression::RoundExpression(v) => v.get_value(context),
+            LispExpression::NumberComparatorExpression(_) => err,
+            LispExpression::DecimalComparatorExpression(_) => err,
+            LispExpression::TextComparatorExpression(_) => err,
+            LispExpression::LogicalBinaryExpression(_) => err,
+            LispExpression::LogicalUnaryExpression(_) => err,
+            LispExpression::LogicalVariadicExpression(_) => err,
+            LispExpression::MatchExpression(v) => v.get_value(context),
+            LispExpression::NumberConditionalExpression(v) => v.get_value(context),
+            LispExpression::DecimalConditionalExpression(v) => v.get_value(context),
+            LispExpression::TextConditionalExpression(_) => err,
+            LispExpression::BooleanConditionalExpression(_) => err,
+            LispExpression::NumberLetExpression(v) => v.get_value(context),
+            LispExpression::DecimalLetExpression(v) => v.get_value(context),
+            LispExpression::TextLetExpression(_) => err,
+            LispExpression::BooleanLetExpression(_) => err,
+            LispExpression::CallExpression(v) => v.get_value(context),
+            LispExpression::CastExpression(v) => v.get_value(context),
+            LispExpression::NullExpression(v) => v.get_value(context),
+            LispExpression::ListExpression(v) => v.get_value(context),
+            LispExpression::CollectionExpression(v) => v.get_value(context),
+            LispExpression::TemporalExpression(v) => v.get_value(context),
+            LispExpression::TemporalComparatorExpression(_) => err,
+            LispExpression::MembershipExpression(_) => err,
+            LispExpression::TextExpression(v) => v.get_value(context),
+            LispExpression::RegexExpression(_) => err,
+            LispExpression::DotExpression(v) => v.get_value(context),
+        }
+    }
+
+    pub fn as_text(&self, context: &Context) -> Result<String, CustomError> {
+        match self {
+            LispExpression::NumberArithmeticExpression(v) => v.get_value(context),
+            LispExpression::DecimalArithmeticExpression(v) => v.get_value(context),
+            LispExpression::RoundExpression(v) => v.get_value(context),
+            LispExpression::NumberComparatorExpression(v) => v.get_value(context),
+            LispExpression::DecimalComparatorExpression(v) => v.get_value(context),
+            LispExpression::TextComparatorExpression(v) => v.get_value(context),
+            LispExpression::LogicalBinaryExpression(v) => v.get_value(context),
+            LispExpression::LogicalUnaryExpression(v) => v.get_value(context),
+            LispExpression::LogicalVariadicExpression(v) => v.get_value(context),
+            LispExpression::MatchExpression(v) => v.get_value(context),
+            LispExpression::NumberConditionalExpression(v) => v.get_value(context),
+            LispExpression::DecimalConditionalExpression(v) => v.get_value(context),
+            LispExpression::TextConditionalExpression(v) => v.get_value(context),
+            LispExpression::BooleanConditionalExpression(v) => v.get_value(context),
+            LispExpression::NumberLetExpression(v) => v.get_value(context),
+            LispExpression::DecimalLetExpression(v) => v.get_value(context),
+            LispExpression::TextLetExpression(v) => v.get_value(context),
+            LispExpression::BooleanLetExpression(v) => v.get_value(context),
+            LispExpression::CallExpression(v) => v.get_value(context),
+            LispExpression::CastExpression(v) => v.get_value(context),
+            LispExpression::NullExpression(v) => v.get_value(context),
+            LispExpression::ListExpression(v) => v.get_value(context),
+            LispExpression::CollectionExpression(v) => v.get_value(context),
+            LispExpression::TemporalExpression(v) => v.get_value(context),
+            LispExpression::TemporalComparatorExpression(v) => v.get_value(context),
+            LispExpression::MembershipExpression(v) => v.get_value(context),
+            LispExpression::TextExpression(v) => v.get_value(context),
+            LispExpression::RegexExpression(v) => v.get_value(context),
+            LispExpression::DotExpression(v) => v.get_value(context),
+        }
+    }
+
+    pub fn as_boolean(&self, context: &Context) -> Result<bool, CustomError> {
+        let err = Err(CustomError::Message(Message::ErrTypeMismatch));
+        match self {
+            LispExpression::NumberArithmeticExpression(_) => err,
+            LispExpression::DecimalArithmeticExpression(_) => err,
+            LispExpression::RoundExpression(_) => err,
+            LispExpression::NumberComparatorExpression(v) => v.get_value(context),
+            LispExpression::DecimalComparatorExpression(v) => v.get_value(context),
+            LispExpression::TextComparatorExpression(v) => v.get_value(context),
+            LispExpression::LogicalBinaryExpression(v) => v.get_value(context),
+            LispExpression::LogicalUnaryExpression(v) => v.get_value(context),
+            LispExpression::LogicalVariadicExpression(v) => v.get_value(context),
+            LispExpression::MatchExpression(v) => v.get_value(context),
+            LispExpression::NumberConditionalExpression(_) => err,
+            LispExpression::DecimalConditionalExpression(_) => err,
+            LispExpression::TextConditionalExpression(_) => err,
+            LispExpression::BooleanConditionalExpression(v) => v.get_value(context),
+            LispExpression::NumberLetExpression(_) => err,
+            LispExpression::DecimalLetExpression(_) => err,
+            LispExpression::TextLetExpression(_) => err,
+            LispExpression::BooleanLetExpression(v) => v.get_value(context),
+            LispExpression::CallExpression(v) => v.get_value(context),
+            LispExpression::CastExpression(v) => v.get_value(context),
+            LispExpression::NullExpression(v) => v.get_value(context),
+            LispExpression::ListExpression(v) => v.get_value(context),
+            LispExpression::CollectionExpression(v) => v.get_value(context),
+            LispExpression::TemporalExpression(_) => err,
+            LispExpression::TemporalComparatorExpression(v) => v.get_value(context),
+            LispExpression::MembershipExpression(v) => v.get_value(context),
+            LispExpression::TextExpression(v) => v.get_value(context),
+            LispExpression::RegexExpression(v) => v.get_value(context),
+            LispExpression::DotExpression(v) => v.get_value(context),
+        }
+    }
+
+    // Only expressions evaluating to leaves can evaluate to temporal values
+    pub fn as_temporal<T: Temporal>(&self, context: &Context) -> Result<T, CustomError> {
+        match self {
+            LispExpression::MatchExpression(v) => v.get_value(context),
+            LispExpression::CallExpression(v) => v.get_value(context),
+            LispExpression::NullExpression(v) => v.get_value(context),
+            LispExpression::ListExpression(v) => v.get_value(context),
+            LispExpression::CollectionExpression(v) => v.get_value(context),
+            LispExpression::TemporalExpression(v) => v.get_value(context),
+            LispExpression::DotExpression(v) => v.get_value(context),
+            _ => Err(CustomError::Message(Message::ErrTypeMismatch)),
+        }
+    }
+
+    pub fn serialize(&self) -> Result<Value, CustomError> {
+        match self {
+            LispExpression::NumberArithmeticExpression(v) => v.serialize(),
+            LispExpression::DecimalArithmeticExpression(v) => v.serialize(),
+            LispExpression::RoundExpression(v) => v.serialize(),
+            LispExpression::NumberComparatorExpression(v) => v.serialize(),
+            LispExpression::DecimalComparatorExpression(v) => v.serialize(),
+            LispExpression::TextComparatorExpression(v) => v.serialize(),
+            LispExpression::LogicalBinaryExpression(v) => v.serialize(),
+            LispExpression::LogicalUnaryExpression(v) => v.serialize(),
+            LispExpression::LogicalVariadicExpression(v) => v.serialize(),
+            LispExpression::MatchExpression(v) => v.serialize(),
+            LispExpression::NumberConditionalExpression(v) => v.serialize_as(LeafType::Number),
+            LispExpression::DecimalConditionalExpression(v) => v.serialize_as(LeafType::Decimal),
+            LispExpression::TextConditionalExpression(v) => v.serialize_as(LeafType::Text),
+            LispExpression::BooleanConditionalExpression(v) => v.serialize_as(LeafType::Boolean),
+            LispExpression::NumberLetExpression(v) => v.serialize_as(LeafType::Number),
+            LispExpression::DecimalLetExpression(v) => v.serialize_as(LeafType::Decimal),
+            LispExpression::TextLetExpression(v) => v.serialize_as(LeafType::Text),
+            LispExpression::BooleanLetExpression(v) => v.serialize_as(LeafType::Boolean),
+            LispExpression::CallExpression(v) => v.serialize(),
+            LispExpression::CastExpression(v) => v.serialize(),
+            LispExpression::NullExpression(v) => v.serialize(),
+            LispExpression::ListExpression(v) => v.serialize(),
+            LispExpression::CollectionExpression(v) => v.serialize(),
+            LispExpression::TemporalExpression(v) => v.serialize(),
+            LispExpression::TemporalComparatorExpression(v) => v.serialize(),
+            LispExpression::MembershipExpression(v) => v.serialize(),
+            LispExpression::TextExpression(v) => v.serialize(),
+            LispExpression::RegexExpression(v) => v.serialize(),
+            LispExpression::DotExpression(v) => v.serialize(),
+        }
+    }
+
+    // Parses decimals from their text, as going through f64 would lose precision
+    pub(crate) fn deserialize_decimal(val: &str) -> Result<BigDecimal, CustomError> {
+        match BigDecimal::from_str(val) {
+            Ok(v) if within_scale(&v) => Ok(v),
+            _ => Err(CustomError::Message(Message::ErrInvalidLiteral)),
+        }
+    }
+
+    pub(crate) fn deserialize_to_number(val: &Value) -> Result<Box<dyn ToValue<i32>>, CustomError> {
+        match val {
+            Value::Number(v) => match v.is_f64() {
+                true => Ok(Box::new(Self::deserialize_decimal(&v.to_string())?)),
+                false => match v.as_i64().and_then(|v1| v1.to_i32()) {
+                    Some(v1) => Ok(Box::new(v1)),
+                    None => Err(CustomError::Message(Message::ErrOverflow)),
+                },
+            },
+            Value::String(v) => Ok(Box::new(Self::deserialize_decimal(v)?)),
+            Value::Null => Ok(Box::new(Null)),
+            Value::Object(_) => match Self::deserialize(val.clone())? {
+                LispExpression::NumberArithmeticExpression(v) => Ok(Box::new(v)),
+                LispExpression::DecimalArithmeticExpression(v) => Ok(Box::new(v)),
+                LispExpression::RoundExpression(v) => Ok(Box::new(v)),
+                LispExpression::MatchExpression(v)
+                    if matches!(v.result_type, LeafType::Number | LeafType::Decimal) =>
+                {
+                    Ok(Box::new(v))
+                }
+                LispExpression::NumberConditionalExpression(v) => Ok(Box::new(v)),
+                LispExpression::DecimalConditionalExpression(v) => Ok(Box::new(v)),
+                LispExpression::NumberLetExpression(v) => Ok(Box::new(v)),
+                LispExpression::DecimalLetExpression(v) => Ok(Box::new(v)),
+                LispExpression::CallExpression(v) => Ok(Box::new(v)),
+                LispExpression::CastExpression(v) => Ok(Box::new(v)),
+                LispExpression::NullExpression(v) => Ok(Box::new(v)),
+                LispExpression::ListExpression(v) => Ok(Box::new(v)),
+                LispExpression::CollectionExpression(v) => Ok(Box::new(v)),
+                LispExpression::TemporalExpression(v) => Ok(Box::new(v)),
+                LispExpression::TextExpression(v) => Ok(Box::new(v)),
+                LispExpression::DotExpression(v) => Ok(Box::new(v)),
+                _ => Err(CustomError::Message(Message::ErrTypeMismatch)),
+            },
+            _ => Err(CustomError::Message(Message::ErrTypeMismatch)),
+        }
+    }
+
+    // Decimal literals must be integers where a Number is compared exactly, rather than
+    // rounded to one
+    pub(crate) fn deserialize_to_integer(
+        val: &Value,
+    ) -> Result<Box<dyn ToValue<i32>>, CustomError> {
+        let text = match val {
+            Value::Number(v) if v.is_f64() => v.to_string(),
+            Value::String(v) => v.to_string(),
+            _ => return Self::deserialize_to_number(val),
+        };
+        match Self::deserialize_decimal(&text)?.is_integer() {
+            true => Self::deserialize_to_number(val),
+            false => Err(CustomError::Message(Message::ErrTypeMismatch)),
+        }
+    }
+
+    pub(crate) fn deserialize_to_decimal(
+        val: &Value,
+    ) -> Result<Box<dyn ToValue<BigDecimal>>, CustomError> {
+        match val {
+            Value::Number(v) => match v.as_i64().and_then(|v1| v1.to_i32()) {
+                Some(v1) => Ok(Box::new(v1)),
+                None => Ok(Box::new(Self::deserialize_decimal(&v.to_string())?)),
+            },
+            Value::String(v) => Ok(Box::new(Self::deserialize_decimal(v)?)),
+            Value::Null => Ok(Box::new(Null)),
+            Value::Object(_) => match Self::deserialize(val.clone())? {
+                LispExpression::NumberArithmeticExpression(v) => Ok(Box::new(v)),
+                LispExpression::DecimalArithmeticExpression(v) => Ok(Box::new(v)),
+                LispExpression::RoundExpression(v) => Ok(Box::new(v)),
+                LispExpression::MatchExpression(v)
+                    if matches!(v.result_type, LeafType::Number | LeafType::Decimal) =>
+                {
+                    Ok(Box::new(v))
+                }
+                LispExpression::NumberConditionalExpression(v) => Ok(Box::new(v)),
+                LispExpression::DecimalConditionalExpression(v) => Ok(Box::new(v)),
+                LispExpression::NumberLetExpression(v) => Ok(Box::new(v)),
+                LispExpression::DecimalLetExpression(v) => Ok(Box::new(v)),
+                LispExpression::CallExpression(v) => Ok(Box::new(v)),
+                LispExpression::CastExpression(v) => Ok(Box::new(v)),
+                LispExpression::NullExpression(v) => Ok(Box::new(v)),
+                LispExpression::ListExpression(v) => Ok(Box::new(v)),
+                LispExpression::CollectionExpression(v) => Ok(Box::new(v)),
+                LispExpression::TemporalExpression(v) => Ok(Box::new(v)),
+                LispExpression::TextExpression(v) => Ok(Box::new(v)),
+                LispExpression::DotExpression(v) => Ok(Box::new(v)),
+                _ => Err(CustomError::Message(Message::ErrTypeMismatch)),
+            },
+            _ => Err(CustomError::Message(Message::ErrTypeMismatch)),
+        }
+    }
+
+    pub(crate) fn deserialize_to_text(
+        val: &Value,
+    ) -> Result<Box<dyn ToValue<String>>, CustomError> {
+        match val {
+            Value::Number(v) => match v.as_i64().and_then(|v1| v1.to_i32()) {
+                Some(v1) => Ok(Box::new(v1)),
+                None => Ok(Box::new(Self::deserialize_decimal(&v.to_string())?)),
+            },
+            Value::String(v) => Ok(Box::new(v.to_string())),
+            Value::Bool(v) => Ok(Box::new(v.to_string())),
+            Value::Null => Ok(Box::new(Null)),
+            Value::Object(_) => match Self::deserialize(val.clone())? {
+                LispExpression::NumberArithmeticExpression(v) => Ok(Box::new(v)),
+                LispExpression::DecimalArithmeticExpression(v) => Ok(Box::new(v)),
+                LispExpression::RoundExpression(v) => Ok(Box::new(v)),
+                LispExpression::NumberComparatorExpression(v) => Ok(Box::new(v)),
+                LispExpression::DecimalComparatorExpression(v) => Ok(Box::new(v)),
+                LispExpression::TextComparatorExpression(v) => Ok(Box::new(v)),
+                LispExpression::LogicalBinaryExpression(v) => Ok(Box::new(v)),
+                LispExpression::LogicalUnaryExpression(v) => Ok(Box::new(v)),
+                LispExpression::LogicalVariadicExpression(v) => Ok(Box::new(v)),
+                LispExpression::MatchExpression(v) => Ok(Box::new(v)),
+                LispExpression::NumberConditionalExpression(v) => Ok(Box::new(v)),
+                LispExpression::DecimalConditionalExpression(v) => Ok(Box::new(v)),
+                LispExpression::TextConditionalExpression(v) => Ok(Box::new(v)),
+                LispExpression::BooleanConditionalExpression(v) => Ok(Box::new(v)),
+                LispExpression::NumberLetExpression(v) => Ok(Box::new(v)),
+                LispExpression::DecimalLetExpression(v) => Ok(Box::new(v)),
+                LispExpression::TextLetExpression(v) => Ok(Box::new(v)),
+                LispExpression::BooleanLetExpression(v) => Ok(Box::new(v)),
+                LispExpression::CallExpression(v) => Ok(Box::new(v)),
+                LispExpression::CastExpression(v) => Ok(Box::new(v)),
+                LispExpression::NullExpression(v) => Ok(Box::new(v)),
+                LispExpression::ListExpression(v) => Ok(Box::new(v)),
+                LispExpression::CollectionExpression(v) => Ok(Box::new(v)),
+                LispExpression::TemporalExpression(v) => Ok(Box::new(v)),
+                LispExpression::TemporalComparatorExpression(v) => Ok(Box::new(v)),
+                LispExpression::MembershipExpression(v) => Ok(Box::new(v)),
+                LispExpression::TextExpression(v) => Ok(Box::new(v)),
+                LispExpression::RegexExpression(v) => Ok(Box::new(v)),
+                LispExpression::DotExpression(v) => Ok(Box::new(v)),
+            },
+            _ => Err(CustomError::Message(Message::ErrTypeMismatch)),
+        }
+    }
+
+    pub(crate) fn deserialize_to_boolean(
+        val: &Value,
+    ) -> Result<Box<dyn ToValue<bool>>, CustomError> {
+        match val {
+            Value::String(v) => match v.parse::<bool>() {
+                Ok(v1) => Ok(Box::new(v1)),
+                Err(_) => Err(CustomError::Message(Message::ErrInvalidLiteral)),
+            },
+            Value::Bool(v) => Ok(Box::new(*v)),
+            Value::Null => Ok(Box::new(Null)),
+            Value::Object(_) => match Self::deserialize(val.clone())? {
+                LispExpression::NumberComparatorExpression(v) => Ok(Box::new(v)),
+                LispExpression::DecimalComparatorExpression(v) => Ok(Box::new(v)),
+                LispExpression::TextComparatorExpression(v) => Ok(Box::new(v)),
+                LispExpression::LogicalBinaryExpression(v) => Ok(Box::new(v)),
+                LispExpression::LogicalUnaryExpression(v) => Ok(Box::new(v)),
+                LispExpression::LogicalVariadicExpression(v) => Ok(Box::new(v)),
+                LispExpression::MatchExpression(v) if v.result_type == LeafType::Boolean => {
+                    Ok(Box::new(v))
+                }
+                LispExpression::BooleanConditionalExpression(v) => Ok(Box::new(v)),
+                LispExpression::BooleanLetExpression(v) => Ok(Box::new(v)),
+                LispExpression::CallExpression(v) => Ok(Box::new(v)),
+                LispExpression::CastExpression(v) => Ok(Box::new(v)),
+                LispExpression::NullExpression(v) => Ok(Box::new(v)),
+                LispExpression::ListExpression(v) => Ok(Box::new(v)),
+                LispExpression::CollectionExpression(v) => Ok(Box::new(v)),
+                LispExpression::TemporalComparatorExpression(v) => Ok(Box::new(v)),
+                LispExpression::MembershipExpression(v) => Ok(Box::new(v)),
+                LispExpression::TextExpression(v) => Ok(Box::new(v)),
+                LispExpression::RegexExpression(v) => Ok(Box::new(v)),
+                LispExpression::DotExpression(v) => Ok(Box::new(v)),
+                _ => Err(CustomError::Message(Message::ErrTypeMismatch)),
+            },
+            _ => Err(CustomError::Message(Message::ErrTypeMismatch)),
+        }
+    }
+
+    pub(crate) fn deserialize_to_list(val: &Value) -> Result<Box<dyn ToValue<List>>, CustomError> {
+        match val {
+            Value::Null => Ok(Box::new(Null)),
+            Value::Object(_) => match Self::deserialize(val.clone())? {
+                LispExpression::CollectionExpression(v) => Ok(Box::new(v)),
+                LispExpression::DotExpression(v) => Ok(Box::new(v)),
+                _ => Err(CustomError::Message(Message::ErrTypeMismatch)),
+            },
+            _ => Err(CustomError::Message(Message::ErrTypeMismatch)),
+        }
+    }
+
+    // Temporal literals are written in their ISO 8601 forms
+    pub(crate) fn deserialize_to_temporal<T: Temporal>(
+        val: &Value,
+    ) -> Result<Box<dyn ToValue<T>>, CustomError> {
+        match val {
+            Value::String(v) => match T::parse(v) {
+                Some(v1) => Ok(Box::new(v1)),
+                None => Err(CustomError::Message(Message::ErrInvalidLiteral)),
+            },
+            Value::Null => Ok(Box::new(Null)),
+            Value::Object(_) => match Self::deserialize(val.clone())? {
+                LispExpression::MatchExpression(v) if v.result_type == T::LEAF_TYPE => {
+                    Ok(Box::new(v))
+                }
+                LispExpression::CallExpression(v) => Ok(Box::new(v)),
+                LispExpression::NullExpression(v) => Ok(Box::new(v)),
+                LispExpression::ListExpression(v) => Ok(Box::new(v)),
+                LispExpression::CollectionExpression(v) => Ok(Box::new(v)),
+                LispExpression::TemporalExpression(v) => Ok(Box::new(v)),
+                LispExpression::DotExpression(v) => Ok(Box::new(v)),
+                _ => Err(CustomError::Message(Message::ErrTypeMismatch)),
+            },
+            _ => Err(CustomError::Message(Message::ErrTypeMismatch)),
+        }
+    }
+
+    pub(crate) fn deserialize_to_string(val: &Value) -> Result<String, CustomError> {
+        match val {
+            Value::Number(v) => Ok(Self::deserialize_decimal(&v.to_string())?.to_string()),
+            Value::String(v) => Ok(v.to_string()),
+            Value::Bool(v) => Ok(v.to_string()),
+            _ => Err(CustomError::Message(Message::ErrTypeMismatch)),
+        }
+    }
+
+    // Deserializes the argument at index, locating any error at its position in args
+    pub(crate) fn deserialize_arg<T: ?Sized>(
+        args: &[Value],
+        index: usize,
+        deserializer: fn(&Value) -> Result<Box<T>, CustomError>,
+    ) -> Result<Box<T>, CustomError> {
+        match args.get(index) {
+            Some(v) => deserializer(v).map_err(|e| e.at(&format!("/args/{}", index))),
+            None => Err(CustomError::Message(Message::ErrWrongArity).at("/args")),
+        }
+    }
+
+    // Deserializes the arguments starting from index
+    pub(crate) fn deserialize_args<T: ?Sized>(
+        args: &[Value],
+        index: usize,
+        deserializer: fn(&Value) -> Result<Box<T>, CustomError>,
+    ) -> Result<Vec<Box<T>>, CustomError> {
+        (index..args.len())
+            .map(|val| Self::deserialize_arg(args, val, deserializer))
+            .collect()
+    }
+
+    // Deserializes guards, [[condition, result], ...]
+    pub(crate) fn deserialize_guards<T, U: ?Sized>(
+        values: &[Value],
+        condition_deserializer: impl Fn(&Value) -> Result<T, CustomError>,
+        result_deserializer: fn(&Value) -> Result<Box<U>, CustomError>,
+    ) -> Result<Vec<(T, Box<U>)>, CustomError> {
+        values
+            .iter()
+            .enumerate()
+            .map(|(index, val)| {
+                let pointer = format!("/{}", index);
+                match val {
+                    Value::Array(v) => match (v.first(), v.get(1)) {
+                        (Some(v1), Some(v2)) => Ok((
+                            condition_deserializer(v1)
+                                .map_err(|e| e.at(&format!("{}/0", pointer)))?,
+                            result_deserializer(v2).map_err(|e| e.at(&format!("{}/1", pointer)))?,
+                        )),
+                        _ => Err(CustomError::Message(Message::ErrWrongArity).at(&pointer)),
+                    },
+                    _ => Err(CustomError::Message(Message::ErrTypeMismatch).at(&pointer)),
+                }
+            })
+            .collect()
+    }
+
+    pub(crate) fn deserialize_match_guards<T, U: ?Sized>(
+        values: &[Value],
+        condition_deserializer: OperandDeserializer<T>,
+        result_deserializer: fn(&Value) -> Result<Box<U>, CustomError>,
+    ) -> Result<Guards<T, U>, CustomError> {
+        Self::deserialize_guards(
+            values,
+            |val| Self::deserialize_guard(val, condition_deserializer),
+            result_deserializer,
+        )
+    }
+
+    pub(crate) fn deserialize_guard<T>(
+        val: &Value,
+        deserializer: OperandDeserializer<T>,
+    ) -> Result<Guard<T>, CustomError> {
+        let values: &[Value] = match val {
+            Value::Array(v) => v,
+            _ => return Ok(Guard::Equals(deserializer(val)?)),
+        };
+        let locate = |index: usize| move |e: CustomError| e.at(&format!("/{}", index));
+        match (values.first().and_then(|v| v.as_str()), values.len()) {
+            (Some("in"), v) if v > 1 => Ok(Guard::In(
+                values
+                    .iter()
+                    .enumerate()
+                    .skip(1)
+                    .map(|(index, val1)| deserializer(val1).map_err(locate(index)))
+                    .collect::<Result<Vec<Box<dyn ToValue<T>>>, CustomError>>()?,
+            )),
+            (Some("between"), 3) => Ok(Guard::Between(
+                deserializer(&values[1]).map_err(locate(1))?,
+                deserializer(&values[2]).map_err(locate(2))?,
+            )),
+            (Some("where"), 3) => match &values[1] {
+                Value::String(v) => Ok(Guard::Where(Lambda {
+                    name: v.to_string(),
+                    body: Self::deserialize_as(&values[2], LeafType::Boolean).map_err(locate(2))?,
+                })),
+                _ => Err(CustomError::Message(Message::ErrTypeMismatch).at("/1")),
+            },
+            (Some("in" | "between" | "where"), _) => {
+                Err(CustomError::Message(Message::ErrWrongArity))
+            }
+            _ => Ok(Guard::Equals(deserializer(val)?)),
+        }
+    }
+
+    pub(crate) fn deserialize_type(
+        val: Option<&Value>,
+        pointer: &str,
+    ) -> Result<LeafType, CustomError> {
+        match val.and_then(LeafType::deserialize) {
+            Some(v) => Ok(v),
+            None => Err(CustomError::Message(Message::ErrTypeMismatch).at(pointer)),
+        }
+    }
+
+    pub(crate) fn deserialize_arithmetic(
+        op: &str,
+        result_type: Option<&Value>,
+        args: &[Value],
+    ) -> Result<LispExpression, CustomError> {
+        let operator: ArithmeticOperator = match op {
+            "+" => ArithmeticOperator::Add,
+            "*" => ArithmeticOperator::Multiply,
+            "-" => ArithmeticOperator::Subtract,
+            "/" => ArithmeticOperator::Divide,
+            "%" => ArithmeticOperator::Modulus,
+            "min" => ArithmeticOperator::Min,
+            "max" => ArithmeticOperator::Max,
+            "pow" => ArithmeticOperator::Pow,
+            "abs" => ArithmeticOperator::Abs,
+            "sqrt" => ArithmeticOperator::Sqrt,
+            "sign" => ArithmeticOperator::Sign,
+            "floor" => ArithmeticOperator::Floor,
+            "ceil" => ArithmeticOperator::Ceil,
+            "clamp" => ArithmeticOperator::Clamp,
+            "percent" => ArithmeticOperator::Percent,
+            _ => return Err(CustomError::Message(Message::ErrUnknownOperator).at("/op")),
+        };
+        match operator.arity() {
+            Some(v) if v != args.len() => {
+                return Err(CustomError::Message(Message::ErrWrongArity).at("/args"))
+            }
+            _ => {}
+        }
+        match Self::deserialize_type(result_type, "/type")? {
+            LeafType::Number => {
+                let v = (
+                    Self::deserialize_arg(args, 0, Self::deserialize_to_number)?,
+                    Self::deserialize_args(args, 1, Self::deserialize_to_number)?,
+                );
+                match operator {
+                    ArithmeticOperator::Add => Ok(NumberArithmeticExpression::Add(v)),
+                    ArithmeticOperator::Multiply => Ok(NumberArithmeticExpression::Multiply(v)),
+                    ArithmeticOperator::Subtract => Ok(NumberArithmeticExpression::Subtract(v)),
+                    ArithmeticOperator::Divide => Ok(NumberArithmeticExpression::Divide(v)),
+                    ArithmeticOperator::Modulus => Ok(NumberArithmeticExpression::Modulus(v)),
+                    ArithmeticOperator::Min => Ok(NumberArithmeticExpression::Min(v)),
+                    ArithmeticOperator::Max => Ok(NumberArithmeticExpression::Max(v)),
+                    ArithmeticOperator::Pow => Ok(NumberArithmeticExpression::Pow(v)),
+                    ArithmeticOperator::Abs => Ok(NumberArithmeticExpression::Abs(v)),
+                    ArithmeticOperator::Sqrt => Ok(NumberArithmeticExpression::Sqrt(v)),
+                    ArithmeticOperator::Sign => Ok(NumberArithmeticExpression::Sign(v)),
+                    ArithmeticOperator::Clamp => Ok(NumberArithmeticExpression::Clamp(v)),
+                    ArithmeticOperator::Percent => Ok(NumberArithmeticExpression::Percent(v)),
+                    // Numbers are already whole
+                    ArithmeticOperator::Floor | ArithmeticOperator::Ceil => {
+                        Err(CustomError::Message(Message::ErrTypeMismatch).at("/type"))
+                    }
+                }
+                .map(LispExpression::NumberArithmeticExpression)
+            }
+            LeafType::Decimal => {
+                let v = (
+                    Self::deserialize_arg(args, 0, Self::deserialize_to_decimal)?,
+                    Self::deserialize_args(args, 1, Self::deserialize_to_decimal)?,
+                );
+                let expr = match operator {
+                    ArithmeticOperator::Add => DecimalArithmeticExpression::Add(v),
+                    ArithmeticOperator::Multiply => DecimalArithmeticExpression::Multiply(v),
+                    ArithmeticOperator::Subtract => DecimalArithmeticExpression::Subtract(v),
+                    ArithmeticOperator::Divide => DecimalArithmeticExpression::Divide(v),
+                    ArithmeticOperator::Modulus => DecimalArithmeticExpression::Modulus(v),
+                    ArithmeticOperator::Min => DecimalArithmeticExpression::Min(v),
+                    ArithmeticOperator::Max => DecimalArithmeticExpression::Max(v),
+                    ArithmeticOperator::Pow => DecimalArithmeticExpression::Pow(v),
+                    ArithmeticOperator::Abs => DecimalArithmeticExpression::Abs(v),
+                    ArithmeticOperator::Sqrt => DecimalArithmeticExpression::Sqrt(v),
+                    ArithmeticOperator::Sign => DecimalArithmeticExpression::Sign(v),
+                    ArithmeticOperator::Floor => DecimalArithmeticExpression::Floor(v),
+                    ArithmeticOperator::Ceil => DecimalArithmeticExpression::Ceil(v),
+                    ArithmeticOperator::Clamp => DecimalArithmeticExpression::Clamp(v),
+                    ArithmeticOperator::Percent => DecimalArithmeticExpression::Percent(v),
+                };
+                Ok(LispExpression::DecimalArithmeticExpression(expr))
+            }
+            _ => Err(CustomError::Message(Message::ErrTypeMismatch).at("/type")),
+        }
+    }
+
+    pub(crate) fn deserialize_comparator(
+        op: &str,
+        result_type: Option<&Value>,
+        args: &[Value],
+    ) -> Result<LispExpression, CustomError> {
+        match Self::deserialize_type(result_type, "/type")? {
+            LeafType::Number => {
+                let v = (
+                    Self::deserialize_arg(args, 0, Self::deserialize_to_number)?,
+                    Self::deserialize_arg(args, 1, Self::deserialize_to_number)?,
+                    Self::deserialize_args(args, 2, Self::deserialize_to_number)?,
+                );
+                match op {
+                    "==" => Ok(NumberComparatorExpression::Equals(v)),
+                    "!=" => Ok(NumberComparatorExpression::NotEquals(v)),
+                    ">=" => Ok(NumberComparatorExpression::GreaterThanEquals(v)),
+                    "<=" => Ok(NumberComparatorExpression::LessThanEquals(v)),
+                    ">" => Ok(NumberComparatorExpression::GreaterThan(v)),
+                    "<" => Ok(NumberComparatorExpression::LessThan(v)),
+                    _ => Err(CustomError::Message(Message::ErrUnknownOperator).at("/op")),
+                }
+                .map(LispExpression::NumberComparatorExpression)
+            }
+            LeafType::Decimal => {
+                let v = (
+                    Self::deserialize_arg(args, 0, Self::deserialize_to_decimal)?,
+                    Self::deserialize_arg(args, 1, Self::deserialize_to_decimal)?,
+                    Self::deserialize_args(args, 2, Self::deserialize_to_decimal)?,
+                );
+                match op {
+                    "==" => Ok(DecimalComparatorExpression::Equals(v)),
+                    "!=" => Ok(DecimalComparatorExpression::NotEquals(v)),
+                    ">=" => Ok(DecimalComparatorExpression::GreaterThanEquals(v)),
+                    "<=" => Ok(DecimalComparatorExpression::LessThanEquals(v)),
+                    ">" => Ok(DecimalComparatorExpression::GreaterThan(v)),
+                    "<" => Ok(DecimalComparatorExpression::LessThan(v)),
+                    _ => Err(CustomError::Message(Message::ErrUnknownOperator).at("/op")),
+                }
+                .map(LispExpression::DecimalComparatorExpression)
+            }
+            LeafType::Text => {
+                let v = (
+                    Self::deserialize_arg(args, 0, Self::deserialize_to_text)?,
+                    Self::deserialize_arg(args, 1, Self::deserialize_to_text)?,
+                    Self::deserialize_args(args, 2, Self::deserialize_to_text)?,
+                );
+                match op {
+                    "==" => Ok(TextComparatorExpression::Equals(v)),
+                    "!=" => Ok(TextComparatorExpression::NotEquals(v)),
+                    ">=" => Ok(TextComparatorExpression::GreaterThanEquals(v)),
+                    "<=" => Ok(TextComparatorExpression::LessThanEquals(v)),
+                    ">" => Ok(TextComparatorExpression::GreaterThan(v)),
+                    "<" => Ok(TextComparatorExpression::LessThan(v)),
+                    _ => Err(CustomError::Message(Message::ErrUnknownOperator).at("/op")),
+                }
+                .map(LispExpression::TextComparatorExpression)
+            }
+            v @ (LeafType::Date | LeafType::DateTime | LeafType::Duration) => {
+                let operator = match op {
+                    "==" => ComparatorOperator::Equals,
+                    "!=" => ComparatorOperator::NotEquals,
+                    ">=" => ComparatorOperator::GreaterThanEquals,
+                    "<=" => ComparatorOperator::LessThanEquals,
+                    ">" => ComparatorOperator::GreaterThan,
+                    "<" => ComparatorOperator::LessThan,
+                    _ => return Err(CustomError::Message(Message::ErrUnknownOperator).at("/op")),
+                };
+                if args.len() < 2 {
+                    return Err(CustomError::Message(Message::ErrWrongArity).at("/args"));
+                }
+                Ok(LispExpression::TemporalComparatorExpression(
+                    TemporalComparatorExpression {
+                        operator,
+                        args: args
+                            .iter()
+                            .enumerate()
+                            .map(|(index, val)| {
+                                Self::deserialize_as(val, v)
+                                    .map_err(|e| e.at(&format!("/args/{}", index)))
+                            })
+                            .collect::<Result<Vec<Binding>, CustomError>>()?,
+                    },
+                ))
+            }
+            _ => Err(CustomError::Message(Message::ErrTypeMismatch).at("/type")),
+        }
+    }
+
+    pub(crate) fn deserialize_membership(
+        op: &str,
+        result_type: Option<&Value>,
+        args: &[Value],
+    ) -> Result<LispExpression, CustomError> {
+        let arity = match op {
+            "in" | "not_in" => args.len() >= 2,
+            _ => args.len() == 3,
+        };
+        if !arity {
+            return Err(CustomError::Message(Message::ErrWrongArity).at("/args"));
+        }
+        let expr =
+            match Self::deserialize_type(result_type, "/type")? {
+                LeafType::Number => MembershipExpression::Number(Self::deserialize_membership_as(
+                    op,
+                    args,
+                    Self::deserialize_to_integer,
+                )?),
+                LeafType::Decimal => MembershipExpression::Decimal(
+                    Self::deserialize_membership_as(op, args, Self::deserialize_to_decimal)?,
+                ),
+                LeafType::Text => MembershipExpression::Text(Self::deserialize_membership_as(
+                    op,
+                    args,
+                    Self::deserialize_to_text,
+                )?),
+                _ => return Err(CustomError::Message(Message::ErrTypeMismatch).at("/type")),
+            };
+        Ok(LispExpression::MembershipExpression(expr))
+    }
+
+    pub(crate) fn deserialize_membership_as<T: Eq + Hash>(
+        op: &str,
+        args: &[Value],
+        deserializer: OperandDeserializer<T>,
+    ) -> Result<Membership<T>, CustomError> {
+        let value = Self::deserialize_arg(args, 0, deserializer)?;
+        match op {
+            "in" | "not_in" => {
+                let candidates = Self::deserialize_args(args, 1, deserializer)?;
+                // literals evaluate the same in any context, nulls are left to evaluation
+                let lookup = match args[1..].iter().all(|val| !val.is_object()) {
+                    true => {
+                        let symbols = HashMap::new();
+                        let context = Context::new(&symbols);
+                        candidates
+                            .iter()
+                            .map(|val| val.get_value(&context).ok())
+                            .collect()
+                    }
+                    false => None,
+                };
+                Ok(Membership::In {
+                    negated: op == "not_in",
+                    value,
+                    candidates,
+                    lookup,
+                })
+            }
+            _ => Ok(Membership::Between {
+                exclusive: op == "between_exclusive",
+                value,
+                low: Self::deserialize_arg(args, 1, deserializer)?,
+                high: Self::deserialize_arg(args, 2, deserializer)?,
+            }),
+        }
+    }
+
+    pub(crate) fn deserialize_logical_binary(
+        op: &str,
+        _result_type: Option<&Value>,
+        args: &[Value],
+    ) -> Result<LispExpression, CustomError> {
+        if (op == "implies" || op == "iff") && args.len() > 2 {
+            return Err(CustomError::Message(Message::ErrWrongArity).at("/args"));
+        }
+        let v = (
+            Self::deserialize_arg(args, 0, Self::deserialize_to_boolean)?,
+            Self::deserialize_arg(args, 1, Self::deserialize_to_boolean)?,
+            Self::deserialize_args(args, 2, Self::deserialize_to_boolean)?,
+        );
+        match op {
+            "and" => Ok(LogicalBinaryExpression::And(v)),
+            "or" => Ok(LogicalBinaryExpression::Or(v)),
+            "xor" => Ok(LogicalBinaryExpression::Xor(v)),
+            "implies" => Ok(LogicalBinaryExpression::Implies(v)),
+            "iff" => Ok(LogicalBinaryExpression::Iff(v)),
+            "nand" => Ok(LogicalBinaryExpression::Nand(v)),
+            "nor" => Ok(LogicalBinaryExpression::Nor(v)),
+            _ => Err(CustomError::Message(Message::ErrUnknownOperator).at("/op")),
+        }
+        .map(LispExpression::LogicalBinaryExpression)
+    }
+
+    pub(crate) fn deserialize_logical_unary(
+        _op: &str,
+        _result_type: Option<&Value>,
+        args: &[Value],
+    ) -> Result<LispExpression, CustomError> {
+        Ok(LispExpression::LogicalUnaryExpression(
+            LogicalUnaryExpression {
+                value: Self::deserialize_arg(args, 0, Self::deserialize_to_boolean)?,
+            },
+        ))
+    }
+
+    pub(crate) fn deserialize_logical_variadic(
+        op: &str,
+        _result_type: Option<&Value>,
+        args: &[Value],
+    ) -> Result<LispExpression, CustomError> {
+        let (quantifier, index) = match op {
+            "all_of" => (LogicalQuantifier::All, 0),
+            "any_of" => (LogicalQuantifier::Any, 0),
+            "none_of" => (LogicalQuantifier::None, 0),
+            _ => (
+                LogicalQuantifier::Exactly(Self::deserialize_arg(
+                    args,
+                    0,
+                    Self::deserialize_to_number,
+                )?),
+                1,
+            ),
+        };
+        Ok(LispExpression::LogicalVariadicExpression(
+            LogicalVariadicExpression {
+                quantifier,
+                args: Self::deserialize_args(args, index, Self::deserialize_to_boolean)?,
+            },
+        ))
+    }
+
+    pub(crate) fn deserialize_text(
+        op: &str,
+        result_type: Option<&Value>,
+        args: &[Value],
+    ) -> Result<LispExpression, CustomError> {
+        if result_type.is_some() && (op == "length" || op == "contains") {
+            return Self::deserialize_list(op, result_type, args);
+        }
+        let maximum: usize = match op {
+            "concat" | "join" => usize::MAX,
+            "length" | "upper" | "lower" | "trim" => 1,
+            "starts_with" | "ends_with" | "contains" => 2,
+            _ => 3,
+        };
+        if args.len() > maximum {
+            return Err(CustomError::Message(Message::ErrWrongArity).at("/args"));
+        }
+        let text = |index: usize| Self::deserialize_arg(args, index, Self::deserialize_to_text);
+        let number = |index: usize| Self::deserialize_arg(args, index, Self::deserialize_to_number);
+        let expr = match op {
+            "concat" => TextExpression::Concat(
+                std::iter::once(text(0))
+                    .chain((1..args.len()).map(text))
+                    .collect::<Result<Vec<Box<dyn ToValue<String>>>, CustomError>>()?,
+            ),
+            "length" => TextExpression::Length(text(0)?),
+            "substring" => TextExpression::Substring {
+                text: text(0)?,
+                start: number(1)?,
+                length: match args.len() > 2 {
+                    true => Some(number(2)?),
+                    false => None,
+                },
+            },
+            "upper" => TextExpression::Upper(text(0)?),
+            "lower" => TextExpression::Lower(text(0)?),
+            "trim" => TextExpression::Trim(text(0)?),
+            "starts_with" => TextExpression::StartsWith {
+                text: text(0)?,
+                part: text(1)?,
+            },
+            "ends_with" => TextExpression::EndsWith {
+                text: text(0)?,
+                part: text(1)?,
+            },
+            "contains" => TextExpression::Contains {
+                text: text(0)?,
+                part: text(1)?,
+            },
+            "replace" => TextExpression::Replace {
+                text: text(0)?,
+                from: text(1)?,
+                to: text(2)?,
+            },
+            "split" => TextExpression::Split {
+                text: text(0)?,
+                separator: text(1)?,
+                index: number(2)?,
+            },
+            "join" => TextExpression::Join {
+                separator: text(0)?,
+                texts: std::iter::once(text(1))
+                    .chain((2..args.len()).map(text))
+                    .collect::<Result<Vec<Box<dyn ToValue<String>>>, CustomError>>()?,
+            },
+            _ => {
+                let (text, width) = (text(0)?, number(1)?);
+                let padding = match args.len() > 2 {
+                    true => Some(Self::deserialize_arg(args, 2, Self::deserialize_to_text)?),
+                    false => None,
+                };
+                match op {
+                    "pad_start" => TextExpression::PadStart {
+                        text,
+                        width,
+                        padding,
+                    },
+                    _ => TextExpression::PadEnd {
+                        text,
+                        width,
+                        padding,
+                    },
+                }
+            }
+        };
+        Ok(LispExpression::TextExpression(expr))
+    }
+
+    pub(crate) fn deserialize_regex(
+        op: &str,
+        _result_type: Option<&Value>,
+        args: &[Value],
+    ) -> Result<LispExpression, CustomError> {
+        let maximum: usize = match op {
+            "regex_match" => 2,
+            _ => 3,
+        };
+        if args.len() > maximum {
+            return Err(CustomError::Message(Message::ErrWrongArity).at("/args"));
+        }
+        let text = Self::deserialize_arg(args, 0, Self::deserialize_to_text)?;
+        let anchored = op == "regex_match";
+        let pattern = Pattern {
+            value: Self::deserialize_arg(args, 1, Self::deserialize_to_text)?,
+            anchored,
+            cache: RefCell::new(match &args[1] {
+                Value::String(v) => Some((
+                    v.to_string(),
+                    Pattern::compile(v, anchored).map_err(|e| e.at("/args/1"))?,
+                )),
+                _ => None,
+            }),
+        };
+        let expr = match op {
+            "regex_match" => RegexExpression::Match { text, pattern },
+            "regex_find" => RegexExpression::Find {
+                text,
+                pattern,
+                group: match args.len() > 2 {
+                    true => Some(Self::deserialize_arg(args, 2, Self::deserialize_to_number)?),
+                    false => None,
+                },
+            },
+            _ => RegexExpression::Replace {
+                text,
+                pattern,
+                replacement: Self::deserialize_arg(args, 2, Self::deserialize_to_text)?,
+            },
+        };
+        Ok(LispExpression::RegexExpression(expr))
+    }
+
+    pub(crate) fn deserialize_match(
+        _op: &str,
+        result_type: Option<&Value>,
+        args: &[Value],
+    ) -> Result<LispExpression, CustomError> {
+        let (return_type, conditional_type) = match result_type {
+            Some(Value::Array(v)) => (
+                Self::deserialize_type(v.first(), "/type/0")?,
+                Self::deserialize_type(v.get(1), "/type/1")?,
+            ),
+            _ => return Err(CustomError::Message(Message::ErrTypeMismatch).at("/type")),
+        };
+        let guards: &Vec<Value> = match args.get(1) {
+            Some(Value::Array(v)) => v,
+            Some(_) => return Err(CustomError::Message(Message::ErrTypeMismatch).at("/args/1")),
+            None => return Err(CustomError::Message(Message::ErrWrongArity).at("/args")),
+        };
+        let expr = match return_type {
+            LeafType::Number => Self::deserialize_match_as::<i32>(conditional_type, guards, args),
+            LeafType::Decimal => {
+                Self::deserialize_match_as::<BigDecimal>(conditional_type, guards, args)
+            }
+            LeafType::Text => Self::deserialize_match_as::<String>(conditional_type, guards, args),
+            LeafType::Boolean => Self::deserialize_match_as::<bool>(conditional_type, guards, args),
+            LeafType::Date => {
+                Self::deserialize_match_as::<NaiveDate>(conditional_type, guards, args)
+            }
+            LeafType::DateTime => {
+                Self::deserialize_match_as::<DateTime<FixedOffset>>(conditional_type, guards, args)
+            }
+            LeafType::Duration => {
+                Self::deserialize_match_as::<Duration>(conditional_type, guards, args)
+            }
+        }?;
+        Ok(LispExpression::MatchExpression(expr))
+    }
+
+    // Reads a match with results of type U, given the type of its condition
+    pub(crate) fn deserialize_match_as<U: Matchable>(
+        conditional_type: LeafType,
+        guards: &[Value],
+        args: &[Value],
+    ) -> Result<MatchExpression, CustomError> {
+        Ok(match conditional_type {
+            LeafType::Number => {
+                MatchExpression::new(Self::deserialize_match_over::<i32, U>(guards, args)?)
+            }
+            LeafType::Decimal => {
+                MatchExpression::new(Self::deserialize_match_over::<BigDecimal, U>(guards, args)?)
+            }
+            LeafType::Text => {
+                MatchExpression::new(Self::deserialize_match_over::<String, U>(guards, args)?)
+            }
+            LeafType::Boolean => {
+                MatchExpression::new(Self::deserialize_match_over::<bool, U>(guards, args)?)
+            }
+            LeafType::Date => {
+                MatchExpression::new(Self::deserialize_match_over::<NaiveDate, U>(guards, args)?)
+            }
+            LeafType::DateTime => MatchExpression::new(Self::deserialize_match_over::<
+                DateTime<FixedOffset>,
+                U,
+            >(guards, args)?),
+            LeafType::Duration => {
+                MatchExpression::new(Self::deserialize_match_over::<Duration, U>(guards, args)?)
+            }
+        })
+    }
+
+    pub(crate) fn deserialize_match_over<T: Matchable, U: Matchable>(
+        guards: &[Value],
+        args: &[Value],
+    ) -> Result<Match<T, U>, CustomError> {
+        Ok(Match {
+            condition: Self::deserialize_arg(args, 0, T::DESERIALIZER)?,
+            guards: Self::deserialize_match_guards(guards, T::DESERIALIZER, U::DESERIALIZER)
+                .map_err(|e| e.at("/args/1"))?,
+            otherwise: Self::deserialize_arg(args, 2, U::DESERIALIZER)?,
+        })
+    }
+
+    pub(crate) fn deserialize_round(
+        _op: &str,
+        _result_type: Option<&Value>,
+        args: &[Value],
+    ) -> Result<LispExpression, CustomError> {
+        if args.len() > 3 {
+            return Err(CustomError::Message(Message::ErrWrongArity).at("/args"));
+        }
+        let value = Self::deserialize_arg(args, 0, Self::deserialize_to_decimal)?;
+        let scale: i64 = match args.get(1) {
+            Some(v) => match v.as_i64() {
+                Some(v1) if v1.unsigned_abs() <= MAX_SCALE as u64 => v1,
+                Some(_) => return Err(CustomError::Message(Message::ErrOutOfRange).at("/args/1")),
+                None => return Err(CustomError::Message(Message::ErrInvalidLiteral).at("/args/1")),
+            },
+            None => return Err(CustomError::Message(Message::ErrWrongArity).at("/args")),
+        };
+        let mode: Option<RoundingMode> = match args.get(2) {
+            Some(v) => match RoundingMode::deserialize(v) {
+                Some(v1) => Some(v1),
+                None => return Err(CustomError::Message(Message::ErrInvalidLiteral).at("/args/2")),
+            },
+            None => None,
+        };
+        Ok(LispExpression::RoundExpression(RoundExpression {
+            value,
+            scale,
+            mode,
+        }))
+    }
+
+    pub(crate) fn deserialize_branches<T>(
+        op: &str,
+        args: &[Value],
+        deserializer: OperandDeserializer<T>,
+    ) -> Result<ConditionalExpression<T>, CustomError> {
+        match op {
+            "if" => match args.len() > 3 {
+                true => Err(CustomError::Message(Message::ErrWrongArity).at("/args")),
+                false => Ok(ConditionalExpression {
+                    operator: ConditionalOperator::If,
+                    guards: vec![(
+                        Self::deserialize_arg(args, 0, Self::deserialize_to_boolean)?,
+                        Self::deserialize_arg(args, 1, deserializer)?,
+                    )],
+                    otherwise: Self::deserialize_arg(args, 2, deserializer)?,
+                }),
+            },
+            _ => {
+                let guards: &Vec<Value> = match (args.first(), args.len() > 2) {
+                    (Some(Value::Array(v)), false) => v,
+                    (Some(_), false) => {
+                        return Err(CustomError::Message(Message::ErrTypeMismatch).at("/args/0"))
+                    }
+                    _ => return Err(CustomError::Message(Message::ErrWrongArity).at("/args")),
+                };
+                Ok(ConditionalExpression {
+                    operator: ConditionalOperator::Cond,
+                    guards: Self::deserialize_guards(
+                        guards,
+                        Self::deserialize_to_boolean,
+                        deserializer,
+                    )
+                    .map_err(|e| e.at("/args/0"))?,
+                    otherwise: Self::deserialize_arg(args, 1, deserializer)?,
+                })
+            }
+        }
+    }
+
+    pub(crate) fn deserialize_conditional(
+        op: &str,
+        result_type: Option<&Value>,
+        args: &[Value],
+    ) -> Result<LispExpression, CustomError> {
+        match Self::deserialize_type(result_type, "/type")? {
+            LeafType::Number => Ok(LispExpression::NumberConditionalExpression(
+                Self::deserialize_branches(op, args, Self::deserialize_to_number)?,
+            )),
+            LeafType::Decimal => Ok(LispExpression::DecimalConditionalExpression(
+                Self::deserialize_branches(op, args, Self::deserialize_to_decimal)?,
+            )),
+            LeafType::Text => Ok(LispExpression::TextConditionalExpression(
+                Self::deserialize_branches(op, args, Self::deserialize_to_text)?,
+            )),
+            LeafType::Boolean => Ok(LispExpression::BooleanConditionalExpression(
+                Self::deserialize_branches(op, args, Self::deserialize_to_boolean)?,
+            )),
+            _ => Err(CustomError::Message(Message::ErrTypeMismatch).at("/type")),
+        }
+    }
+
+    // Deserializes the value following its declared type, at index in values
+    pub(crate) fn deserialize_typed(
+        values: &[Value],
+        index: usize,
+    ) -> Result<Binding, CustomError> {
+        let binding_type = Self::deserialize_type(values.get(index), &format!("/{}", index))?;
+        let val: &Value = match values.get(index + 1) {
+            Some(v) => v,
+            None => return Err(CustomError::Message(Message::ErrWrongArity)),
+        };
+        Self::deserialize_as(val, binding_type).map_err(|e| e.at(&format!("/{}", index + 1)))
+    }
+
+    pub(crate) fn deserialize_as(
+        val: &Value,
+        binding_type: LeafType,
+    ) -> Result<Binding, CustomError> {
+        match binding_type {
+            LeafType::Number => Ok(Binding::Number(Self::deserialize_to_number(val)?)),
+            LeafType::Decimal => Ok(Binding::Decimal(Self::deserialize_to_decimal(val)?)),
+            LeafType::Text => Ok(Binding::Text(Self::deserialize_to_text(val)?)),
+            LeafType::Boolean => Ok(Binding::Boolean(Self::deserialize_to_boolean(val)?)),
+            LeafType::Date => Ok(Binding::Date(Self::deserialize_to_temporal(val)?)),
+            LeafType::DateTime => Ok(Binding::DateTime(Self::deserialize_to_temporal(val)?)),
+            LeafType::Duration => Ok(Binding::Duration(Self::deserialize_to_temporal(val)?)),
+        }
+    }
+
+    // Deserializes the argument at index, [type, value]
+    pub(crate) fn deserialize_typed_arg(
+        args: &[Value],
+        index: usize,
+    ) -> Result<Binding, CustomError> {
+        let pointer = format!("/args/{}", index);
+        match args.get(index) {
+            Some(Value::Array(v)) if v.len() == 2 => {
+                Self::deserialize_typed(v, 0).map_err(|e| e.at(&pointer))
+            }
+            Some(Value::Array(_)) => Err(CustomError::Message(Message::ErrWrongArity).at(&pointer)),
+            Some(_) => Err(CustomError::Message(Message::ErrTypeMismatch).at(&pointer)),
+            None => Err(CustomError::Message(Message::ErrWrongArity).at("/args")),
+        }
+    }
+
+    // Deserializes a binding, [name, type, value]
+    pub(crate) fn deserialize_binding(val: &Value) -> Result<(String, Binding), CustomError> {
+        match val {
+            Value::Array(v) => match v.as_slice() {
+                [Value::String(v1), _, _] => Ok((v1.to_string(), Self::deserialize_typed(v, 1)?)),
+                [_, _, _] => Err(CustomError::Message(Message::ErrTypeMismatch).at("/0")),
+                _ => Err(CustomError::Message(Message::ErrWrongArity)),
+            },
+            _ => Err(CustomError::Message(Message::ErrTypeMismatch)),
+        }
+    }
+
+    pub(crate) fn deserialize_bindings<T>(
+        args: &[Value],
+        deserializer: OperandDeserializer<T>,
+    ) -> Result<LetExpression<T>, CustomError> {
+        let bindings: &Vec<Value> = match (args.first(), args.len() > 2) {
+            (Some(Value::Array(v)), false) => v,
+            (Some(_), false) => {
+                return Err(CustomError::Message(Message::ErrTypeMismatch).at("/args/0"))
+            }
+            _ => return Err(CustomError::Message(Message::ErrWrongArity).at("/args")),
+        };
+        Ok(LetExpression {
+            bindings: bindings
+                .iter()
+                .enumerate()
+                .map(|(index, val)| {
+                    Self::deserialize_binding(val).map_err(|e| e.at(&format!("/args/0/{}", index)))
+                })
+                .collect::<Result<Vec<(String, Binding)>, CustomError>>()?,
+            body: Self::deserialize_arg(args, 1, deserializer)?,
+        })
+    }
+
+    pub(crate) fn deserialize_let(
+        _op: &str,
+        result_type: Option<&Value>,
+        args: &[Value],
+    ) -> Result<LispExpression, CustomError> {
+        match Self::deserialize_type(result_type, "/type")? {
+            LeafType::Number => Ok(LispExpression::NumberLetExpression(
+                Self::deserialize_bindings(args, Self::deserialize_to_number)?,
+            )),
+            LeafType::Decimal => Ok(LispExpression::DecimalLetExpression(
+                Self::deserialize_bindings(args, Self::deserialize_to_decimal)?,
+            )),
+            LeafType::Text => Ok(LispExpression::TextLetExpression(
+                Self::deserialize_bindings(args, Self::deserialize_to_text)?,
+            )),
+            LeafType::Boolean => Ok(LispExpression::BooleanLetExpression(
+                Self::deserialize_bindings(args, Self::deserialize_to_boolean)?,
+            )),
+            _ => Err(CustomError::Message(Message::ErrTypeMismatch).at("/type")),
+        }
+    }
+
+    pub(crate) fn deserialize_call(
+        _op: &str,
+        result_type: Option<&Value>,
+        args: &[Value],
+    ) -> Result<LispExpression, CustomError> {
+        let result_type = Self::deserialize_type(result_type, "/type")?;
+        let name: String = match args.first() {
+            Some(Value::String(v)) => v.to_string(),
+            Some(_) => return Err(CustomError::Message(Message::ErrTypeMismatch).at("/args/0")),
+            None => return Err(CustomError::Message(Message::ErrWrongArity).at("/args")),
+        };
+        Ok(LispExpression::CallExpression(CallExpression {
+            result_type,
+            name,
+            args: (1..args.len())
+                .map(|index| Self::deserialize_typed_arg(args, index))
+                .collect::<Result<Vec<Binding>, CustomError>>()?,
+        }))
+    }
+
+    pub(crate) fn deserialize_cast(
+        op: &str,
+        _result_type: Option<&Value>,
+        args: &[Value],
+    ) -> Result<LispExpression, CustomError> {
+        let maximum: usize = match op {
+            "to_number" | "parse_number" | "parse_decimal" => 2,
+            _ => 1,
+        };
+        if args.len() > maximum {
+            return Err(CustomError::Message(Message::ErrWrongArity).at("/args"));
+        }
+        let expr = match op {
+            "to_number" => CastExpression::ToNumber {
+                value: Self::deserialize_typed_arg(args, 0)?,
+                mode: match args.get(1) {
+                    Some(v) => match RoundingMode::deserialize(v) {
+                        Some(v1) => Some(v1),
+                        None => {
+                            return Err(
+                                CustomError::Message(Message::ErrInvalidLiteral).at("/args/1")
+                            )
+                        }
+                    },
+                    None => None,
+                },
+            },
+            "to_decimal" => CastExpression::ToDecimal(Self::deserialize_typed_arg(args, 0)?),
+            "to_text" => CastExpression::ToText(Self::deserialize_typed_arg(args, 0)?),
+            "to_boolean" => CastExpression::ToBoolean(Self::deserialize_typed_arg(args, 0)?),
+            "parse_number" => CastExpression::ParseNumber {
+                text: Self::deserialize_arg(args, 0, Self::deserialize_to_text)?,
+                default: match args.len() > 1 {
+                    true => Some(Self::deserialize_arg(args, 1, Self::deserialize_to_number)?),
+                    false => None,
+                },
+            },
+            _ => CastExpression::ParseDecimal {
+                text: Self::deserialize_arg(args, 0, Self::deserialize_to_text)?,
+                default: match args.len() > 1 {
+                    true => Some(Self::deserialize_arg(
+                        args,
+                        1,
+                        Self::deserialize_to_decimal,
+                    )?),
+                    false => None,
+                },
+            },
+        };
+        Ok(LispExpression::CastExpression(expr))
+    }
+
+    pub(crate) fn deserialize_list(
+        op: &str,
+        result_type: Option<&Value>,
+        args: &[Value],
+    ) -> Result<LispExpression, CustomError> {
+        let item_type = Self::deserialize_type(result_type, "/type")?;
+        let arity: usize = match op {
+            "contains" => 2,
+            _ => 1,
+        };
+        if args.len() != arity {
+            return Err(CustomError::Message(Message::ErrWrongArity).at("/args"));
+        }
+        let list = Self::deserialize_arg(args, 0, Self::deserialize_to_list)?;
+        let expr = match op {
+            "length" => ListExpression::Length { item_type, list },
+            "contains" => ListExpression::Contains {
+                list,
+                value: Self::deserialize_as(&args[1], item_type).map_err(|e| e.at("/args/1"))?,
+            },
+            "first" => ListExpression::First { item_type, list },
+            _ => ListExpression::Last { item_type, list },
+        };
+        Ok(LispExpression::ListExpression(expr))
+    }
+
+    // Deserializes a lambda, [name, body], at index in args
+    pub(crate) fn deserialize_lambda(
+        args: &[Value],
+        index: usize,
+        body_type: LeafType,
+    ) -> Result<Lambda, CustomError> {
+        let name: String = match args.get(index) {
+            Some(Value::String(v)) => v.to_string(),
+            Some(_) => {
+                return Err(
+                    CustomError::Message(Message::ErrTypeMismatch).at(&format!("/args/{}", index))
+                )
+            }
+            None => return Err(CustomError::Message(Message::ErrWrongArity).at("/args")),
+        };
+        match args.get(index + 1) {
+            Some(v) => Ok(Lambda {
+                name,
+                body: Self::deserialize_as(v, body_type)
+                    .map_err(|e| e.at(&format!("/args/{}", index + 1)))?,
+            }),
+            None => Err(CustomError::Message(Message::ErrWrongArity).at("/args")),
+        }
+    }
+
+    pub(crate) fn deserialize_collection(
+        op: &str,
+        result_type: Option<&Value>,
+        args: &[Value],
+    ) -> Result<LispExpression, CustomError> {
+        let arity_matches = match op {
+            "reduce" => args.len() == 5,
+            "count" | "sum" | "avg" => args.len() == 1 || args.len() == 3,
+            _ => args.len() == 3,
+        };
+        if !arity_matches {
+            return Err(CustomError::Message(Message::ErrWrongArity).at("/args"));
+        }
+        let list = Self::deserialize_arg(args, 0, Self::deserialize_to_list)?;
+        let expr = match op {
+            "map" => {
+                let result_type = Self::deserialize_type(result_type, "/type")?;
+                CollectionExpression::Map {
+                    result_type,
+                    list,
+                    lambda: Self::deserialize_lambda(args, 1, result_type)?,
+                }
+            }
+            "filter" => CollectionExpression::Filter {
+                list,
+                lambda: Self::deserialize_lambda(args, 1, LeafType::Boolean)?,
+            },
+            "any" => CollectionExpression::Any {
+                list,
+                lambda: Self::deserialize_lambda(args, 1, LeafType::Boolean)?,
+            },
+            "all" => CollectionExpression::All {
+                list,
+                lambda: Self::deserialize_lambda(args, 1, LeafType::Boolean)?,
+            },
+            "count" => CollectionExpression::Count {
+                list,
+                lambda: match args.len() > 1 {
+                    true => Some(Self::deserialize_lambda(args, 1, LeafType::Boolean)?),
+                    false => None,
+                },
+            },
+            "sum" | "avg" => {
+                let result_type = match Self::deserialize_type(result_type, "/type")? {
+                    LeafType::Text | LeafType::Boolean => {
+                        return Err(CustomError::Message(Message::ErrTypeMismatch).at("/type"))
+                    }
+                    v => v,
+                };
+                let lambda = match args.len() > 1 {
+                    true => Some(Self::deserialize_lambda(args, 1, result_type)?),
+                    false => None,
+                };
+                match op {
+                    "sum" => CollectionExpression::Sum {
+                        result_type,
+                        list,
+                        lambda,
+                    },
+                    _ => CollectionExpression::Avg {
+                        result_type,
+                        list,
+                        lambda,
+                    },
+                }
+            }
+            _ => {
+                let result_type = Self::deserialize_type(result_type, "/type")?;
+                let (item, accumulator) = match (&args[1], &args[2]) {
+                    (Value::String(v), Value::String(v1)) => (v.to_string(), v1.to_string()),
+                    (Value::String(_), _) => {
+                        return Err(CustomError::Message(Message::ErrTypeMismatch).at("/args/2"))
+                    }
+                    _ => return Err(CustomError::Message(Message::ErrTypeMismatch).at("/args/1")),
+                };
+                CollectionExpression::Reduce {
+                    result_type,
+                    list,
+                    item,
+                    accumulator,
+                    initial: Self::deserialize_as(&args[3], result_type)
+                        .map_err(|e| e.at("/args/3"))?,
+                    body: Self::deserialize_as(&args[4], result_type)
+                        .map_err(|e| e.at("/args/4"))?,
+                }
+            }
+        };
+        Ok(LispExpression::CollectionExpression(expr))
+    }
+
+    pub(crate) fn deserialize_temporal(
+        op: &str,
+        _result_type: Option<&Value>,
+        args: &[Value],
+    ) -> Result<LispExpression, CustomError> {
+        let arity: usize = match op {
+            "start_of_month" | "end_of_month" | "weekday" => 1,
+            _ => 2,
+        };
+        if args.len() != arity {
+            return Err(CustomError::Message(Message::ErrWrongArity).at("/args"));
+        }
+        let date = |index: usize| Self::deserialize_arg(args, index, Self::deserialize_to_temporal);
+        let date_time =
+            |index: usize| Self::deserialize_arg(args, index, Self::deserialize_to_temporal);
+        let expr = match op {
+            "add_days" => TemporalExpression::AddDays {
+                date: date(0)?,
+                days: Self::deserialize_arg(args, 1, Self::deserialize_to_number)?,
+            },
+            "add_months" => TemporalExpression::AddMonths {
+                date: date(0)?,
+                months: Self::deserialize_arg(args, 1, Self::deserialize_to_number)?,
+            },
+            "diff_days" => TemporalExpression::DiffDays {
+                from: date(0)?,
+                to: date(1)?,
+            },
+            "start_of_month" => TemporalExpression::StartOfMonth(date(0)?),
+            "end_of_month" => TemporalExpression::EndOfMonth(date(0)?),
+            "weekday" => TemporalExpression::Weekday(date(0)?),
+            "age" => TemporalExpression::Age {
+                birth: date(0)?,
+                date: date(1)?,
+            },
+            "add_duration" => TemporalExpression::AddDuration {
+                date_time: date_time(0)?,
+                duration: Self::deserialize_arg(args, 1, Self::deserialize_to_temporal)?,
+            },
+            _ => TemporalExpression::DurationBetween {
+                from: date_time(0)?,
+                to: date_time(1)?,
+            },
+        };
+        Ok(LispExpression::TemporalExpression(expr))
+    }
+
+    pub(crate) fn deserialize_null(
+        op: &str,
+        result_type: Option<&Value>,
+        args: &[Value],
+    ) -> Result<LispExpression, CustomError> {
+        let expr = match op {
+            "exists" => match Self::deserialize_dot(op, result_type, args)? {
+                LispExpression::DotExpression(v) => NullExpression::Exists(v),
+                _ => return Err(CustomError::Message(Message::ErrUnexpected)),
+            },
+            "is_null" => match args.len() > 1 {
+                true => return Err(CustomError::Message(Message::ErrWrongArity).at("/args")),
+                false => NullExpression::IsNull(Self::deserialize_typed_arg(args, 0)?),
+            },
+            _ => {
+                let result_type = Self::deserialize_type(result_type, "/type")?;
+                if args.is_empty() {
+                    return Err(CustomError::Message(Message::ErrWrongArity).at("/args"));
+                }
+                NullExpression::Coalesce {
+                    result_type,
+                    args: args
+                        .iter()
+                        .enumerate()
+                        .map(|(index, val)| {
+                            Self::deserialize_as(val, result_type)
+                                .map_err(|e| e.at(&format!("/args/{}", index)))
+                        })
+                        .collect::<Result<Vec<Binding>, CustomError>>()?,
+                }
+            }
+        };
+        Ok(LispExpression::NullExpression(expr))
+    }
+
+    pub(crate) fn deserialize_dot(
+        _op: &str,
+        _result_type: Option<&Value>,
+        args: &[Value],
+    ) -> Result<LispExpression, CustomError> {
+        match args.is_empty() {
+            true => Err(CustomError::Message(Message::ErrWrongArity).at("/args")),
+            false => Ok(LispExpression::DotExpression(DotExpression {
+                path: args
+                    .iter()
+                    .enumerate()
+                    .map(|(index, val)| {
+                        Self::deserialize_to_string(val)
+                            .map_err(|e| e.at(&format!("/args/{}", index)))
+                    })
+                    .collect::<Result<Vec<String>, CustomError>>()?,
+            })),
+        }
+    }
+
+    // Errors are located by JSON pointer into the deserialized value
+    pub fn deserialize(json: Value) -> Result<LispExpression, CustomError> {
+        match &json {
+            Value::Object(v) => match (v.get("op"), v.get("args")) {
+                (Some(Value::String(v1)), Some(Value::Array(v2))) => {
+                    match Self::OPERATORS.iter().find(|(op, _)| op == v1) {
+                        Some((_, deserializer)) => deserializer(v1, v.get("type"), v2),
+                        None => Err(CustomError::Message(Message::ErrUnknownOperator).at("/op")),
+                    }
+                }
+                (Some(Value::String(_)), _) => {
+                    Err(CustomError::Message(Message::ErrDeserialization).at("/args"))
+                }
+                _ => Err(CustomError::Message(Message::ErrDeserialization).at("/op")),
+            },
+            _ => Err(CustomError::Message(Message::ErrDeserialization)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod expression_tests {
+    use std::collections::HashSet;
+
+    use super::*;
+    use crate::context::OverflowPolicy;
+    use crate::error::Language;
+    use crate::function::Function;
+    use crate::symbol::{Leaf, Symbol};
+    use serde_json::json;
+
+    #[test]
+    fn test_located_errors() {
+        let symbols: HashMap<String, Symbol> = vec![
+            (
+                "x".to_string(),
+                Symbol {
+                    value: Some(Leaf::Text("a".to_string())),
+                    values: HashMap::new(),
+                },
+            ),
+            (
+                "z".to_string(),
+                Symbol {
+                    value: None,
+                    values: vec![(
+                        "z".to_string(),
+                        Symbol {
+                            value: Some(Leaf::Number(6)),
+                            values: HashMap::new(),
+                        },
+                    )]
+                    .into_iter()
+                    .collect(),
+                },
+            ),
+        ]
+        .into_iter()
+        .collect();
+        let deserialization_error = |val: Value| match LispExpression::deserialize(val) {
+            Ok(_) => Value::Null,
+            Err(e) => e.serialize(&Language::English),
+        };
+        assert_eq!(
+            json!({"/args/1/args/1/0/1": "Type mismatch"}),
+            deserialization_error(json!({
+                "op": "+",
+                "type": "Number",
+                "args": [1, {"op": "match", "type": ["Number", "Text"], "args": ["a", [["a", true]], 1]}]
+            }))
+        );
+        assert_eq!(
+            json!({"/args/0/op": "Unknown operator"}),
+            deserialization_error(json!({
+                "op": "+",
+                "type": "Number",
+                "args": [{"op": "^", "type": "Number", "args": [1]}]
+            }))
+        );
+        assert_eq!(
+            json!({"/args": "Wrong number of arguments"}),
+            deserialization_error(json!({"op": "not", "args": []}))
+        );
+        assert_eq!(
+            json!({"/args/1": "Invalid literal"}),
+            deserialization_error(json!({"op": "and", "args": [true, "yes"]}))
+        );
+        assert_eq!(
+            json!({"/args/0": "Invalid literal"}),
+            deserialization_error(
+                json!({"op": "floor", "type": "Decimal", "args": ["1e100000000"]})
+            )
+        );
+        assert_eq!(
+            json!({"/args/0": "Invalid literal"}),
+            deserialization_error(json!({
+                "op": "+",
+                "type": "Decimal",
+                "args": ["1e-100000000", "1e100000000"]
+            }))
+        );
+        assert_eq!(
+            Value::Null,
+            deserialization_error(
+                json!({"op": "+", "type": "Decimal", "args": ["1e-1024", "1e1024"]})
+            )
+        );
+        assert_eq!(
+            json!({"/args/0": "Arithmetic overflow"}),
+            deserialization_error(json!({"op": "+", "type": "Number", "args": [3000000000i64]}))
+        );
+        assert_eq!(
+            json!({"/type": "Type mismatch"}),
+            deserialization_error(json!({"op": "+", "type": "Text", "args": [1]}))
+        );
+        let evaluation_error = |val: Value| match LispExpression::deserialize(val) {
+            Ok(v) => match v.as_text(&Context::new(&symbols)) {
+                Ok(_) => Value::Null,
+                Err(e) => e.serialize(&Language::English),
+            },
+            Err(_) => Value::Null,
+        };
+        assert_eq!(
+            json!({"/args/1/args/1": "Symbol not found"}),
+            evaluation_error(json!({
+                "op": "+",
+                "type": "Number",
+                "args": [1, {"op": ".", "args": ["z", "y"]}]
+            }))
+        );
+        assert_eq!(
+            json!({"/args/1/0/1/args/0": "Type mismatch"}),
+            evaluation_error(json!({
+                "op": "match",
+                "type": ["Text", "Number"],
+                "args": [
+                    {"op": ".", "args": ["z", "z"]},
+                    [[6, {"op": "not", "args": [{"op": ".", "args": ["x"]}]}]],
+                    "b"
+                ]
+            }))
+        );
+    }
+
+    #[test]
+    fn test_overflow_policy() {
+        let symbols: HashMap<String, Symbol> = HashMap::new();
+        let eval = |text: &str, overflow: OverflowPolicy| {
+            let expr = LispExpression::parse(text).unwrap();
+            let context = Context {
+                overflow,
+                ..Context::new(&symbols)
+            };
+            match expr.as_text(&context) {
+                Ok(v) => json!(v),
+                Err(e) => e.serialize(&Language::English),
+            }
+        };
+        let text = "(+:Number 2147483647 1 -5)";
+        assert_eq!(
+            json!({"/args/1": "Arithmetic overflow"}),
+            eval(text, OverflowPolicy::Error)
+        );
+        assert_eq!(json!("2147483642"), eval(text, OverflowPolicy::Saturate));
+        assert_eq!(json!("2147483643"), eval(text, OverflowPolicy::Wrap));
+        assert_eq!(json!("2147483643"), eval(text, OverflowPolicy::Promote));
+        let text = "(/:Number -2147483648 -1 3)";
+        assert_eq!(
+            json!({"/args/1": "Arithmetic overflow"}),
+            eval(text, OverflowPolicy::Error)
+        );
+        assert_eq!(json!("715827882"), eval(text, OverflowPolicy::Saturate));
+        assert_eq!(json!("-715827882"), eval(text, OverflowPolicy::Wrap));
+        assert_eq!(json!("715827882"), eval(text, OverflowPolicy::Promote));
+        assert_eq!(
+            json!("0"),
+            eval("(%:Number -2147483648 -1)", OverflowPolicy::Error)
+        );
+        for overflow in [
+            OverflowPolicy::Error,
+            OverflowPolicy::Saturate,
+            OverflowPolicy::Wrap,
+            OverflowPolicy::Promote,
+        ] {
+            assert_eq!(
+                json!({"/args/2": "Division by zero"}),
+                eval("(/:Number 1 1 0)", overflow)
+            );
+            assert_eq!(
+                json!({"/args/1": "Division by zero"}),
+                eval("(%:Decimal 1.5 0)", overflow)
+            );
+        }
+        // A promoted result still has to fit when a Number is required
+        let expr = LispExpression::parse("(*:Number 65536 65536)").unwrap();
+        let context = Context {
+            overflow: OverflowPolicy::Promote,
+            ..Context::new(&symbols)
+        };
+        assert_eq!(
+            json!("Arithmetic overflow"),
+            expr.as_number(&context)
+                .unwrap_err()
+                .serialize(&Language::English)
+        );
+        assert_eq!(
+            BigDecimal::from(4294967296i64),
+            expr.as_decimal(&context).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_math_expression() {
+        let symbols: HashMap<String, Symbol> = vec![(
+            "price".to_string(),
+            Symbol {
+                value: Some(Leaf::Decimal(BigDecimal::from_str("-12.34").unwrap())),
+                values: HashMap::new(),
+            },
+        )]
+        .into_iter()
+        .collect();
+        let expr = DecimalArithmeticExpression::Clamp((
+            Box::new(DecimalArithmeticExpression::Abs((
+                Box::new(DotExpression {
+                    path: vec!["price".to_string()],
+                }),
+                vec![],
+            ))),
+            vec![
+                Box::new(BigDecimal::from(0)),
+                Box::new(BigDecimal::from(10)),
+            ],
+        ));
+        let res: BigDecimal = expr.get_value(&Context::new(&symbols)).unwrap();
+        assert_eq!(BigDecimal::from(10), res);
+        // eval == serialize.deserialize.eval
+        assert_eq!(
+            res,
+            (match LispExpression::deserialize(
+                (&expr as &dyn ToValue<BigDecimal>).serialize().unwrap()
+            ) {
+                Ok(v) => match v {
+                    LispExpression::DecimalArithmeticExpression(v) =>
+                        (&v as &dyn ToValue<BigDecimal>).get_value(&Context::new(&symbols)),
+                    _ => Err(CustomError::Message(Message::ErrUnexpected)),
+                },
+                Err(_) => Err(CustomError::Message(Message::ErrUnexpected)),
+            })
+            .unwrap()
+        );
+        // serialize == serialize.deserialize.serialize
+        assert_eq!(
+            (&expr as &dyn ToValue<BigDecimal>).serialize().unwrap(),
+            match LispExpression::deserialize(
+                (&expr as &dyn ToValue<BigDecimal>).serialize().unwrap()
+            ) {
+                Ok(v) => match v {
+                    LispExpression::DecimalArithmeticExpression(v) =>
+                        (&v as &dyn ToValue<BigDecimal>).serialize().unwrap(),
+                    _ => Value::Null,
+                },
+                Err(_) => Value::Null,
+            }
+        );
+        let eval = |text: &str, overflow: OverflowPolicy| {
+            let expr = LispExpression::parse(text).unwrap();
+            let context = Context {
+                overflow,
+                ..Context::new(&symbols)
+            };
+            match expr.as_text(&context) {
+                Ok(v) => json!(v),
+                Err(e) => e.serialize(&Language::English),
+            }
+        };
+        let policy = OverflowPolicy::Error;
+        assert_eq!(json!("-2"), eval("(min:Number 3 -2 7)", policy));
+        assert_eq!(json!("7"), eval("(max:Number 3 -2 7)", policy));
+        assert_eq!(json!("-12.34"), eval("(min:Decimal (. price) 1.5)", policy));
+        assert_eq!(json!("1024"), eval("(pow:Number 2 10)", policy));
+        assert_eq!(json!("64"), eval("(pow:Number 2 3 2)", policy));
+        assert_eq!(json!("0.25"), eval("(pow:Decimal 2 -2)", policy));
+        assert_eq!(json!("12.34"), eval("(abs:Decimal (. price))", policy));
+        assert_eq!(json!("4"), eval("(sqrt:Number 17)", policy));
+        assert_eq!(json!("-1"), eval("(sign:Decimal (. price))", policy));
+        assert_eq!(json!("0"), eval("(sign:Number 0)", policy));
+        assert_eq!(json!("-13"), eval("(floor:Decimal (. price))", policy));
+        assert_eq!(json!("-12"), eval("(ceil:Decimal (. price))", policy));
+        assert_eq!(json!("5"), eval("(clamp:Number 12 -5 5)", policy));
+        assert_eq!(json!("-5"), eval("(clamp:Number -12 -5 5)", policy));
+        assert_eq!(json!("37"), eval("(percent:Number 250 15)", policy));
+        assert_eq!(json!("1.851"), eval("(percent:Decimal 12.34 15)", policy));
+        // Operands of fixed arity ops are evaluated once, so nesting them stays linear
+        let text = format!("{}(. price){}", "(abs:Decimal ".repeat(64), ")".repeat(64));
+        assert_eq!(json!("12.34"), eval(&text, policy));
+        // Results outside a Number follow the overflow policy
+        let text = "(pow:Number 2 31)";
+        assert_eq!(
+            json!({"/args/1": "Arithmetic overflow"}),
+            eval(text, OverflowPolicy::Error)
+        );
+        assert_eq!(json!("2147483647"), eval(text, OverflowPolicy::Saturate));
+        assert_eq!(json!("-2147483648"), eval(text, OverflowPolicy::Wrap));
+        assert_eq!(json!("2147483648"), eval(text, OverflowPolicy::Promote));
+        let text = "(abs:Number -2147483648)";
+        assert_eq!(
+            json!({"/args/0": "Arithmetic overflow"}),
+            eval(text, OverflowPolicy::Error)
+        );
+        assert_eq!(json!("2147483647"), eval(text, OverflowPolicy::Saturate));
+        assert_eq!(json!("2147483648"), eval(text, OverflowPolicy::Promote));
+        assert_eq!(
+            json!({"/args/1": "Invalid argument"}),
+            eval("(pow:Number 2 -1)", policy)
+        );
+        assert_eq!(
+            json!({"/args/1": "Invalid argument"}),
+            eval("(pow:Decimal 2 0.5)", policy)
+        );
+        assert_eq!(
+            json!({"/args/1": "Out of range"}),
+            eval("(pow:Decimal 2 1025)", policy)
+        );
+        assert_eq!(
+            json!({"/args/1": "Out of range"}),
+            eval("(pow:Decimal (pow:Decimal 9.9 1024) 1024)", policy)
+        );
+        assert_eq!(
+            json!({"/args/1": "Out of range"}),
+            eval("(pow:Decimal 1e-1024 1024)", policy)
+        );
+        assert_eq!(
+            json!("3325.26"),
+            eval("(round (pow:Decimal (pow:Decimal 1.5 10) 2) 2)", policy)
+        );
+        assert_eq!(
+            json!({"/args/1": "Division by zero"}),
+            eval("(pow:Decimal 0 -1)", policy)
+        );
+        assert_eq!(
+            json!({"/args/0": "Invalid argument"}),
+            eval("(sqrt:Decimal (. price))", policy)
+        );
+        assert_eq!(
+            json!({"/args/2": "Invalid argument"}),
+            eval("(clamp:Number 1 5 -5)", policy)
+        );
+        assert_eq!(
+            json!({"/type": "Type mismatch"}),
+            match LispExpression::parse("(floor:Number 1)") {
+                Ok(_) => Value::Null,
+                Err(e) => e.serialize(&Language::English),
+            }
+        );
+        assert_eq!(
+            json!({"/args": "Wrong number of arguments"}),
+            match LispExpression::parse("(clamp:Number 1 2)") {
+                Ok(_) => Value::Null,
+                Err(e) => e.serialize(&Language::English),
+            }
+        );
+    }
+
+    // Deterministic generator of well typed expressions, for round-trip properties
+    struct Generator {
+        state: u64,
+    }
+
+    impl Generator {
+        fn next(&mut self, bound: usize) -> usize {
+            self.state ^= self.state << 13;
+            self.state ^= self.state >> 7;
+            self.state ^= self.state << 17;
+            (self.state % bound as u64) as usize
+        }
+
+        fn type_name(leaf_type: LeafType) -> &'static str {
+            match leaf_type {
+                LeafType::Number => "Number",
+                LeafType::Decimal => "Decimal",
+                LeafType::Text => "Text",
+                LeafType::Boolean => "Boolean",
+                LeafType::Date => "Date",
+                LeafType::DateTime => "DateTime",
+                LeafType::Duration => "Duration",
+            }
+        }
+
+        fn leaf(&mut self, leaf_type: LeafType) -> Value {
+            match self.next(3) {
+                0 => json!({"op": ".", "args": [Self::type_name(leaf_type)]}),
+                _ => match leaf_type {
+                    LeafType::Number => json!(self.next(21) as i32 - 10),
+                    LeafType::Decimal => json!(format!(
+                        "{}.{:02}",
+                        self.next(201) as i32 - 100,
+                        self.next(100)
+                    )),
+                    LeafType::Text => json!(["a", "b", "c d"][self.next(3)]),
+                    LeafType::Boolean => json!(self.next(2) == 0),
+                    LeafType::Date => json!(format!(
+                        "2024-{:02}-{:02}",
+                        1 + self.next(12),
+                        1 + self.next(28)
+                    )),
+                    LeafType::DateTime => json!(format!(
+                        "2024-01-{:02}T{:02}:30:00{}",
+                        1 + self.next(28),
+                        self.next(24),
+                        ["+00:00", "+05:30", "-08:00"][self.next(3)]
+                    )),
+                    LeafType::Duration => {
+                        json!(["PT0S", "P1D", "PT2H", "-P1DT2H30M", "PT45.5S"][self.next(5)])
+                    }
+                },
+            }
+        }
+
+        fn args(&mut self, leaf_type: LeafType, count: usize, depth: usize) -> Vec<Value> {
+            (0..count)
+                .map(|_| self.expression(leaf_type, depth - 1))
+                .collect()
+        }
+
+        fn numeric(&mut self) -> LeafType {
+            [LeafType::Number, LeafType::Decimal][self.next(2)]
+        }
+
+        fn matching(&mut self, return_type: LeafType, depth: usize) -> Value {
+            let conditional_type = [
+                LeafType::Number,
+                LeafType::Decimal,
+                LeafType::Text,
+                LeafType::Boolean,
+                LeafType::Date,
+                LeafType::DateTime,
+                LeafType::Duration,
+            ][self.next(7)];
+            let guards: Vec<Value> = (0..self.next(3))
+                .map(|_| {
+                    let guard = match self.next(5) {
+                        0 => {
+                            let count = 1 + self.next(3);
+                            let mut values = vec![json!("in")];
+                            values.extend(self.args(conditional_type, count, depth));
+                            Value::Array(values)
+                        }
+                        1 => {
+                            let mut values = vec![json!("between")];
+                            values.extend(self.args(conditional_type, 2, depth));
+                            Value::Array(values)
+                        }
+                        2 => {
+                            let name = Self::type_name(conditional_type);
+                            json!(["where", name, self.expression(LeafType::Boolean, depth - 1)])
+                        }
+                        _ => self.expression(conditional_type, depth - 1),
+                    };
+                    json!([guard, self.expression(return_type, depth - 1)])
+                })
+                .collect();
+            json!({
+                "op": "match",
+                "type": [Self::type_name(return_type), Self::type_name(conditional_type)],
+                "args": [
+                    self.expression(conditional_type, depth - 1),
+                    guards,
+                    self.expression(return_type, depth - 1)
+                ]
+            })
+        }
+
+        fn conditional(&mut self, return_type: LeafType, depth: usize) -> Value {
+            match self.next(2) {
+                0 => json!({
+                    "op": "if",
+                    "type": Self::type_name(return_type),
+                    "args": [
+                        self.expression(LeafType::Boolean, depth - 1),
+                        self.expression(return_type, depth - 1),
+                        self.expression(return_type, depth - 1)
+                    ]
+                }),
+                _ => {
+                    let guards: Vec<Value> = (0..self.next(3))
+                        .map(|_| {
+                            json!([
+                                self.expression(LeafType::Boolean, depth - 1),
+                                self.expression(return_type, depth - 1)
+                            ])
+                        })
+                        .collect();
+                    json!({
+                        "op": "cond",
+                        "type": Self::type_name(return_type),
+                        "args": [guards, self.expression(return_type, depth - 1)]
+                    })
+                }
+            }
+        }
+
+        // Bindings are named after their type, so that they shadow the symbol of that type
+        fn binding(&mut self, return_type: LeafType, depth: usize) -> Value {
+            let bindings: Vec<Value> = (0..self.next(3))
+                .map(|_| {
+                    let v = [
+                        LeafType::Number,
+                        LeafType::Decimal,
+                        LeafType::Text,
+                        LeafType::Boolean,
+                    ][self.next(4)];
+                    json!([
+                        Self::type_name(v),
+                        Self::type_name(v),
+                        self.expression(v, depth - 1)
+                    ])
+                })
+                .collect();
+            json!({
+                "op": "let",
+                "type": Self::type_name(return_type),
+                "args": [bindings, self.expression(return_type, depth - 1)]
+            })
+        }
+
+        fn text(&mut self, return_type: LeafType, depth: usize) -> Value {
+            let (op, params): (&str, &[LeafType]) = match return_type {
+                LeafType::Number | LeafType::Decimal => ("length", &[LeafType::Text]),
+                LeafType::Boolean => (
+                    ["starts_with", "ends_with", "contains"][self.next(3)],
+                    &[LeafType::Text, LeafType::Text],
+                ),
+                _ => match self.next(9) {
+                    0 => (
+                        "concat",
+                        &[LeafType::Text, LeafType::Text][..1 + self.next(2)],
+                    ),
+                    1 => (
+                        "substring",
+                        &[LeafType::Text, LeafType::Number, LeafType::Number][..2 + self.next(2)],
+                    ),
+                    2 => ("upper", &[LeafType::Text]),
+                    3 => ("lower", &[LeafType::Text]),
+                    4 => ("trim", &[LeafType::Text]),
+                    5 => ("replace", &[LeafType::Text, LeafType::Text, LeafType::Text]),
+                    6 => ("split", &[LeafType::Text, LeafType::Text, LeafType::Number]),
+                    7 => ("join", &[LeafType::Text, LeafType::Text, LeafType::Text]),
+                    _ => (
+                        ["pad_start", "pad_end"][self.next(2)],
+                        &[LeafType::Text, LeafType::Number, LeafType::Text][..2 + self.next(2)],
+                    ),
+                },
+            };
+            let args: Vec<Value> = params
+                .iter()
+                .map(|v| self.expression(*v, depth - 1))
+                .collect();
+            json!({"op": op, "args": args})
+        }
+
+        fn regex(&mut self, return_type: LeafType, depth: usize) -> Value {
+            let pattern = json!(["b", "[a-c]+", "(c) (d)", "^a", "d$", r"\w"][self.next(6)]);
+            let text = self.expression(LeafType::Text, depth - 1);
+            match (return_type, self.next(2)) {
+                (LeafType::Boolean, _) => json!({"op": "regex_match", "args": [text, pattern]}),
+                (_, 0) => {
+                    let mut args = vec![text, pattern];
+                    if self.next(2) == 0 {
+                        args.push(json!(self.next(3)));
+                    }
+                    json!({"op": "regex_find", "args": args})
+                }
+                _ => json!({
+                    "op": "regex_replace",
+                    "args": [text, pattern, self.expression(LeafType::Text, depth - 1)]
+                }),
+            }
+        }
+
+        fn cast(&mut self, return_type: LeafType, depth: usize) -> Value {
+            let op = match return_type {
+                LeafType::Number => ["to_number", "parse_number"][self.next(2)],
+                LeafType::Decimal => ["to_decimal", "parse_decimal"][self.next(2)],
+                LeafType::Boolean => "to_boolean",
+                _ => "to_text",
+            };
+            let mut args: Vec<Value> = match op {
+                "parse_number" | "parse_decimal" => {
+                    vec![self.expression(LeafType::Text, depth - 1)]
+                }
+                _ => {
+                    let v = [
+                        LeafType::Number,
+                        LeafType::Decimal,
+                        LeafType::Text,
+                        LeafType::Boolean,
+                    ][self.next(4)];
+                    vec![json!([Self::type_name(v), self.expression(v, depth - 1)])]
+                }
+            };
+            match (op, self.next(2)) {
+                ("to_number", 0) => {
+                    let mode = ["HalfEven", "HalfUp", "Floor", "Ceiling", "Truncate"];
+                    args.push(json!(mode[self.next(5)]));
+                }
+                ("parse_number", 0) | ("parse_decimal", 0) => {
+                    args.push(self.expression(return_type, depth - 1));
+                }
+                _ => {}
+            }
+            json!({"op": op, "args": args})
+        }
+
+        fn nullable(&mut self, return_type: LeafType, depth: usize) -> Value {
+            match (return_type, self.next(3)) {
+                (LeafType::Boolean, 0) => {
+                    let path = ["Number", "Text", "missing"][self.next(3)];
+                    json!({"op": "exists", "args": [path]})
+                }
+                (LeafType::Boolean, 1) => {
+                    let v = [
+                        LeafType::Number,
+                        LeafType::Decimal,
+                        LeafType::Text,
+                        LeafType::Boolean,
+                    ][self.next(4)];
+                    let value = match self.next(2) {
+                        0 => Value::Null,
+                        _ => self.expression(v, depth - 1),
+                    };
+                    json!({"op": "is_null", "args": [[Self::type_name(v), value]]})
+                }
+                _ => {
+                    let args: Vec<Value> = (0..1 + self.next(3))
+                        .map(|_| match self.next(3) {
+                            0 => Value::Null,
+                            _ => self.expression(return_type, depth - 1),
+                        })
+                        .collect();
+                    json!({
+                        "op": "coalesce",
+                        "type": Self::type_name(return_type),
+                        "args": args
+                    })
+                }
+            }
+        }
+
+        // Reads the list of numbers named List
+        fn list(&mut self, return_type: LeafType, depth: usize) -> Value {
+            let list = json!({"op": ".", "args": ["List"]});
+            match (return_type, self.next(3)) {
+                (LeafType::Boolean, _) => json!({
+                    "op": "contains",
+                    "type": "Number",
+                    "args": [list, self.expression(LeafType::Number, depth - 1)]
+                }),
+                (_, 0) => json!({"op": "length", "type": "Number", "args": [list]}),
+                (_, 1) => {
+                    let op = ["first", "last"][self.next(2)];
+                    json!({"op": op, "type": "Number", "args": [list]})
+                }
+                _ => json!({"op": ".", "args": ["List", self.next(4).to_string()]}),
+            }
+        }
+
+        // Folds the list of numbers named List, binding each item to x
+        fn collection(&mut self, return_type: LeafType, depth: usize) -> Value {
+            let list = json!({"op": ".", "args": ["List"]});
+            let item = json!({"op": ".", "args": ["x"]});
+            let comparator = [">", "<", "=="][self.next(3)];
+            let predicate = json!({
+                "op": comparator,
+                "type": "Number",
+                "args": [item, self.expression(LeafType::Number, depth - 1)]
+            });
+            let result_type = match return_type {
+                LeafType::Decimal => LeafType::Decimal,
+                _ => LeafType::Number,
+            };
+            let op = match return_type {
+                LeafType::Boolean => ["any", "all"][self.next(2)],
+                _ => ["count", "reduce", "sum", "avg"][self.next(4)],
+            };
+            match op {
+                "any" | "all" => json!({"op": op, "args": [list, "x", predicate]}),
+                "count" => match self.next(2) {
+                    0 => json!({"op": op, "args": [list]}),
+                    _ => json!({"op": op, "args": [list, "x", predicate]}),
+                },
+                "reduce" => {
+                    let initial = self.expression(result_type, depth - 1);
+                    let operator = ["+", "max"][self.next(2)];
+                    let body = json!({
+                        "op": operator,
+                        "type": Self::type_name(result_type),
+                        "args": [{"op": ".", "args": ["total"]}, item]
+                    });
+                    json!({
+                        "op": op,
+                        "type": Self::type_name(result_type),
+                        "args": [list, "x", "total", initial, body]
+                    })
+                }
+                _ => {
+                    let list = match self.next(2) {
+                        0 => list,
+                        _ => {
+                            let body = json!({
+                                "op": "*",
+                                "type": Self::type_name(result_type),
+                                "args": [item, self.expression(result_type, depth - 1)]
+                            });
+                            let filtered = json!({"op": "filter", "args": [list, "x", predicate]});
+                            json!({
+                                "op": "map",
+                                "type": Self::type_name(result_type),
+                                "args": [filtered, "x", body]
+                            })
+                        }
+                    };
+                    json!({
+                        "op": op,
+                        "type": Self::type_name(result_type),
+                        "args": [list]
+                    })
+                }
+            }
+        }
+
+        fn temporal(&mut self, return_type: LeafType, depth: usize) -> Value {
+            let (op, params): (&str, &[LeafType]) = match return_type {
+                LeafType::Number | LeafType::Decimal => match self.next(3) {
+                    0 => ("diff_days", &[LeafType::Date, LeafType::Date]),
+                    1 => ("weekday", &[LeafType::Date]),
+                    _ => ("age", &[LeafType::Date, LeafType::Date]),
+                },
+                LeafType::Boolean => {
+                    let v = [LeafType::Date, LeafType::DateTime, LeafType::Duration][self.next(3)];
+                    let count = 2 + self.next(2);
+                    let op = ["==", "!=", ">=", "<=", ">", "<"][self.next(6)];
+                    return json!({
+                        "op": op,
+                        "type": Self::type_name(v),
+                        "args": self.args(v, count, depth)
+                    });
+                }
+                LeafType::DateTime => ("add_duration", &[LeafType::DateTime, LeafType::Duration]),
+                LeafType::Duration => (
+                    "duration_between",
+                    &[LeafType::DateTime, LeafType::DateTime],
+                ),
+                _ => match self.next(4) {
+                    0 => ("add_days", &[LeafType::Date, LeafType::Number]),
+                    1 => ("add_months", &[LeafType::Date, LeafType::Number]),
+                    2 => ("start_of_month", &[LeafType::Date]),
+                    _ => ("end_of_month", &[LeafType::Date]),
+                },
+            };
+            let args: Vec<Value> = params
+                .iter()
+                .map(|v| self.expression(*v, depth - 1))
+                .collect();
+            json!({"op": op, "args": args})
+        }
+
+        fn quantifier(&mut self, depth: usize) -> Value {
+            let op = ["all_of", "any_of", "none_of", "exactly_n_of"][self.next(4)];
+            let count = self.next(4);
+            let mut args = self.args(LeafType::Boolean, count, depth);
+            if op == "exactly_n_of" {
+                args.insert(0, self.expression(LeafType::Number, depth - 1));
+            }
+            json!({"op": op, "args": args})
+        }
+
+        fn membership(&mut self, depth: usize) -> Value {
+            let v = [LeafType::Number, LeafType::Decimal, LeafType::Text][self.next(3)];
+            let (op, count) = match self.next(4) {
+                0 => ("in", 2 + self.next(4)),
+                1 => ("not_in", 2 + self.next(4)),
+                2 => ("between", 3),
+                _ => ("between_exclusive", 3),
+            };
+            json!({
+                "op": op,
+                "type": Self::type_name(v),
+                "args": self.args(v, count, depth)
+            })
+        }
+
+        // Calls the identity function registered under the name of the type
+        fn call(&mut self, return_type: LeafType, depth: usize) -> Value {
+            json!({
+                "op": "call",
+                "type": Self::type_name(return_type),
+                "args": [
+                    Self::type_name(return_type),
+                    [Self::type_name(return_type), self.expression(return_type, depth - 1)]
+                ]
+            })
+        }
+
+        // Generates a value, in the form serializers emit, that deserializes wherever
+        // leaf_type is expected
+        fn expression(&mut self, leaf_type: LeafType, depth: usize) -> Value {
+            if depth == 0 {
+                return self.leaf(leaf_type);
+            }
+            match leaf_type {
+                LeafType::Number | LeafType::Decimal => match self.next(13) {
+                    0 => {
+                        let v = self.numeric();
+                        let ops: [(&str, usize); 15] = [
+                            ("+", 0),
+                            ("*", 0),
+                            ("-", 0),
+                            ("/", 0),
+                            ("%", 0),
+                            ("min", 0),
+                            ("max", 0),
+                            ("pow", 0),
+                            ("abs", 1),
+                            ("sqrt", 1),
+                            ("sign", 1),
+                            ("clamp", 3),
+                            ("percent", 2),
+                            ("floor", 1),
+                            ("ceil", 1),
+                        ];
+                        // floor and ceil are only generated for decimals
+                        let (op, arity) = match v {
+                            LeafType::Number => ops[self.next(13)],
+                            _ => ops[self.next(15)],
+                        };
+                        let count = match arity {
+                            0 => 1 + self.next(3),
+                            _ => arity,
+                        };
+                        json!({
+                            "op": op,
+                            "type": Self::type_name(v),
+                            "args": self.args(v, count, depth)
+                        })
+                    }
+                    1 => {
+                        let mut args = vec![
+                            self.expression(LeafType::Decimal, depth - 1),
+                            json!(self.next(4)),
+                        ];
+                        if self.next(2) == 0 {
+                            let mode = ["HalfEven", "HalfUp", "Floor", "Ceiling", "Truncate"];
+                            args.push(json!(mode[self.next(5)]));
+                        }
+                        json!({"op": "round", "args": args})
+                    }
+                    2 => {
+                        let v = self.numeric();
+                        self.matching(v, depth)
+                    }
+                    3 => {
+                        let v = self.numeric();
+                        self.conditional(v, depth)
+                    }
+                    4 => {
+                        let v = self.numeric();
+                        self.binding(v, depth)
+                    }
+                    5 => {
+                        let v = self.numeric();
+                        self.call(v, depth)
+                    }
+                    6 => self.text(leaf_type, depth),
+                    7 => self.cast(leaf_type, depth),
+                    8 => self.nullable(leaf_type, depth),
+                    9 => self.list(leaf_type, depth),
+                    10 => self.collection(leaf_type, depth),
+                    11 => self.temporal(leaf_type, depth),
+                    _ => self.leaf(leaf_type),
+                },
+                LeafType::Text => match self.next(13) {
+                    0 => self.matching(LeafType::Text, depth),
+                    2 => self.conditional(LeafType::Text, depth),
+                    3 => self.binding(LeafType::Text, depth),
+                    4 => self.call(LeafType::Text, depth),
+                    5 => self.text(LeafType::Text, depth),
+                    6 => self.regex(LeafType::Text, depth),
+                    7 => self.cast(LeafType::Text, depth),
+                    8 => self.nullable(LeafType::Text, depth),
+                    9 => self.list(LeafType::Text, depth),
+                    10 => self.collection(LeafType::Text, depth),
+                    11 => self.temporal(LeafType::Text, depth),
+                    1 => {
+                        let v =
+                            [LeafType::Number, LeafType::Decimal, LeafType::Boolean][self.next(3)];
+                        // literals in Text positions are serialized as strings
+                        match self.expression(v, depth) {
+                            Value::Number(v1) => json!(v1.to_string()),
+                            Value::Bool(v1) => json!(v1.to_string()),
+                            v1 => v1,
+                        }
+                    }
+                    _ => self.leaf(leaf_type),
+                },
+                LeafType::Boolean => match self.next(17) {
+                    0 => {
+                        let v = [LeafType::Number, LeafType::Decimal, LeafType::Text][self.next(3)];
+                        let count = 2 + self.next(2);
+                        let op = ["==", "!=", ">=", "<=", ">", "<"][self.next(6)];
+                        json!({
+                            "op": op,
+                            "type": Self::type_name(v),
+                            "args": self.args(v, count, depth)
+                        })
+                    }
+                    1 => {
+                        let op =
+                            ["and", "or", "xor", "implies", "iff", "nand", "nor"][self.next(7)];
+                        let count = match op {
+                            "implies" | "iff" => 2,
+                            _ => 2 + self.next(2),
+                        };
+                        json!({
+                            "op": op,
+                            "args": self.args(LeafType::Boolean, count, depth)
+                        })
+                    }
+                    2 => json!({"op": "not", "args": self.args(LeafType::Boolean, 1, depth)}),
+                    3 => self.matching(LeafType::Boolean, depth),
+                    4 => self.conditional(LeafType::Boolean, depth),
+                    5 => self.binding(LeafType::Boolean, depth),
+                    6 => self.call(LeafType::Boolean, depth),
+                    7 => self.text(LeafType::Boolean, depth),
+                    8 => self.regex(LeafType::Boolean, depth),
+                    9 => self.cast(LeafType::Boolean, depth),
+                    10 => self.nullable(LeafType::Boolean, depth),
+                    11 => self.list(LeafType::Boolean, depth),
+                    12 => self.collection(LeafType::Boolean, depth),
+                    13 => self.temporal(LeafType::Boolean, depth),
+                    14 => self.membership(depth),
+                    15 => self.quantifier(depth),
+                    _ => self.leaf(leaf_type),
+                },
+                LeafType::Date | LeafType::DateTime | LeafType::Duration => match self.next(3) {
+                    0 => self.temporal(leaf_type, depth),
+                    1 => self.matching(leaf_type, depth),
+                    _ => self.leaf(leaf_type),
+                },
+            }
+        }
+    }
+
+    fn variant_name(expr: &LispExpression) -> &'static str {
+        match expr {
+            LispExpression::NumberArithmeticExpression(_) => "NumberArithmeticExpression",
+            LispExpression::DecimalArithmeticExpression(_) => "DecimalArithmeticExpression",
+            LispExpression::RoundExpression(_) => "RoundExpression",
+            LispExpression::NumberComparatorExpression(_) => "NumberComparatorExpression",
+            LispExpression::DecimalComparatorExpression(_) => "DecimalComparatorExpression",
+            LispExpression::TextComparatorExpression(_) => "TextComparatorExpression",
+            LispExpression::LogicalBinaryExpression(_) => "LogicalBinaryExpression",
+            LispExpression::LogicalUnaryExpression(_) => "LogicalUnaryExpression",
+            LispExpression::LogicalVariadicExpression(_) => "LogicalVariadicExpression",
+            LispExpression::MatchExpression(_) => "MatchExpression",
+            LispExpression::NumberConditionalExpression(_) => "NumberConditionalExpression",
+            LispExpression::DecimalConditionalExpression(_) => "DecimalConditionalExpression",
+            LispExpression::TextConditionalExpression(_) => "TextConditionalExpression",
+            LispExpression::BooleanConditionalExpression(_) => "BooleanConditionalExpression",
+            LispExpression::NumberLetExpression(_) => "NumberLetExpression",
+            LispExpression::DecimalLetExpression(_) => "DecimalLetExpression",
+            LispExpression::TextLetExpression(_) => "TextLetExpression",
+            LispExpression::BooleanLetExpression(_) => "BooleanLetExpression",
+            LispExpression::CallExpression(_) => "CallExpression",
+            LispExpression::CastExpression(_) => "CastExpression",
+            LispExpression::NullExpression(_) => "NullExpression",
+            LispExpression::ListExpression(_) => "ListExpression",
+            LispExpression::CollectionExpression(_) => "CollectionExpression",
+            LispExpression::TemporalExpression(_) => "TemporalExpression",
+            LispExpression::TemporalComparatorExpression(_) => "TemporalComparatorExpression",
+            LispExpression::MembershipExpression(_) => "MembershipExpression",
+            LispExpression::TextExpression(_) => "TextExpression",
+            LispExpression::RegexExpression(_) => "RegexExpression",
+            LispExpression::DotExpression(_) => "DotExpression",
+        }
+    }
+
+    #[test]
+    fn test_round_trip_property() {
+        let symbols: HashMap<String, Symbol> = vec![
+            ("Number", Leaf::Number(3)),
+            (
+                "Decimal",
+                Leaf::Decimal(BigDecimal::from_str("2.50").unwrap()),
+            ),
+            ("Text", Leaf::Text("b".to_string())),
+            ("Boolean", Leaf::Boolean(true)),
+            (
+                "Date",
+                Leaf::Date(NaiveDate::from_ymd_opt(2024, 1, 31).unwrap()),
+            ),
+            (
+                "DateTime",
+                Leaf::DateTime(DateTime::parse("2024-01-31T23:30:00+05:30").unwrap()),
+            ),
+            ("Duration", Leaf::Duration(Duration::hours(36))),
+            (
+                "List",
+                Leaf::List(
+                    (1..4)
+                        .map(|v| Symbol {
+                            value: Some(Leaf::Number(v)),
+                            values: HashMap::new(),
+                        })
+                        .collect(),
+                ),
+            ),
+        ]
+        .into_iter()
+        .map(|(key, val)| {
+            (
+                key.to_string(),
+                Symbol {
+                    value: Some(val),
+                    values: HashMap::new(),
+                },
+            )
+        })
+        .collect();
+        let functions: HashMap<String, Function> = ["Number", "Decimal", "Text", "Boolean"]
+            .iter()
+            .map(|v| {
+                let val = json!({
+                    "op": "lambda",
+                    "type": v,
+                    "args": [[[v, v]], {"op": ".", "args": [v]}]
+                });
+                (v.to_string(), Function::deserialize(&val).unwrap())
+            })
+            .collect();
+        let context = Context {
+            functions: Some(&functions),
+            ..Context::new(&symbols)
+        };
+        let eval = |expr: &LispExpression| match expr.as_text(&context) {
+            Ok(v) => json!(v),
+            Err(e) => e.serialize(&Language::English),
+        };
+        let mut generator = Generator { state: 0x2545f491 };
+        let mut variants: HashSet<&str> = HashSet::new();
+        for _ in 0..2000 {
+            let leaf_type = [
+                LeafType::Number,
+                LeafType::Decimal,
+                LeafType::Text,
+                LeafType::Boolean,
+            ][generator.next(4)];
+            let depth = 1 + generator.next(3);
+            let val = generator.expression(leaf_type, depth);
+            if !val.is_object() {
+                continue;
+            }
+            let expr = LispExpression::deserialize(val.clone()).unwrap();
+            variants.insert(variant_name(&expr));
+            // val == deserialize.serialize
+            assert_eq!(val, expr.serialize().unwrap());
+            // serialize == serialize.deserialize.serialize, byte for byte
+            let text = serde_json::to_string(&expr.serialize().unwrap()).unwrap();
+            let expr1 = LispExpression::deserialize(serde_json::from_str(&text).unwrap()).unwrap();
+            assert_eq!(
+                text,
+                serde_json::to_string(&expr1.serialize().unwrap()).unwrap(),
+                "{}",
+                val
+            );
+            // eval == serialize.deserialize.eval
+            assert_eq!(eval(&expr), eval(&expr1), "{}", val);
+            // serialize == serialize.to_text.parse.serialize
+            let expr2 = LispExpression::parse(&expr.to_text().unwrap()).unwrap();
+            assert_eq!(
+                expr.serialize().unwrap(),
+                expr2.serialize().unwrap(),
+                "{}",
+                val
+            );
+        }
+        assert_eq!(29, variants.len());
+    }
+}
@@ -0,0 +1,413 @@
+use crate::binding::Binding;
+use crate::context::Context;
+use crate::error::{CustomError, Message};
+use crate::expression::LispExpression;
+use crate::symbol::{Leaf, LeafType, Symbol};
+use crate::temporal::Temporal;
+use crate::value::ToValue;
+use bigdecimal::BigDecimal;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+// FUNCTIONS
+
+// A named formula, registered once and called from any expression evaluated with the registry.
+// {"op": "lambda", "type": T, "args": [[[name, type], ...], body]}
+pub struct Function {
+    pub(crate) params: Vec<(String, LeafType)>,
+    pub(crate) result_type: LeafType,
+    pub(crate) body: LispExpression,
+}
+
+impl Function {
+    // Evaluates the body with arguments bound to the names of the params.
+    // Errors in the body are located by their pointer into the definition.
+    pub(crate) fn call(
+        &self,
+        bindings: HashMap<String, Symbol>,
+        context: &Context,
+    ) -> Result<Leaf, CustomError> {
+        let scope = context.call(bindings)?;
+        let result = match self.result_type {
+            LeafType::Number => self.body.as_number(&scope).map(Leaf::Number),
+            LeafType::Decimal => self.body.as_decimal(&scope).map(Leaf::Decimal),
+            LeafType::Text => self.body.as_text(&scope).map(Leaf::Text),
+            LeafType::Boolean => self.body.as_boolean(&scope).map(Leaf::Boolean),
+            LeafType::Date => self.body.as_temporal(&scope).map(Leaf::Date),
+            LeafType::DateTime => self.body.as_temporal(&scope).map(Leaf::DateTime),
+            LeafType::Duration => self.body.as_temporal(&scope).map(Leaf::Duration),
+        };
+        result.map_err(|e| e.at("/args/1"))
+    }
+
+    pub fn serialize(&self) -> Result<Value, CustomError> {
+        let params: Vec<Value> = self
+            .params
+            .iter()
+            .map(|(name, param_type)| json!([name, param_type.serialize()]))
+            .collect();
+        Ok(json!({
+            "op": "lambda",
+            "type": self.result_type.serialize(),
+            "args": [params, self.body.serialize()?]
+        }))
+    }
+
+    pub fn deserialize(val: &Value) -> Result<Function, CustomError> {
+        let (result_type, args) = match val {
+            Value::Object(v) => match (v.get("op"), v.get("args")) {
+                (Some(Value::String(v1)), Some(Value::Array(v2))) if v1 == "lambda" => (
+                    LispExpression::deserialize_type(v.get("type"), "/type")?,
+                    v2,
+                ),
+                (Some(Value::String(_)), Some(Value::Array(_))) => {
+                    return Err(CustomError::Message(Message::ErrUnknownOperator).at("/op"))
+                }
+                _ => return Err(CustomError::Message(Message::ErrDeserialization)),
+            },
+            _ => return Err(CustomError::Message(Message::ErrDeserialization)),
+        };
+        let params: &Vec<Value> = match (args.first(), args.len()) {
+            (Some(Value::Array(v)), 2) => v,
+            (Some(_), 2) => {
+                return Err(CustomError::Message(Message::ErrTypeMismatch).at("/args/0"))
+            }
+            _ => return Err(CustomError::Message(Message::ErrWrongArity).at("/args")),
+        };
+        Ok(Function {
+            params: params
+                .iter()
+                .enumerate()
+                .map(|(index, val)| {
+                    let pointer = format!("/args/0/{}", index);
+                    match val {
+                        Value::Array(v) => match v.as_slice() {
+                            [Value::String(v1), v2] => Ok((
+                                v1.to_string(),
+                                LispExpression::deserialize_type(
+                                    Some(v2),
+                                    &format!("{}/1", pointer),
+                                )?,
+                            )),
+                            [_, _] => Err(CustomError::Message(Message::ErrTypeMismatch)
+                                .at(&format!("{}/0", pointer))),
+                            _ => Err(CustomError::Message(Message::ErrWrongArity).at(&pointer)),
+                        },
+                        _ => Err(CustomError::Message(Message::ErrTypeMismatch).at(&pointer)),
+                    }
+                })
+                .collect::<Result<Vec<(String, LeafType)>, CustomError>>()?,
+            result_type,
+            body: LispExpression::deserialize(args[1].clone()).map_err(|e| e.at("/args/1"))?,
+        })
+    }
+
+    // A registry is serialized as an object from function names to their definitions
+    pub fn serialize_registry(functions: &HashMap<String, Function>) -> Result<Value, CustomError> {
+        Ok(Value::Object(
+            functions
+                .iter()
+                .map(|(name, function)| Ok((name.to_string(), function.serialize()?)))
+                .collect::<Result<serde_json::Map<String, Value>, CustomError>>()?,
+        ))
+    }
+
+    pub fn deserialize_registry(val: &Value) -> Result<HashMap<String, Function>, CustomError> {
+        match val {
+            Value::Object(v) => v
+                .iter()
+                .map(|(name, val1)| {
+                    Ok((
+                        name.to_string(),
+                        Self::deserialize(val1).map_err(|e| e.at(&format!("/{}", name)))?,
+                    ))
+                })
+                .collect(),
+            _ => Err(CustomError::Message(Message::ErrDeserialization)),
+        }
+    }
+}
+
+// Calls a function of the registry in the context, with typed arguments.
+// {"op": "call", "type": T, "args": [name, [type, value], ...]}
+pub struct CallExpression {
+    pub(crate) result_type: LeafType,
+    pub(crate) name: String,
+    pub(crate) args: Vec<Binding>,
+}
+
+impl CallExpression {
+    pub(crate) fn eval(&self, context: &Context) -> Result<Leaf, CustomError> {
+        let function = match context.functions.and_then(|v| v.get(&self.name)) {
+            Some(v) => v,
+            None => return Err(CustomError::Message(Message::ErrMissingFunction).at("/args/0")),
+        };
+        if function.result_type != self.result_type {
+            return Err(CustomError::Message(Message::ErrTypeMismatch).at("/type"));
+        }
+        if function.params.len() != self.args.len() {
+            return Err(CustomError::Message(Message::ErrWrongArity).at("/args"));
+        }
+        let mut bindings: HashMap<String, Symbol> = HashMap::new();
+        for (index, ((name, param_type), arg)) in function.params.iter().zip(&self.args).enumerate()
+        {
+            let pointer = format!("/args/{}", index + 1);
+            if *param_type != arg.leaf_type() {
+                return Err(CustomError::Message(Message::ErrTypeMismatch).at(&pointer));
+            }
+            let v = arg
+                .eval(context)
+                .map_err(|e| e.at(&format!("{}/1", pointer)))?;
+            bindings.insert(name.to_string(), Symbol::from_leaf(v));
+        }
+        // errors of the body are reported against the name of the function called
+        function
+            .call(bindings, context)
+            .map_err(|e| e.at("/args/0"))
+    }
+
+    pub(crate) fn serialize(&self) -> Result<Value, CustomError> {
+        let mut args: Vec<Value> = vec![json!(self.name)];
+        for arg in &self.args {
+            let (arg_type, v) = arg.serialize()?;
+            args.push(json!([arg_type.serialize(), v]));
+        }
+        Ok(json!({
+            "op": "call",
+            "type": self.result_type.serialize(),
+            "args": args
+        }))
+    }
+}
+
+impl ToValue<i32> for CallExpression {
+    fn get_value(&self, context: &Context) -> Result<i32, CustomError> {
+        match self.eval(context)? {
+            Leaf::Number(v) => Ok(v),
+            Leaf::Decimal(v) => context.to_number(&v),
+            Leaf::Null => Err(CustomError::Message(Message::ErrNull)),
+            _ => Err(CustomError::Message(Message::ErrTypeMismatch)),
+        }
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        self.serialize()
+    }
+}
+
+impl ToValue<BigDecimal> for CallExpression {
+    fn get_value(&self, context: &Context) -> Result<BigDecimal, CustomError> {
+        match self.eval(context)? {
+            Leaf::Number(v) => Ok(BigDecimal::from(v)),
+            Leaf::Decimal(v) => Ok(v),
+            Leaf::Null => Err(CustomError::Message(Message::ErrNull)),
+            _ => Err(CustomError::Message(Message::ErrTypeMismatch)),
+        }
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        self.serialize()
+    }
+}
+
+impl ToValue<String> for CallExpression {
+    fn get_value(&self, context: &Context) -> Result<String, CustomError> {
+        match self.eval(context)? {
+            Leaf::Number(v) => Ok(v.to_string()),
+            Leaf::Decimal(v) => Ok(v.to_string()),
+            Leaf::Text(v) => Ok(v),
+            Leaf::Boolean(v) => Ok(v.to_string()),
+            Leaf::Date(v) => Ok(v.to_text()),
+            Leaf::DateTime(v) => Ok(v.to_text()),
+            Leaf::Duration(v) => Ok(v.to_text()),
+            Leaf::Null => Err(CustomError::Message(Message::ErrNull)),
+            Leaf::List(_) => Err(CustomError::Message(Message::ErrTypeMismatch)),
+        }
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        self.serialize()
+    }
+}
+
+impl ToValue<bool> for CallExpression {
+    fn get_value(&self, context: &Context) -> Result<bool, CustomError> {
+        match self.eval(context)? {
+            Leaf::Boolean(v) => Ok(v),
+            Leaf::Null => Err(CustomError::Message(Message::ErrNull)),
+            _ => Err(CustomError::Message(Message::ErrTypeMismatch)),
+        }
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        self.serialize()
+    }
+}
+
+#[cfg(test)]
+mod function_tests {
+    use std::str::FromStr;
+
+    use super::*;
+    use crate::dot::DotExpression;
+    use crate::error::Language;
+
+    #[test]
+    fn test_call_expression() {
+        let symbols: HashMap<String, Symbol> = vec![(
+            "price".to_string(),
+            Symbol {
+                value: Some(Leaf::Decimal(BigDecimal::from_str("12.50").unwrap())),
+                values: HashMap::new(),
+            },
+        )]
+        .into_iter()
+        .collect();
+        let registry = json!({
+            "tax": {
+                "op": "lambda",
+                "type": "Decimal",
+                "args": [
+                    [["amount", "Decimal"], ["rate", "Decimal"]],
+                    {"op": "*", "type": "Decimal", "args": [
+                        {"op": ".", "args": ["amount"]},
+                        {"op": ".", "args": ["rate"]}
+                    ]}
+                ]
+            },
+            "factorial": {
+                "op": "lambda",
+                "type": "Number",
+                "args": [
+                    [["n", "Number"]],
+                    {"op": "if", "type": "Number", "args": [
+                        {"op": "==", "type": "Number", "args": [{"op": ".", "args": ["n"]}, 0]},
+                        1,
+                        {"op": "*", "type": "Number", "args": [
+                            {"op": ".", "args": ["n"]},
+                            {"op": "call", "type": "Number", "args": [
+                                "factorial",
+                                ["Number", {"op": "-", "type": "Number", "args": [
+                                    {"op": ".", "args": ["n"]},
+                                    1
+                                ]}]
+                            ]}
+                        ]}
+                    ]}
+                ]
+            },
+            "loop": {
+                "op": "lambda",
+                "type": "Number",
+                "args": [
+                    [],
+                    {"op": "call", "type": "Number", "args": ["loop"]}
+                ]
+            },
+            "n": {
+                "op": "lambda",
+                "type": "Number",
+                "args": [[], {"op": ".", "args": ["n"]}]
+            }
+        });
+        let functions = Function::deserialize_registry(&registry).unwrap();
+        // registry == deserialize.serialize
+        assert_eq!(registry, Function::serialize_registry(&functions).unwrap());
+        let context = Context {
+            functions: Some(&functions),
+            ..Context::new(&symbols)
+        };
+        let expr = CallExpression {
+            result_type: LeafType::Decimal,
+            name: "tax".to_string(),
+            args: vec![
+                Binding::Decimal(Box::new(DotExpression {
+                    path: vec!["price".to_string()],
+                })),
+                Binding::Decimal(Box::new(BigDecimal::from_str("0.18").unwrap())),
+            ],
+        };
+        let res: BigDecimal = expr.get_value(&context).unwrap();
+        assert_eq!(BigDecimal::from_str("2.25").unwrap(), res);
+        // eval == serialize.deserialize.eval
+        assert_eq!(
+            res,
+            (match LispExpression::deserialize(
+                (&expr as &dyn ToValue<BigDecimal>).serialize().unwrap()
+            ) {
+                Ok(v) => match v {
+                    LispExpression::CallExpression(v) =>
+                        (&v as &dyn ToValue<BigDecimal>).get_value(&context),
+                    _ => Err(CustomError::Message(Message::ErrUnexpected)),
+                },
+                Err(_) => Err(CustomError::Message(Message::ErrUnexpected)),
+            })
+            .unwrap()
+        );
+        // serialize == serialize.deserialize.serialize
+        assert_eq!(
+            (&expr as &dyn ToValue<BigDecimal>).serialize().unwrap(),
+            match LispExpression::deserialize(
+                (&expr as &dyn ToValue<BigDecimal>).serialize().unwrap()
+            ) {
+                Ok(v) => match v {
+                    LispExpression::CallExpression(v) =>
+                        (&v as &dyn ToValue<BigDecimal>).serialize().unwrap(),
+                    _ => Value::Null,
+                },
+                Err(_) => Value::Null,
+            }
+        );
+        let eval = |text: &str| match LispExpression::parse(text).unwrap().as_text(&context) {
+            Ok(v) => json!(v),
+            Err(e) => e.serialize(&Language::English),
+        };
+        assert_eq!(json!("120"), eval("(call:Number factorial [Number 5])"));
+        // the body sees its arguments, but not the bindings of the caller
+        assert_eq!(
+            json!({"/args/1/args/0/args/1/args/0": "Symbol not found"}),
+            eval("(let:Number [[n Number 1]] (call:Number n))")
+        );
+        assert_eq!(
+            json!({"/args/0": "Function not found"}),
+            eval("(call:Number round [Number 1])")
+        );
+        assert_eq!(
+            json!({"/args": "Wrong number of arguments"}),
+            eval("(call:Number factorial)")
+        );
+        assert_eq!(
+            json!({"/args/1": "Type mismatch"}),
+            eval("(call:Number factorial [Decimal 5])")
+        );
+        assert_eq!(
+            json!({"/type": "Type mismatch"}),
+            eval("(call:Decimal factorial [Number 5])")
+        );
+        let context = Context {
+            max_depth: 2,
+            ..context
+        };
+        assert_eq!(
+            json!({"/args/0/args/1/args/0/args/1/args/0": "Call depth exceeded"}),
+            match LispExpression::parse("(call:Number loop)")
+                .unwrap()
+                .as_number(&context)
+            {
+                Ok(v) => json!(v),
+                Err(e) => e.serialize(&Language::English),
+            }
+        );
+        assert_eq!(
+            json!({"/args/0/0/1": "Type mismatch"}),
+            match Function::deserialize(&json!({
+                "op": "lambda",
+                "type": "Number",
+                "args": [[["n", "Time"]], {"op": ".", "args": ["n"]}]
+            })) {
+                Ok(_) => Value::Null,
+                Err(e) => e.serialize(&Language::English),
+            }
+        );
+    }
+}
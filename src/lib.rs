@@ -0,0 +1,48 @@
+/* Copyright (C) Gokyun (OPC) Private  value: ()  value: () Limited - All Rights Reserved
+ * Unauthorized copying of this file, via any medium is strictly prohibited
+ * Proprietary and confidential
+ * Written by Rajat Pundir <rajatpundir13@gmail.com>, August 2021
+ */
+
+// Notes for the future.
+// 1. Build some docs and audio visual documentation for quick understanding
+
+// TODO
+// 1. Add Diesel
+
+mod context;
+mod error;
+mod symbol;
+mod value;
+
+// Expression families, each with its nodes and their evaluation
+mod arithmetic;
+mod binding;
+mod cast;
+mod collection;
+mod comparator;
+mod conditional;
+mod dot;
+mod function;
+mod list;
+mod logical;
+mod matching;
+mod membership;
+mod null;
+mod temporal;
+mod text;
+
+mod expression;
+mod syntax;
+mod typecheck;
+
+pub use context::{Context, OverflowPolicy, RoundingMode};
+pub use error::{CustomError, Language, Message};
+pub use expression::LispExpression;
+pub use function::Function;
+pub use list::List;
+pub use matching::Matchable;
+pub use symbol::{Leaf, LeafType, Symbol};
+pub use temporal::Temporal;
+pub use typecheck::{typecheck, typecheck_value, SymbolType};
+pub use value::ToValue;
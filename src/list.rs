@@ -0,0 +1,301 @@
+use crate::binding::Binding;
+use crate::context::Context;
+use crate::error::{CustomError, Message};
+use crate::symbol::{Leaf, LeafType, Symbol};
+use crate::temporal::Temporal;
+use crate::value::{eval_arg, ToValue};
+use bigdecimal::{BigDecimal, ToPrimitive};
+use serde_json::{json, Value};
+
+// LIST OPS
+
+// The items of a list valued symbol
+pub type List = Vec<Symbol>;
+
+// Operations on a list, whose items hold values of the declared type.
+// {"op": "length", "type": T, "args": [list]}, and likewise first and last
+// {"op": "contains", "type": T, "args": [list, value]}
+// Typed length and contains operate on lists, untyped ones on texts.
+pub enum ListExpression {
+    Length {
+        item_type: LeafType,
+        list: Box<dyn ToValue<List>>,
+    },
+    Contains {
+        list: Box<dyn ToValue<List>>,
+        value: Binding,
+    },
+    First {
+        item_type: LeafType,
+        list: Box<dyn ToValue<List>>,
+    },
+    Last {
+        item_type: LeafType,
+        list: Box<dyn ToValue<List>>,
+    },
+}
+
+impl ListExpression {
+    // Numbers equal decimals of the same value
+    pub(crate) fn equals(a: &Leaf, b: &Leaf) -> bool {
+        match (a, b) {
+            (Leaf::Number(v), Leaf::Decimal(v1)) | (Leaf::Decimal(v1), Leaf::Number(v)) => {
+                BigDecimal::from(*v) == *v1
+            }
+            _ => a == b,
+        }
+    }
+
+    // The first and last items of an empty list are null, as are items without a value
+    pub(crate) fn eval(&self, context: &Context) -> Result<Leaf, CustomError> {
+        match self {
+            ListExpression::Length { list, .. } => {
+                let items = eval_arg(list.as_ref(), 0, context)?;
+                match items.len().to_i32() {
+                    Some(v) => Ok(Leaf::Number(v)),
+                    None => Err(CustomError::Message(Message::ErrOverflow)),
+                }
+            }
+            ListExpression::Contains { list, value } => {
+                let items = eval_arg(list.as_ref(), 0, context)?;
+                let v = value.eval(context).map_err(|e| e.at("/args/1"))?;
+                Ok(Leaf::Boolean(items.iter().any(|val| match &val.value {
+                    Some(v1) => Self::equals(v1, &v),
+                    None => false,
+                })))
+            }
+            ListExpression::First { list, .. } => {
+                let items = eval_arg(list.as_ref(), 0, context)?;
+                Ok(items
+                    .first()
+                    .and_then(|v| v.value.clone())
+                    .unwrap_or(Leaf::Null))
+            }
+            ListExpression::Last { list, .. } => {
+                let items = eval_arg(list.as_ref(), 0, context)?;
+                Ok(items
+                    .last()
+                    .and_then(|v| v.value.clone())
+                    .unwrap_or(Leaf::Null))
+            }
+        }
+    }
+
+    pub(crate) fn serialize(&self) -> Result<Value, CustomError> {
+        let (op, item_type, args): (&str, LeafType, Vec<Value>) = match self {
+            ListExpression::Length { item_type, list } => {
+                ("length", *item_type, vec![list.serialize()?])
+            }
+            ListExpression::Contains { list, value } => {
+                let (item_type, v) = value.serialize()?;
+                ("contains", item_type, vec![list.serialize()?, v])
+            }
+            ListExpression::First { item_type, list } => {
+                ("first", *item_type, vec![list.serialize()?])
+            }
+            ListExpression::Last { item_type, list } => {
+                ("last", *item_type, vec![list.serialize()?])
+            }
+        };
+        Ok(json!({
+            "op": op,
+            "type": item_type.serialize(),
+            "args": args
+        }))
+    }
+}
+
+impl ToValue<i32> for ListExpression {
+    fn get_value(&self, context: &Context) -> Result<i32, CustomError> {
+        match self.eval(context)? {
+            Leaf::Number(v) => Ok(v),
+            Leaf::Decimal(v) => context.to_number(&v),
+            Leaf::Null => Err(CustomError::Message(Message::ErrNull)),
+            _ => Err(CustomError::Message(Message::ErrTypeMismatch)),
+        }
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        self.serialize()
+    }
+}
+
+impl ToValue<BigDecimal> for ListExpression {
+    fn get_value(&self, context: &Context) -> Result<BigDecimal, CustomError> {
+        match self.eval(context)? {
+            Leaf::Number(v) => Ok(BigDecimal::from(v)),
+            Leaf::Decimal(v) => Ok(v),
+            Leaf::Null => Err(CustomError::Message(Message::ErrNull)),
+            _ => Err(CustomError::Message(Message::ErrTypeMismatch)),
+        }
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        self.serialize()
+    }
+}
+
+impl ToValue<String> for ListExpression {
+    fn get_value(&self, context: &Context) -> Result<String, CustomError> {
+        match self.eval(context)? {
+            Leaf::Number(v) => Ok(v.to_string()),
+            Leaf::Decimal(v) => Ok(v.to_string()),
+            Leaf::Text(v) => Ok(v),
+            Leaf::Boolean(v) => Ok(v.to_string()),
+            Leaf::Date(v) => Ok(v.to_text()),
+            Leaf::DateTime(v) => Ok(v.to_text()),
+            Leaf::Duration(v) => Ok(v.to_text()),
+            Leaf::Null => Err(CustomError::Message(Message::ErrNull)),
+            Leaf::List(_) => Err(CustomError::Message(Message::ErrTypeMismatch)),
+        }
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        self.serialize()
+    }
+}
+
+impl ToValue<bool> for ListExpression {
+    fn get_value(&self, context: &Context) -> Result<bool, CustomError> {
+        match self.eval(context)? {
+            Leaf::Boolean(v) => Ok(v),
+            Leaf::Null => Err(CustomError::Message(Message::ErrNull)),
+            _ => Err(CustomError::Message(Message::ErrTypeMismatch)),
+        }
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        self.serialize()
+    }
+}
+
+#[cfg(test)]
+mod list_tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::dot::DotExpression;
+    use crate::error::Language;
+    use crate::expression::LispExpression;
+
+    #[test]
+    fn test_list_expression() {
+        let line = |qty: i32| Symbol {
+            value: None,
+            values: vec![(
+                "qty".to_string(),
+                Symbol {
+                    value: Some(Leaf::Number(qty)),
+                    values: HashMap::new(),
+                },
+            )]
+            .into_iter()
+            .collect(),
+        };
+        let tag = |name: &str| Symbol {
+            value: Some(Leaf::Text(name.to_string())),
+            values: HashMap::new(),
+        };
+        let symbols: HashMap<String, Symbol> = vec![
+            (
+                "lines".to_string(),
+                Symbol {
+                    value: Some(Leaf::List(vec![line(2), line(5)])),
+                    values: HashMap::new(),
+                },
+            ),
+            (
+                "tags".to_string(),
+                Symbol {
+                    value: Some(Leaf::List(vec![tag("vip"), tag("new")])),
+                    values: HashMap::new(),
+                },
+            ),
+            (
+                "empty".to_string(),
+                Symbol {
+                    value: Some(Leaf::List(vec![])),
+                    values: HashMap::new(),
+                },
+            ),
+        ]
+        .into_iter()
+        .collect();
+        let expr = ListExpression::Contains {
+            list: Box::new(DotExpression {
+                path: vec!["tags".to_string()],
+            }),
+            value: Binding::Text(Box::new("vip".to_string())),
+        };
+        let res: bool = expr.get_value(&Context::new(&symbols)).unwrap();
+        assert!(res);
+        // eval == serialize.deserialize.eval
+        assert_eq!(
+            res,
+            (match LispExpression::deserialize((&expr as &dyn ToValue<bool>).serialize().unwrap()) {
+                Ok(v) => match v {
+                    LispExpression::ListExpression(v) =>
+                        (&v as &dyn ToValue<bool>).get_value(&Context::new(&symbols)),
+                    _ => Err(CustomError::Message(Message::ErrUnexpected)),
+                },
+                Err(_) => Err(CustomError::Message(Message::ErrUnexpected)),
+            })
+            .unwrap()
+        );
+        // serialize == serialize.deserialize.serialize
+        assert_eq!(
+            (&expr as &dyn ToValue<bool>).serialize().unwrap(),
+            match LispExpression::deserialize((&expr as &dyn ToValue<bool>).serialize().unwrap()) {
+                Ok(v) => match v {
+                    LispExpression::ListExpression(v) =>
+                        (&v as &dyn ToValue<bool>).serialize().unwrap(),
+                    _ => Value::Null,
+                },
+                Err(_) => Value::Null,
+            }
+        );
+        let eval = |text: &str| match LispExpression::parse(text)
+            .unwrap()
+            .as_text(&Context::new(&symbols))
+        {
+            Ok(v) => json!(v),
+            Err(e) => e.serialize(&Language::English),
+        };
+        assert_eq!(json!("5"), eval("(. lines 1 qty)"));
+        assert_eq!(json!("2"), eval("(length:Number (. lines))"));
+        assert_eq!(json!("vip"), eval("(first:Text (. tags))"));
+        assert_eq!(json!("new"), eval("(last:Text (. tags))"));
+        assert_eq!(json!("false"), eval("(contains:Text (. tags) old)"));
+        // Untyped length and contains are still text operations
+        assert_eq!(json!("3"), eval("(length (. tags 0))"));
+        assert_eq!(json!("true"), eval("(contains (. tags 1) ew)"));
+        assert_eq!(json!("0"), eval("(length:Number (. empty))"));
+        assert_eq!(
+            json!("none"),
+            eval("(coalesce:Text (first:Text (. empty)) none)")
+        );
+        assert_eq!(
+            json!({"/args/1": "Symbol not found"}),
+            eval("(. lines 2 qty)")
+        );
+        assert_eq!(
+            json!({"/args/0": "Type mismatch"}),
+            eval("(length:Number (. lines 0 qty))")
+        );
+        assert_eq!(json!("Type mismatch"), eval("(. tags)"));
+        assert_eq!(
+            json!({"/args": "Wrong number of arguments"}),
+            match LispExpression::parse("(contains:Text (. tags))") {
+                Ok(_) => Value::Null,
+                Err(e) => e.serialize(&Language::English),
+            }
+        );
+        assert_eq!(
+            json!({"/type": "Type mismatch"}),
+            match LispExpression::parse("(first (. tags))") {
+                Ok(_) => Value::Null,
+                Err(e) => e.serialize(&Language::English),
+            }
+        );
+    }
+}
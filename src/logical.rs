@@ -0,0 +1,678 @@
+use crate::context::Context;
+use crate::error::{CustomError, Message};
+use crate::value::{eval_arg, ToValue};
+use bigdecimal::ToPrimitive;
+use serde_json::{json, Value};
+
+// LOGICAL OPS
+
+pub enum LogicalResultType {
+    Boolean,
+    Text,
+}
+
+pub enum LogicalResult {
+    Boolean(bool),
+    Text(String),
+}
+
+// BINARY LOGICAL
+
+// The first two args, and the rest
+pub type LogicalOperands = (
+    Box<dyn ToValue<bool>>,
+    Box<dyn ToValue<bool>>,
+    Vec<Box<dyn ToValue<bool>>>,
+);
+
+pub enum LogicalBinaryOperator {
+    And,
+    Or,
+    Xor,
+    Implies,
+    Iff,
+    Nand,
+    Nor,
+}
+
+// Operands are evaluated in order until one decides the result, so later operands can rely
+// on the earlier ones, and errors in skipped operands are never raised. A null operand
+// leaves the result null, unless a later operand decides it.
+pub(crate) fn eval_junction<'a>(
+    operands: impl Iterator<Item = &'a Box<dyn ToValue<bool>>>,
+    decisive: bool,
+    context: &Context,
+) -> Result<bool, CustomError> {
+    let mut unknown: Option<CustomError> = None;
+    for (index, val) in operands.enumerate() {
+        match val.get_value(context) {
+            Ok(v) if v == decisive => return Ok(decisive),
+            Ok(_) => {}
+            Err(e) if e.is_null() => {
+                unknown = unknown.or_else(|| Some(e.at(&format!("/args/{}", index))))
+            }
+            Err(e) => return Err(e.at(&format!("/args/{}", index))),
+        }
+    }
+    match unknown {
+        Some(e) => Err(e),
+        None => Ok(!decisive),
+    }
+}
+
+// Whether an odd number of operands are true, which takes every one of them
+pub(crate) fn eval_parity<'a>(
+    operands: impl Iterator<Item = &'a Box<dyn ToValue<bool>>>,
+    context: &Context,
+) -> Result<bool, CustomError> {
+    let mut result = false;
+    let mut unknown: Option<CustomError> = None;
+    for (index, val) in operands.enumerate() {
+        match val.get_value(context) {
+            Ok(v) => result ^= v,
+            Err(e) if e.is_null() => {
+                unknown = unknown.or_else(|| Some(e.at(&format!("/args/{}", index))))
+            }
+            Err(e) => return Err(e.at(&format!("/args/{}", index))),
+        }
+    }
+    match unknown {
+        Some(e) => Err(e),
+        None => Ok(result),
+    }
+}
+
+pub enum LogicalBinaryExpression {
+    And(LogicalOperands),
+    Or(LogicalOperands),
+    Xor(LogicalOperands),
+    Implies(LogicalOperands), // of two args only
+    Iff(LogicalOperands),     // of two args only
+    Nand(LogicalOperands),
+    Nor(LogicalOperands),
+}
+
+impl LogicalBinaryExpression {
+    pub(crate) fn eval(
+        &self,
+        result_type: LogicalResultType,
+        context: &Context,
+    ) -> Result<LogicalResult, CustomError> {
+        let (args, operator) = match self {
+            LogicalBinaryExpression::And(v) => (v, LogicalBinaryOperator::And),
+            LogicalBinaryExpression::Or(v) => (v, LogicalBinaryOperator::Or),
+            LogicalBinaryExpression::Xor(v) => (v, LogicalBinaryOperator::Xor),
+            LogicalBinaryExpression::Implies(v) => (v, LogicalBinaryOperator::Implies),
+            LogicalBinaryExpression::Iff(v) => (v, LogicalBinaryOperator::Iff),
+            LogicalBinaryExpression::Nand(v) => (v, LogicalBinaryOperator::Nand),
+            LogicalBinaryExpression::Nor(v) => (v, LogicalBinaryOperator::Nor),
+        };
+        let operands = std::iter::once(&args.0)
+            .chain(std::iter::once(&args.1))
+            .chain(&args.2);
+        let result: bool = match operator {
+            LogicalBinaryOperator::And => eval_junction(operands, false, context)?,
+            LogicalBinaryOperator::Or => eval_junction(operands, true, context)?,
+            LogicalBinaryOperator::Nand => !eval_junction(operands, false, context)?,
+            LogicalBinaryOperator::Nor => !eval_junction(operands, true, context)?,
+            LogicalBinaryOperator::Xor => eval_parity(operands, context)?,
+            LogicalBinaryOperator::Iff => !eval_parity(operands, context)?,
+            // The consequent is only evaluated when the antecedent is not false
+            LogicalBinaryOperator::Implies => match args.0.get_value(context) {
+                Ok(false) => true,
+                Ok(true) => eval_arg(args.1.as_ref(), 1, context)?,
+                Err(e) if e.is_null() => match args.1.get_value(context) {
+                    Ok(true) => true,
+                    Err(e1) if !e1.is_null() => return Err(e1.at("/args/1")),
+                    _ => return Err(e.at("/args/0")),
+                },
+                Err(e) => return Err(e.at("/args/0")),
+            },
+        };
+        match result_type {
+            LogicalResultType::Boolean => Ok(LogicalResult::Boolean(result)),
+            LogicalResultType::Text => Ok(LogicalResult::Text(result.to_string())),
+        }
+    }
+
+    pub(crate) fn serialize(&self) -> Result<Value, CustomError> {
+        let operator: &str = match self {
+            LogicalBinaryExpression::And(_) => "and",
+            LogicalBinaryExpression::Or(_) => "or",
+            LogicalBinaryExpression::Xor(_) => "xor",
+            LogicalBinaryExpression::Implies(_) => "implies",
+            LogicalBinaryExpression::Iff(_) => "iff",
+            LogicalBinaryExpression::Nand(_) => "nand",
+            LogicalBinaryExpression::Nor(_) => "nor",
+        };
+        match self {
+            LogicalBinaryExpression::And(v)
+            | LogicalBinaryExpression::Or(v)
+            | LogicalBinaryExpression::Xor(v)
+            | LogicalBinaryExpression::Implies(v)
+            | LogicalBinaryExpression::Iff(v)
+            | LogicalBinaryExpression::Nand(v)
+            | LogicalBinaryExpression::Nor(v) => {
+                let mut err: Option<CustomError> = None;
+                let result: Vec<Result<Value, CustomError>> = std::iter::once(&v.0)
+                    .chain(std::iter::once(&v.1))
+                    .chain(&v.2)
+                    .map(|val| match val.serialize() {
+                        Ok(v) => Ok(v),
+                        Err(e) => {
+                            err = Some(e.clone());
+                            Err(e)
+                        }
+                    })
+                    .collect();
+                match err {
+                    Some(e) => Err(e),
+                    None => {
+                        let args: Vec<Value> = result
+                            .iter()
+                            .map(|val| match val {
+                                Ok(v) => v.clone(),
+                                Err(_) => panic!(),
+                            })
+                            .collect();
+                        Ok(json!({
+                            "op": operator,
+                            "args": args
+                        }))
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl ToValue<String> for LogicalBinaryExpression {
+    fn get_value(&self, context: &Context) -> Result<String, CustomError> {
+        match self.eval(LogicalResultType::Text, context)? {
+            LogicalResult::Text(v) => Ok(v),
+            _ => Err(CustomError::Message(Message::ErrUnexpected)),
+        }
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        self.serialize()
+    }
+}
+
+impl ToValue<bool> for LogicalBinaryExpression {
+    fn get_value(&self, context: &Context) -> Result<bool, CustomError> {
+        match self.eval(LogicalResultType::Boolean, context)? {
+            LogicalResult::Boolean(v) => Ok(v),
+            _ => Err(CustomError::Message(Message::ErrUnexpected)),
+        }
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        self.serialize()
+    }
+}
+
+// UNARY LOGICAL
+
+pub struct LogicalUnaryExpression {
+    pub(crate) value: Box<dyn ToValue<bool>>,
+}
+
+impl LogicalUnaryExpression {
+    pub(crate) fn eval(
+        &self,
+        result_type: LogicalResultType,
+        context: &Context,
+    ) -> Result<LogicalResult, CustomError> {
+        let result: Result<bool, CustomError> = match self.value.get_value(context) {
+            Ok(v) => Ok(!v),
+            Err(e) => Err(e.at("/args/0")),
+        };
+        match result {
+            Ok(v) => match result_type {
+                LogicalResultType::Boolean => Ok(LogicalResult::Boolean(v)),
+                LogicalResultType::Text => Ok(LogicalResult::Text(v.to_string())),
+            },
+            Err(e) => Err(e),
+        }
+    }
+
+    pub(crate) fn serialize(&self) -> Result<Value, CustomError> {
+        match self.value.serialize() {
+            Ok(v) => Ok(json!({
+                "op": "not",
+                "args": json!([v])
+            })),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+impl ToValue<String> for LogicalUnaryExpression {
+    fn get_value(&self, context: &Context) -> Result<String, CustomError> {
+        match self.eval(LogicalResultType::Text, context)? {
+            LogicalResult::Text(v) => Ok(v),
+            _ => Err(CustomError::Message(Message::ErrUnexpected)),
+        }
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        self.serialize()
+    }
+}
+
+impl ToValue<bool> for LogicalUnaryExpression {
+    fn get_value(&self, context: &Context) -> Result<bool, CustomError> {
+        match self.eval(LogicalResultType::Boolean, context)? {
+            LogicalResult::Boolean(v) => Ok(v),
+            _ => Err(CustomError::Message(Message::ErrUnexpected)),
+        }
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        self.serialize()
+    }
+}
+
+// VARIADIC LOGICAL
+
+// Quantifies over any number of operands, none included
+pub enum LogicalQuantifier {
+    All,
+    Any,
+    None,
+    Exactly(Box<dyn ToValue<i32>>), // taken as the first arg
+}
+
+pub struct LogicalVariadicExpression {
+    pub(crate) quantifier: LogicalQuantifier,
+    pub(crate) args: Vec<Box<dyn ToValue<bool>>>,
+}
+
+impl LogicalVariadicExpression {
+    pub(crate) fn eval(
+        &self,
+        result_type: LogicalResultType,
+        context: &Context,
+    ) -> Result<LogicalResult, CustomError> {
+        let result: bool = match &self.quantifier {
+            LogicalQuantifier::All => eval_junction(self.args.iter(), false, context)?,
+            LogicalQuantifier::Any => eval_junction(self.args.iter(), true, context)?,
+            LogicalQuantifier::None => !eval_junction(self.args.iter(), true, context)?,
+            // Operands are evaluated until more than n of them are true. Nulls leave the
+            // result null while they could make up the count.
+            LogicalQuantifier::Exactly(n) => {
+                let n = match eval_arg(n.as_ref(), 0, context)?.to_usize() {
+                    Some(v) => v,
+                    None => return Err(CustomError::Message(Message::ErrOutOfRange).at("/args/0")),
+                };
+                let mut count: usize = 0;
+                let mut unknown: (usize, Option<CustomError>) = (0, None);
+                for (index, val) in self.args.iter().enumerate() {
+                    match val.get_value(context) {
+                        Ok(true) => count += 1,
+                        Ok(false) => {}
+                        Err(e) if e.is_null() => {
+                            unknown.0 += 1;
+                            unknown.1 = unknown
+                                .1
+                                .or_else(|| Some(e.at(&format!("/args/{}", index + 1))));
+                        }
+                        Err(e) => return Err(e.at(&format!("/args/{}", index + 1))),
+                    }
+                    if count > n {
+                        break;
+                    }
+                }
+                match unknown {
+                    (v, Some(e)) if count <= n && count + v >= n => return Err(e),
+                    _ => count == n,
+                }
+            }
+        };
+        match result_type {
+            LogicalResultType::Boolean => Ok(LogicalResult::Boolean(result)),
+            LogicalResultType::Text => Ok(LogicalResult::Text(result.to_string())),
+        }
+    }
+
+    pub(crate) fn serialize(&self) -> Result<Value, CustomError> {
+        let mut args: Vec<Value> = vec![];
+        let op: &str = match &self.quantifier {
+            LogicalQuantifier::All => "all_of",
+            LogicalQuantifier::Any => "any_of",
+            LogicalQuantifier::None => "none_of",
+            LogicalQuantifier::Exactly(n) => {
+                args.push(n.serialize()?);
+                "exactly_n_of"
+            }
+        };
+        for val in self.args.iter() {
+            args.push(val.serialize()?);
+        }
+        Ok(json!({
+            "op": op,
+            "args": args
+        }))
+    }
+}
+
+impl ToValue<String> for LogicalVariadicExpression {
+    fn get_value(&self, context: &Context) -> Result<String, CustomError> {
+        match self.eval(LogicalResultType::Text, context)? {
+            LogicalResult::Text(v) => Ok(v),
+            _ => Err(CustomError::Message(Message::ErrUnexpected)),
+        }
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        self.serialize()
+    }
+}
+
+impl ToValue<bool> for LogicalVariadicExpression {
+    fn get_value(&self, context: &Context) -> Result<bool, CustomError> {
+        match self.eval(LogicalResultType::Boolean, context)? {
+            LogicalResult::Boolean(v) => Ok(v),
+            _ => Err(CustomError::Message(Message::ErrUnexpected)),
+        }
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        self.serialize()
+    }
+}
+
+#[cfg(test)]
+mod logical_tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::error::Language;
+    use crate::expression::LispExpression;
+    use crate::symbol::{Leaf, Symbol};
+
+    #[test]
+    fn test_logical_binary_expression() {
+        let symbols: HashMap<String, Symbol> = HashMap::new();
+        let expr = LogicalBinaryExpression::And((Box::new(true), Box::new(true), vec![]));
+        let res: bool = (&expr).get_value(&Context::new(&symbols)).unwrap();
+        assert_eq!(true, res);
+        // eval == serialize.deserialize.eval
+        assert_eq!(
+            res,
+            (match LispExpression::deserialize(
+                (&expr as &dyn ToValue<bool>).serialize().unwrap()
+            ) {
+                Ok(v) => match v {
+                    LispExpression::LogicalBinaryExpression(v) =>
+                        (&v as &dyn ToValue<bool>).get_value(&Context::new(&symbols)),
+                    _ => Err(CustomError::Message(Message::ErrUnexpected)),
+                },
+                Err(_) => Err(CustomError::Message(Message::ErrUnexpected)),
+            })
+            .unwrap()
+        );
+        // serialize == serialize.deserialize.serialize
+        assert_eq!(
+            (&expr as &dyn ToValue<bool>).serialize().unwrap(),
+            match LispExpression::deserialize((&expr as &dyn ToValue<bool>).serialize().unwrap()) {
+                Ok(v) => match v {
+                    LispExpression::LogicalBinaryExpression(v) =>
+                        (&v as &dyn ToValue<bool>).serialize().unwrap(),
+                    _ => Value::Null,
+                },
+                Err(_) => Value::Null,
+            }
+        );
+        let res: bool = LogicalBinaryExpression::And((Box::new(true), Box::new(false), vec![]))
+            .get_value(&Context::new(&symbols))
+            .unwrap();
+        assert_eq!(false, res);
+        let res: bool = LogicalBinaryExpression::Or((Box::new(true), Box::new(false), vec![]))
+            .get_value(&Context::new(&symbols))
+            .unwrap();
+        assert_eq!(true, res);
+        let res: bool = LogicalBinaryExpression::Or((Box::new(false), Box::new(false), vec![]))
+            .get_value(&Context::new(&symbols))
+            .unwrap();
+        assert_eq!(false, res);
+    }
+
+    #[test]
+    fn test_short_circuit() {
+        let symbols: HashMap<String, Symbol> = vec![
+            (
+                "yes".to_string(),
+                Symbol {
+                    value: Some(Leaf::Boolean(true)),
+                    values: HashMap::new(),
+                },
+            ),
+            (
+                "no".to_string(),
+                Symbol {
+                    value: Some(Leaf::Boolean(false)),
+                    values: HashMap::new(),
+                },
+            ),
+        ]
+        .into_iter()
+        .collect();
+        let eval = |text: &str| match LispExpression::parse(text)
+            .unwrap()
+            .as_boolean(&Context::new(&symbols))
+        {
+            Ok(v) => json!(v),
+            Err(e) => e.serialize(&Language::English),
+        };
+        // skipped operands are not evaluated, so their errors are not raised
+        assert_eq!(json!(false), eval("(and (. no) (>:Number (. x y) 3))"));
+        assert_eq!(json!(false), eval("(and (. yes) (. no) (. x) (. y))"));
+        assert_eq!(json!(true), eval("(or (. yes) (. x))"));
+        assert_eq!(json!(true), eval("(or (. no) (. yes) (. x) (. y))"));
+        assert_eq!(
+            json!({"/args/1/args/0/args/0": "Symbol not found"}),
+            eval("(and (. yes) (>:Number (. x y) 3))")
+        );
+        assert_eq!(
+            json!({"/args/2/args/0": "Symbol not found"}),
+            eval("(or (. no) (. no) (. x) (. yes))")
+        );
+        assert_eq!(json!(true), eval("(and (. yes) (. yes) (. yes))"));
+        assert_eq!(json!(false), eval("(or (. no) (. no) (. no))"));
+    }
+
+    #[test]
+    fn test_logical_unary_expression() {
+        let symbols: HashMap<String, Symbol> = HashMap::new();
+        let expr = LogicalUnaryExpression {
+            value: Box::new(false),
+        };
+        let res: bool = (&expr).get_value(&Context::new(&symbols)).unwrap();
+        assert_eq!(true, res);
+        // eval == serialize.deserialize.eval
+        assert_eq!(
+            res,
+            (match LispExpression::deserialize(
+                (&expr as &dyn ToValue<bool>).serialize().unwrap()
+            ) {
+                Ok(v) => match v {
+                    LispExpression::LogicalUnaryExpression(v) =>
+                        (&v as &dyn ToValue<bool>).get_value(&Context::new(&symbols)),
+                    _ => Err(CustomError::Message(Message::ErrUnexpected)),
+                },
+                Err(_) => Err(CustomError::Message(Message::ErrUnexpected)),
+            })
+            .unwrap()
+        );
+        // serialize == serialize.deserialize.serialize
+        assert_eq!(
+            (&expr as &dyn ToValue<bool>).serialize().unwrap(),
+            match LispExpression::deserialize((&expr as &dyn ToValue<bool>).serialize().unwrap()) {
+                Ok(v) => match v {
+                    LispExpression::LogicalUnaryExpression(v) =>
+                        (&v as &dyn ToValue<bool>).serialize().unwrap(),
+                    _ => Value::Null,
+                },
+                Err(_) => Value::Null,
+            }
+        );
+        let res: bool = LogicalUnaryExpression {
+            value: Box::new(true),
+        }
+        .get_value(&Context::new(&symbols))
+        .unwrap();
+        assert_eq!(false, res);
+    }
+
+    #[test]
+    fn test_logical_variadic_expression() {
+        let symbols: HashMap<String, Symbol> = vec![
+            (
+                "yes".to_string(),
+                Symbol {
+                    value: Some(Leaf::Boolean(true)),
+                    values: HashMap::new(),
+                },
+            ),
+            (
+                "no".to_string(),
+                Symbol {
+                    value: Some(Leaf::Boolean(false)),
+                    values: HashMap::new(),
+                },
+            ),
+            (
+                "unknown".to_string(),
+                Symbol {
+                    value: Some(Leaf::Null),
+                    values: HashMap::new(),
+                },
+            ),
+        ]
+        .into_iter()
+        .collect();
+        let expr = LogicalVariadicExpression {
+            quantifier: LogicalQuantifier::Exactly(Box::new(2)),
+            args: vec![Box::new(true), Box::new(false), Box::new(true)],
+        };
+        let res: bool = expr.get_value(&Context::new(&symbols)).unwrap();
+        assert!(res);
+        // eval == serialize.deserialize.eval
+        assert_eq!(
+            res,
+            (match LispExpression::deserialize((&expr as &dyn ToValue<bool>).serialize().unwrap()) {
+                Ok(v) => match v {
+                    LispExpression::LogicalVariadicExpression(v) =>
+                        (&v as &dyn ToValue<bool>).get_value(&Context::new(&symbols)),
+                    _ => Err(CustomError::Message(Message::ErrUnexpected)),
+                },
+                Err(_) => Err(CustomError::Message(Message::ErrUnexpected)),
+            })
+            .unwrap()
+        );
+        // serialize == serialize.deserialize.serialize
+        assert_eq!(
+            (&expr as &dyn ToValue<bool>).serialize().unwrap(),
+            match LispExpression::deserialize((&expr as &dyn ToValue<bool>).serialize().unwrap()) {
+                Ok(v) => match v {
+                    LispExpression::LogicalVariadicExpression(v) =>
+                        (&v as &dyn ToValue<bool>).serialize().unwrap(),
+                    _ => Value::Null,
+                },
+                Err(_) => Value::Null,
+            }
+        );
+        let eval = |text: &str| match LispExpression::parse(text)
+            .unwrap()
+            .as_text(&Context::new(&symbols))
+        {
+            Ok(v) => json!(v),
+            Err(e) => e.serialize(&Language::English),
+        };
+        // Binary ops over every pair of operands
+        for (a, b) in [(false, false), (false, true), (true, false), (true, true)] {
+            let operands = format!("{} {}", a, b);
+            let expected = [
+                ("xor", a != b),
+                ("implies", !a || b),
+                ("iff", a == b),
+                ("nand", !(a && b)),
+                ("nor", !(a || b)),
+            ];
+            for (op, v) in expected {
+                assert_eq!(
+                    json!(v.to_string()),
+                    eval(&format!("({} {})", op, operands)),
+                    "{} {}",
+                    op,
+                    operands
+                );
+            }
+        }
+        assert_eq!(json!("true"), eval("(xor true true true)"));
+        assert_eq!(json!("false"), eval("(nand true true true)"));
+        assert_eq!(json!("true"), eval("(nor false false false)"));
+        assert_eq!(json!("true"), eval("(all_of)"));
+        assert_eq!(json!("false"), eval("(any_of)"));
+        assert_eq!(json!("true"), eval("(none_of)"));
+        assert_eq!(json!("true"), eval("(exactly_n_of 0)"));
+        assert_eq!(json!("true"), eval("(all_of (. yes))"));
+        assert_eq!(json!("false"), eval("(all_of (. yes) (. no) (. yes))"));
+        assert_eq!(json!("true"), eval("(any_of (. no) (. no) (. yes))"));
+        assert_eq!(json!("false"), eval("(none_of (. no) (. yes))"));
+        assert_eq!(
+            json!("false"),
+            eval("(exactly_n_of 1 (. yes) (. no) (. yes))")
+        );
+        assert_eq!(json!("true"), eval("(exactly_n_of 3 true true true)"));
+        // Nulls are decided by other operands where possible, and otherwise leave the result null
+        assert_eq!(json!("true"), eval("(implies false (. x))"));
+        assert_eq!(json!("true"), eval("(implies (. unknown) true)"));
+        assert_eq!(
+            json!({"/args/0": "Value is null"}),
+            eval("(implies (. unknown) false)")
+        );
+        assert_eq!(
+            json!({"/args/1": "Value is null"}),
+            eval("(xor true (. unknown) true)")
+        );
+        assert_eq!(json!("true"), eval("(nand (. unknown) false)"));
+        assert_eq!(json!("false"), eval("(none_of (. unknown) true)"));
+        assert_eq!(
+            json!({"/args/0": "Value is null"}),
+            eval("(any_of (. unknown) false)")
+        );
+        assert_eq!(
+            json!("false"),
+            eval("(exactly_n_of 1 true (. unknown) true (. x))")
+        );
+        assert_eq!(
+            json!("false"),
+            eval("(exactly_n_of 3 true (. unknown) false)")
+        );
+        assert_eq!(
+            json!({"/args/2": "Value is null"}),
+            eval("(exactly_n_of 1 true (. unknown) false)")
+        );
+        assert_eq!(
+            json!({"/args/0": "Out of range"}),
+            eval("(exactly_n_of -1 true)")
+        );
+        assert_eq!(
+            json!({"/args": "Wrong number of arguments"}),
+            match LispExpression::parse("(implies true true true)") {
+                Ok(_) => Value::Null,
+                Err(e) => e.serialize(&Language::English),
+            }
+        );
+        assert_eq!(
+            json!({"/args": "Wrong number of arguments"}),
+            match LispExpression::parse("(exactly_n_of)") {
+                Ok(_) => Value::Null,
+                Err(e) => e.serialize(&Language::English),
+            }
+        );
+    }
+}
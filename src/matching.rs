@@ -0,0 +1,703 @@
+use crate::collection::Lambda;
+use crate::context::Context;
+use crate::error::{CustomError, Message};
+use crate::expression::{LispExpression, OperandDeserializer};
+use crate::symbol::{Leaf, LeafType, Symbol};
+use crate::temporal::Temporal;
+use crate::value::{eval_arg, ToValue};
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, Duration, FixedOffset, NaiveDate};
+use serde_json::{json, Value};
+
+// MATCH OPS
+
+// A leaf type that conditions and results of matches can take, with the reader of its operands
+pub trait Matchable: PartialOrd + Clone + Into<Leaf> + 'static {
+    const LEAF_TYPE: LeafType;
+    const DESERIALIZER: OperandDeserializer<Self>;
+}
+
+impl Matchable for i32 {
+    const LEAF_TYPE: LeafType = LeafType::Number;
+    const DESERIALIZER: OperandDeserializer<i32> = LispExpression::deserialize_to_number;
+}
+
+impl Matchable for BigDecimal {
+    const LEAF_TYPE: LeafType = LeafType::Decimal;
+    const DESERIALIZER: OperandDeserializer<BigDecimal> = LispExpression::deserialize_to_decimal;
+}
+
+impl Matchable for String {
+    const LEAF_TYPE: LeafType = LeafType::Text;
+    const DESERIALIZER: OperandDeserializer<String> = LispExpression::deserialize_to_text;
+}
+
+impl Matchable for bool {
+    const LEAF_TYPE: LeafType = LeafType::Boolean;
+    const DESERIALIZER: OperandDeserializer<bool> = LispExpression::deserialize_to_boolean;
+}
+
+impl Matchable for NaiveDate {
+    const LEAF_TYPE: LeafType = LeafType::Date;
+    const DESERIALIZER: OperandDeserializer<NaiveDate> = LispExpression::deserialize_to_temporal;
+}
+
+impl Matchable for DateTime<FixedOffset> {
+    const LEAF_TYPE: LeafType = LeafType::DateTime;
+    const DESERIALIZER: OperandDeserializer<DateTime<FixedOffset>> =
+        LispExpression::deserialize_to_temporal;
+}
+
+impl Matchable for Duration {
+    const LEAF_TYPE: LeafType = LeafType::Duration;
+    const DESERIALIZER: OperandDeserializer<Duration> = LispExpression::deserialize_to_temporal;
+}
+
+// A test of the condition, in a match guard
+// A value equal to the condition, as in [value, result]
+// [in, value...], any of the values
+// [between, low, high], within the bounds, both included
+// [where, name, predicate], satisfying the predicate with the condition bound to name
+pub enum Guard<T> {
+    Equals(Box<dyn ToValue<T>>),
+    In(Vec<Box<dyn ToValue<T>>>),
+    Between(Box<dyn ToValue<T>>, Box<dyn ToValue<T>>),
+    Where(Lambda),
+}
+
+impl<T: Matchable> Guard<T> {
+    pub(crate) fn test(&self, condition: &T, context: &Context) -> Result<bool, CustomError> {
+        let locate = |index: usize| move |e: CustomError| e.at(&format!("/{}", index));
+        match self {
+            Guard::Equals(v) => Ok(v.get_value(context)? == *condition),
+            Guard::In(v) => {
+                for (index, val) in v.iter().enumerate() {
+                    if val.get_value(context).map_err(locate(index + 1))? == *condition {
+                        return Ok(true);
+                    }
+                }
+                Ok(false)
+            }
+            Guard::Between(low, high) => Ok(low.get_value(context).map_err(locate(1))?
+                <= *condition
+                && *condition <= high.get_value(context).map_err(locate(2))?),
+            Guard::Where(lambda) => {
+                match lambda
+                    .apply(&Symbol::from_leaf(condition.clone().into()), context)
+                    .map_err(locate(2))?
+                {
+                    Leaf::Boolean(v) => Ok(v),
+                    Leaf::Null => Err(CustomError::Message(Message::ErrNull).at("/2")),
+                    _ => Err(CustomError::Message(Message::ErrUnexpected)),
+                }
+            }
+        }
+    }
+
+    pub(crate) fn serialize(&self) -> Result<Value, CustomError> {
+        match self {
+            Guard::Equals(v) => v.serialize(),
+            Guard::In(v) => {
+                let mut values = vec![json!("in")];
+                for val in v.iter() {
+                    values.push(val.serialize()?);
+                }
+                Ok(Value::Array(values))
+            }
+            Guard::Between(low, high) => {
+                Ok(json!(["between", low.serialize()?, high.serialize()?]))
+            }
+            Guard::Where(lambda) => {
+                let mut values = vec![json!("where")];
+                values.extend(lambda.serialize()?);
+                Ok(Value::Array(values))
+            }
+        }
+    }
+}
+
+// Guards and the results they select
+pub type Guards<T, U> = Vec<(Guard<T>, Box<U>)>;
+
+// Evaluates to the result of the first guard the condition satisfies, or to otherwise.
+// Guards after it are not evaluated, and errors in the ones before it are raised.
+// {"op": "match", "type": [result type, condition type], "args": [condition, [[guard, result], ...], otherwise]}
+pub struct Match<T, U> {
+    pub(crate) condition: Box<dyn ToValue<T>>,
+    pub(crate) guards: Guards<T, dyn ToValue<U>>,
+    pub(crate) otherwise: Box<dyn ToValue<U>>,
+}
+
+impl<T: Matchable, U: Matchable> Match<T, U> {
+    pub(crate) fn eval(&self, context: &Context) -> Result<U, CustomError> {
+        let condition = eval_arg(self.condition.as_ref(), 0, context)?;
+        for (index, (guard, result)) in self.guards.iter().enumerate() {
+            let matched = guard
+                .test(&condition, context)
+                .map_err(|e| e.at(&format!("/args/1/{}/0", index)))?;
+            if matched {
+                return result
+                    .get_value(context)
+                    .map_err(|e| e.at(&format!("/args/1/{}/1", index)));
+            }
+        }
+        self.otherwise
+            .get_value(context)
+            .map_err(|e| e.at("/args/2"))
+    }
+
+    pub(crate) fn serialize(&self) -> Result<Value, CustomError> {
+        let guards: Vec<Value> = self
+            .guards
+            .iter()
+            .map(|(guard, result)| Ok(json!([guard.serialize()?, result.serialize()?])))
+            .collect::<Result<Vec<Value>, CustomError>>()?;
+        Ok(json!({
+            "op": "match",
+            "type": [U::LEAF_TYPE.serialize(), T::LEAF_TYPE.serialize()],
+            "args": [self.condition.serialize()?, guards, self.otherwise.serialize()?]
+        }))
+    }
+}
+
+impl<T: Matchable, U: Matchable> ToValue<Leaf> for Match<T, U> {
+    fn get_value(&self, context: &Context) -> Result<Leaf, CustomError> {
+        Ok(self.eval(context)?.into())
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        self.serialize()
+    }
+}
+
+// A match, whatever the types of its condition and results.
+// Its result is read as a leaf, converted to the type it is used as.
+pub struct MatchExpression {
+    pub(crate) result_type: LeafType,
+    pub(crate) value: Box<dyn ToValue<Leaf>>,
+}
+
+impl MatchExpression {
+    pub(crate) fn new<T: Matchable, U: Matchable>(value: Match<T, U>) -> MatchExpression {
+        MatchExpression {
+            result_type: U::LEAF_TYPE,
+            value: Box::new(value),
+        }
+    }
+
+    pub(crate) fn eval(&self, context: &Context) -> Result<Leaf, CustomError> {
+        self.value.get_value(context)
+    }
+
+    pub(crate) fn serialize(&self) -> Result<Value, CustomError> {
+        self.value.serialize()
+    }
+}
+
+impl ToValue<i32> for MatchExpression {
+    fn get_value(&self, context: &Context) -> Result<i32, CustomError> {
+        match self.eval(context)? {
+            Leaf::Number(v) => Ok(v),
+            Leaf::Decimal(v) => context.to_number(&v),
+            Leaf::Null => Err(CustomError::Message(Message::ErrNull)),
+            _ => Err(CustomError::Message(Message::ErrTypeMismatch)),
+        }
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        self.serialize()
+    }
+}
+
+impl ToValue<BigDecimal> for MatchExpression {
+    fn get_value(&self, context: &Context) -> Result<BigDecimal, CustomError> {
+        match self.eval(context)? {
+            Leaf::Number(v) => Ok(BigDecimal::from(v)),
+            Leaf::Decimal(v) => Ok(v),
+            Leaf::Null => Err(CustomError::Message(Message::ErrNull)),
+            _ => Err(CustomError::Message(Message::ErrTypeMismatch)),
+        }
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        self.serialize()
+    }
+}
+
+impl ToValue<String> for MatchExpression {
+    fn get_value(&self, context: &Context) -> Result<String, CustomError> {
+        match self.eval(context)? {
+            Leaf::Number(v) => Ok(v.to_string()),
+            Leaf::Decimal(v) => Ok(v.to_string()),
+            Leaf::Text(v) => Ok(v),
+            Leaf::Boolean(v) => Ok(v.to_string()),
+            Leaf::Date(v) => Ok(v.to_text()),
+            Leaf::DateTime(v) => Ok(v.to_text()),
+            Leaf::Duration(v) => Ok(v.to_text()),
+            Leaf::Null => Err(CustomError::Message(Message::ErrNull)),
+            Leaf::List(_) => Err(CustomError::Message(Message::ErrTypeMismatch)),
+        }
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        self.serialize()
+    }
+}
+
+impl ToValue<bool> for MatchExpression {
+    fn get_value(&self, context: &Context) -> Result<bool, CustomError> {
+        match self.eval(context)? {
+            Leaf::Boolean(v) => Ok(v),
+            Leaf::Null => Err(CustomError::Message(Message::ErrNull)),
+            _ => Err(CustomError::Message(Message::ErrTypeMismatch)),
+        }
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        self.serialize()
+    }
+}
+
+impl<T: Temporal> ToValue<T> for MatchExpression {
+    fn get_value(&self, context: &Context) -> Result<T, CustomError> {
+        T::from_leaf(self.eval(context)?)
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        self.serialize()
+    }
+}
+
+#[cfg(test)]
+mod matching_tests {
+    use std::collections::HashMap;
+    use std::str::FromStr;
+
+    use super::*;
+    use crate::dot::DotExpression;
+    use crate::error::Language;
+
+    #[test]
+    fn test_number_match_expression() {
+        let symbols: HashMap<String, Symbol> = HashMap::new();
+        let expr = MatchExpression::new(Match::<i32, i32> {
+            condition: Box::new(2),
+            guards: vec![],
+            otherwise: Box::new(7),
+        });
+        let res: i32 = (&expr).get_value(&Context::new(&symbols)).unwrap();
+        assert_eq!(7, res);
+        // eval == serialize.deserialize.eval
+        assert_eq!(
+            res,
+            (match LispExpression::deserialize((&expr as &dyn ToValue<i32>).serialize().unwrap()) {
+                Ok(v) => match v {
+                    LispExpression::MatchExpression(v) =>
+                        (&v as &dyn ToValue<i32>).get_value(&Context::new(&symbols)),
+                    _ => Err(CustomError::Message(Message::ErrUnexpected)),
+                },
+                Err(_) => Err(CustomError::Message(Message::ErrUnexpected)),
+            })
+            .unwrap()
+        );
+        // serialize == serialize.deserialize.serialize
+        assert_eq!(
+            (&expr as &dyn ToValue<i32>).serialize().unwrap(),
+            match LispExpression::deserialize((&expr as &dyn ToValue<i32>).serialize().unwrap()) {
+                Ok(v) => match v {
+                    LispExpression::MatchExpression(v) =>
+                        (&v as &dyn ToValue<i32>).serialize().unwrap(),
+                    _ => Value::Null,
+                },
+                Err(_) => Value::Null,
+            }
+        );
+        let res: i32 = MatchExpression::new(Match::<i32, i32> {
+            condition: Box::new(2),
+            guards: vec![
+                (Guard::Equals(Box::new(5)), Box::new(8)),
+                (Guard::Equals(Box::new(2)), Box::new(11)),
+                (Guard::Equals(Box::new(3)), Box::new(13)),
+            ],
+            otherwise: Box::new(7),
+        })
+        .get_value(&Context::new(&symbols))
+        .unwrap();
+        assert_eq!(11, res);
+    }
+
+    #[test]
+    fn test_decimal_match_expression() {
+        let symbols: HashMap<String, Symbol> = HashMap::new();
+        let expr = MatchExpression::new(Match::<i32, BigDecimal> {
+            condition: Box::new(2),
+            guards: vec![],
+            otherwise: Box::new(BigDecimal::from_str("2.3").unwrap()),
+        });
+        let res: BigDecimal = (&expr).get_value(&Context::new(&symbols)).unwrap();
+        assert_eq!(BigDecimal::from_str("2.3").unwrap(), res);
+        // eval == serialize.deserialize.eval
+        assert_eq!(
+            res,
+            (match LispExpression::deserialize(
+                (&expr as &dyn ToValue<BigDecimal>).serialize().unwrap()
+            ) {
+                Ok(v) => match v {
+                    LispExpression::MatchExpression(v) =>
+                        (&v as &dyn ToValue<BigDecimal>).get_value(&Context::new(&symbols)),
+                    _ => Err(CustomError::Message(Message::ErrUnexpected)),
+                },
+                Err(_) => Err(CustomError::Message(Message::ErrUnexpected)),
+            })
+            .unwrap()
+        );
+        // serialize == serialize.deserialize.serialize
+        assert_eq!(
+            (&expr as &dyn ToValue<BigDecimal>).serialize().unwrap(),
+            match LispExpression::deserialize(
+                (&expr as &dyn ToValue<BigDecimal>).serialize().unwrap()
+            ) {
+                Ok(v) => match v {
+                    LispExpression::MatchExpression(v) =>
+                        (&v as &dyn ToValue<BigDecimal>).serialize().unwrap(),
+                    _ => Value::Null,
+                },
+                Err(_) => Value::Null,
+            }
+        );
+    }
+
+    #[test]
+    fn test_text_match_expression() {
+        let symbols: HashMap<String, Symbol> = HashMap::new();
+        let expr = MatchExpression::new(Match::<i32, String> {
+            condition: Box::new(2),
+            guards: vec![],
+            otherwise: Box::new(BigDecimal::from_str("2.3").unwrap()),
+        });
+        let res: String = (&expr).get_value(&Context::new(&symbols)).unwrap();
+        assert_eq!("2.3".to_string(), res);
+        // eval == serialize.deserialize.eval
+        assert_eq!(
+            res,
+            (match LispExpression::deserialize(
+                (&expr as &dyn ToValue<String>).serialize().unwrap()
+            ) {
+                Ok(v) => match v {
+                    LispExpression::MatchExpression(v) =>
+                        (&v as &dyn ToValue<String>).get_value(&Context::new(&symbols)),
+                    _ => Err(CustomError::Message(Message::ErrUnexpected)),
+                },
+                Err(_) => Err(CustomError::Message(Message::ErrUnexpected)),
+            })
+            .unwrap()
+        );
+        // serialize == serialize.deserialize.serialize
+        assert_eq!(
+            (&expr as &dyn ToValue<String>).serialize().unwrap(),
+            match LispExpression::deserialize((&expr as &dyn ToValue<String>).serialize().unwrap())
+            {
+                Ok(v) => match v {
+                    LispExpression::MatchExpression(v) =>
+                        (&v as &dyn ToValue<String>).serialize().unwrap(),
+                    _ => Value::Null,
+                },
+                Err(_) => Value::Null,
+            }
+        );
+    }
+
+    #[test]
+    fn test_boolean_match_expression() {
+        let symbols: HashMap<String, Symbol> = HashMap::new();
+        let expr = MatchExpression::new(Match::<i32, bool> {
+            condition: Box::new(2),
+            guards: vec![],
+            otherwise: Box::new(false),
+        });
+        let res: bool = (&expr).get_value(&Context::new(&symbols)).unwrap();
+        assert_eq!(false, res);
+        // eval == serialize.deserialize.eval
+        assert_eq!(
+            res,
+            (match LispExpression::deserialize(
+                (&expr as &dyn ToValue<bool>).serialize().unwrap()
+            ) {
+                Ok(v) => match v {
+                    LispExpression::MatchExpression(v) =>
+                        (&v as &dyn ToValue<bool>).get_value(&Context::new(&symbols)),
+                    _ => Err(CustomError::Message(Message::ErrUnexpected)),
+                },
+                Err(_) => Err(CustomError::Message(Message::ErrUnexpected)),
+            })
+            .unwrap()
+        );
+        // serialize == serialize.deserialize.serialize
+        assert_eq!(
+            (&expr as &dyn ToValue<bool>).serialize().unwrap(),
+            match LispExpression::deserialize((&expr as &dyn ToValue<bool>).serialize().unwrap()) {
+                Ok(v) => match v {
+                    LispExpression::MatchExpression(v) =>
+                        (&v as &dyn ToValue<bool>).serialize().unwrap(),
+                    _ => Value::Null,
+                },
+                Err(_) => Value::Null,
+            }
+        );
+    }
+
+    #[test]
+    fn test_match_guards() {
+        let symbols: HashMap<String, Symbol> = vec![
+            (
+                "score".to_string(),
+                Symbol {
+                    value: Some(Leaf::Number(72)),
+                    values: HashMap::new(),
+                },
+            ),
+            (
+                "missing".to_string(),
+                Symbol {
+                    value: Some(Leaf::Null),
+                    values: HashMap::new(),
+                },
+            ),
+        ]
+        .into_iter()
+        .collect();
+        let expr = MatchExpression::new(Match::<i32, String> {
+            condition: Box::new(DotExpression {
+                path: vec!["score".to_string()],
+            }),
+            guards: vec![
+                (
+                    Guard::Between(Box::new(90), Box::new(100)),
+                    Box::new("A".to_string()),
+                ),
+                (
+                    Guard::In(vec![Box::new(71), Box::new(72)]),
+                    Box::new("C".to_string()),
+                ),
+            ],
+            otherwise: Box::new("F".to_string()),
+        });
+        let res: String = expr.get_value(&Context::new(&symbols)).unwrap();
+        assert_eq!("C", res);
+        // eval == serialize.deserialize.eval
+        assert_eq!(
+            res,
+            (match LispExpression::deserialize(
+                (&expr as &dyn ToValue<String>).serialize().unwrap()
+            ) {
+                Ok(v) => match v {
+                    LispExpression::MatchExpression(v) =>
+                        (&v as &dyn ToValue<String>).get_value(&Context::new(&symbols)),
+                    _ => Err(CustomError::Message(Message::ErrUnexpected)),
+                },
+                Err(_) => Err(CustomError::Message(Message::ErrUnexpected)),
+            })
+            .unwrap()
+        );
+        // serialize == serialize.deserialize.serialize
+        assert_eq!(
+            (&expr as &dyn ToValue<String>).serialize().unwrap(),
+            match LispExpression::deserialize((&expr as &dyn ToValue<String>).serialize().unwrap())
+            {
+                Ok(v) => match v {
+                    LispExpression::MatchExpression(v) =>
+                        (&v as &dyn ToValue<String>).serialize().unwrap(),
+                    _ => Value::Null,
+                },
+                Err(_) => Value::Null,
+            }
+        );
+        let eval = |text: &str| match LispExpression::parse(text)
+            .unwrap()
+            .as_text(&Context::new(&symbols))
+        {
+            Ok(v) => json!(v),
+            Err(e) => e.serialize(&Language::English),
+        };
+        // The first guard the condition satisfies decides the result
+        assert_eq!(
+            json!("first"),
+            eval("(match:Text:Number (. score) [[72 first] [72 second]] none)")
+        );
+        let table = "[[[between 90 100] A] [[between 80 89] B] [[in 70 71 72 73] C] [[where s (>:Number (. s) 59)] D]]";
+        for (score, grade) in [
+            (95, "A"),
+            (80, "B"),
+            (72, "C"),
+            (60, "D"),
+            (59, "F"),
+            (101, "D"),
+            (40, "F"),
+        ] {
+            assert_eq!(
+                json!(grade),
+                eval(&format!("(match:Text:Number {} {} F)", score, table)),
+                "{}",
+                score
+            );
+        }
+        assert_eq!(
+            json!("1"),
+            eval("(match:Number:Decimal 2.50 [[[between 1 2.5] 1]] 0)")
+        );
+        assert_eq!(
+            json!("b"),
+            eval("(match:Text:Text (. score) [[[in 7 70] a] [[where score (ends_with (. score) 2)] b]] c)")
+        );
+        // Guards after the one satisfied are not evaluated, while errors in the ones before it are raised
+        assert_eq!(
+            json!("a"),
+            eval("(match:Text:Number (. score) [[72 a] [(. x) b]] c)")
+        );
+        assert_eq!(
+            json!({"/args/1/0/0/args/0": "Symbol not found"}),
+            eval("(match:Text:Number (. score) [[(. x) a] [72 b]] c)")
+        );
+        assert_eq!(
+            json!({"/args/1/1/0/2/args/0": "Symbol not found"}),
+            eval("(match:Text:Number (. score) [[1 a] [[in 2 (. x) 72] b]] c)")
+        );
+        assert_eq!(
+            json!({"/args/1/0/0/2": "Value is null"}),
+            eval("(match:Text:Number (. score) [[[where s (. missing)] a]] c)")
+        );
+        assert_eq!(
+            json!({"/args/1/0/1/args/0": "Symbol not found"}),
+            eval("(match:Text:Number (. score) [[72 (. x)]] c)")
+        );
+        assert_eq!(
+            json!({"/args/0": "Value is null"}),
+            eval("(match:Text:Number (. missing) [[72 a]] c)")
+        );
+        assert_eq!(
+            json!({"/args/1/0/0": "Wrong number of arguments"}),
+            match LispExpression::parse("(match:Text:Number (. score) [[[between 1] a]] c)") {
+                Ok(_) => Value::Null,
+                Err(e) => e.serialize(&Language::English),
+            }
+        );
+        assert_eq!(
+            json!({"/args/1/0/0/1": "Type mismatch"}),
+            match LispExpression::parse("(match:Text:Number (. score) [[[where 1 true] a]] c)") {
+                Ok(_) => Value::Null,
+                Err(e) => e.serialize(&Language::English),
+            }
+        );
+        assert_eq!(
+            json!({"/args/1/0/0": "Type mismatch"}),
+            match LispExpression::parse("(match:Text:Number (. score) [[[up_to 1] a]] c)") {
+                Ok(_) => Value::Null,
+                Err(e) => e.serialize(&Language::English),
+            }
+        );
+    }
+
+    #[test]
+    fn test_temporal_match_expression() {
+        let symbols: HashMap<String, Symbol> = vec![
+            (
+                "due".to_string(),
+                Symbol {
+                    value: Some(Leaf::Date(NaiveDate::from_ymd_opt(2024, 3, 1).unwrap())),
+                    values: HashMap::new(),
+                },
+            ),
+            (
+                "plan".to_string(),
+                Symbol {
+                    value: Some(Leaf::Text("basic".to_string())),
+                    values: HashMap::new(),
+                },
+            ),
+        ]
+        .into_iter()
+        .collect();
+        let expr = MatchExpression::new(Match::<NaiveDate, String> {
+            condition: Box::new(DotExpression {
+                path: vec!["due".to_string()],
+            }),
+            guards: vec![(
+                Guard::Between(
+                    Box::new(NaiveDate::from_ymd_opt(2024, 1, 1).unwrap()),
+                    Box::new(NaiveDate::from_ymd_opt(2024, 6, 30).unwrap()),
+                ),
+                Box::new("H1".to_string()),
+            )],
+            otherwise: Box::new("H2".to_string()),
+        });
+        let res: String = expr.get_value(&Context::new(&symbols)).unwrap();
+        assert_eq!("H1", res);
+        // eval == serialize.deserialize.eval
+        assert_eq!(
+            res,
+            (match LispExpression::deserialize(
+                (&expr as &dyn ToValue<String>).serialize().unwrap()
+            ) {
+                Ok(v) => match v {
+                    LispExpression::MatchExpression(v) =>
+                        (&v as &dyn ToValue<String>).get_value(&Context::new(&symbols)),
+                    _ => Err(CustomError::Message(Message::ErrUnexpected)),
+                },
+                Err(_) => Err(CustomError::Message(Message::ErrUnexpected)),
+            })
+            .unwrap()
+        );
+        // serialize == serialize.deserialize.serialize
+        assert_eq!(
+            (&expr as &dyn ToValue<String>).serialize().unwrap(),
+            match LispExpression::deserialize((&expr as &dyn ToValue<String>).serialize().unwrap())
+            {
+                Ok(v) => match v {
+                    LispExpression::MatchExpression(v) =>
+                        (&v as &dyn ToValue<String>).serialize().unwrap(),
+                    _ => Value::Null,
+                },
+                Err(_) => Value::Null,
+            }
+        );
+        let eval = |text: &str| match LispExpression::parse(text)
+            .unwrap()
+            .as_text(&Context::new(&symbols))
+        {
+            Ok(v) => json!(v),
+            Err(e) => e.serialize(&Language::English),
+        };
+        assert_eq!(
+            json!("2024-01-31"),
+            eval("(match:Date:Text (. plan) [[basic 2024-01-31]] 2024-12-31)")
+        );
+        assert_eq!(
+            json!("2024-02-01"),
+            eval("(add_days (match:Date:Text (. plan) [[basic 2024-01-31]] 2024-12-31) 1)")
+        );
+        assert_eq!(
+            json!("late"),
+            eval("(match:Text:Date (. due) [[[where d (>:Date (. d) 2024-02-01)] late]] early)")
+        );
+        assert_eq!(
+            json!("PT0S"),
+            eval("(match:Duration:Date (. due) [[[in 2024-02-29 2024-03-01] PT0S]] P1D)")
+        );
+        assert_eq!(
+            json!({"/args/1/0/1": "Invalid literal"}),
+            match LispExpression::parse("(match:Date:Text (. plan) [[basic tomorrow]] 2024-12-31)")
+            {
+                Ok(_) => Value::Null,
+                Err(e) => e.serialize(&Language::English),
+            }
+        );
+        // matches are read as their result type, wherever they are used
+        assert_eq!(
+            json!({"/args/0": "Type mismatch"}),
+            match LispExpression::parse("(add_days (match:Duration:Text a [[a P1D]] P2D) 1)") {
+                Ok(_) => Value::Null,
+                Err(e) => e.serialize(&Language::English),
+            }
+        );
+    }
+}
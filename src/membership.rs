@@ -0,0 +1,319 @@
+use crate::context::Context;
+use crate::error::CustomError;
+use crate::symbol::LeafType;
+use crate::value::{eval_arg, ToValue};
+use bigdecimal::BigDecimal;
+use serde_json::{json, Value};
+use std::collections::HashSet;
+use std::hash::Hash;
+
+// MEMBERSHIP
+
+// Tests a value against candidates, or against the bounds of a range.
+// Candidates that are all literals are hashed once, when deserialized.
+// As with or, a null candidate leaves the result null unless another one matches.
+pub enum Membership<T> {
+    In {
+        negated: bool,
+        value: Box<dyn ToValue<T>>,
+        candidates: Vec<Box<dyn ToValue<T>>>,
+        lookup: Option<HashSet<T>>,
+    },
+    Between {
+        exclusive: bool, // whether the bounds are excluded
+        value: Box<dyn ToValue<T>>,
+        low: Box<dyn ToValue<T>>,
+        high: Box<dyn ToValue<T>>,
+    },
+}
+
+impl<T: Eq + Hash + Ord> Membership<T> {
+    pub(crate) fn eval(&self, context: &Context) -> Result<bool, CustomError> {
+        match self {
+            Membership::In {
+                negated,
+                value,
+                candidates,
+                lookup,
+            } => {
+                let value = eval_arg(value.as_ref(), 0, context)?;
+                let found = match lookup {
+                    Some(v) => v.contains(&value),
+                    None => {
+                        let mut unknown: Option<CustomError> = None;
+                        let mut found = false;
+                        for (index, val) in candidates.iter().enumerate() {
+                            match eval_arg(val.as_ref(), index + 1, context) {
+                                Ok(v) if v == value => {
+                                    found = true;
+                                    break;
+                                }
+                                Ok(_) => {}
+                                Err(e) if e.is_null() => unknown = unknown.or(Some(e)),
+                                Err(e) => return Err(e),
+                            }
+                        }
+                        match (found, unknown) {
+                            (false, Some(e)) => return Err(e),
+                            _ => found,
+                        }
+                    }
+                };
+                Ok(found != *negated)
+            }
+            Membership::Between {
+                exclusive,
+                value,
+                low,
+                high,
+            } => {
+                let value = eval_arg(value.as_ref(), 0, context)?;
+                let low = eval_arg(low.as_ref(), 1, context)?;
+                let high = eval_arg(high.as_ref(), 2, context)?;
+                match exclusive {
+                    true => Ok(low < value && value < high),
+                    false => Ok(low <= value && value <= high),
+                }
+            }
+        }
+    }
+
+    pub(crate) fn serialize(&self, value_type: LeafType) -> Result<Value, CustomError> {
+        let (op, args): (&str, Vec<Result<Value, CustomError>>) = match self {
+            Membership::In {
+                negated,
+                value,
+                candidates,
+                ..
+            } => (
+                match negated {
+                    true => "not_in",
+                    false => "in",
+                },
+                std::iter::once(value)
+                    .chain(candidates)
+                    .map(|val| val.serialize())
+                    .collect(),
+            ),
+            Membership::Between {
+                exclusive,
+                value,
+                low,
+                high,
+            } => (
+                match exclusive {
+                    true => "between_exclusive",
+                    false => "between",
+                },
+                vec![value.serialize(), low.serialize(), high.serialize()],
+            ),
+        };
+        Ok(json!({
+            "op": op,
+            "type": value_type.serialize(),
+            "args": args.into_iter().collect::<Result<Vec<Value>, CustomError>>()?
+        }))
+    }
+}
+
+pub enum MembershipExpression {
+    Number(Membership<i32>),
+    Decimal(Membership<BigDecimal>),
+    Text(Membership<String>),
+}
+
+impl MembershipExpression {
+    pub(crate) fn eval(&self, context: &Context) -> Result<bool, CustomError> {
+        match self {
+            MembershipExpression::Number(v) => v.eval(context),
+            MembershipExpression::Decimal(v) => v.eval(context),
+            MembershipExpression::Text(v) => v.eval(context),
+        }
+    }
+
+    pub(crate) fn serialize(&self) -> Result<Value, CustomError> {
+        match self {
+            MembershipExpression::Number(v) => v.serialize(LeafType::Number),
+            MembershipExpression::Decimal(v) => v.serialize(LeafType::Decimal),
+            MembershipExpression::Text(v) => v.serialize(LeafType::Text),
+        }
+    }
+}
+
+impl ToValue<String> for MembershipExpression {
+    fn get_value(&self, context: &Context) -> Result<String, CustomError> {
+        Ok(self.eval(context)?.to_string())
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        self.serialize()
+    }
+}
+
+impl ToValue<bool> for MembershipExpression {
+    fn get_value(&self, context: &Context) -> Result<bool, CustomError> {
+        self.eval(context)
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        self.serialize()
+    }
+}
+
+#[cfg(test)]
+mod membership_tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::dot::DotExpression;
+    use crate::error::{Language, Message};
+    use crate::expression::LispExpression;
+    use crate::symbol::{Leaf, Symbol};
+
+    #[test]
+    fn test_membership_expression() {
+        let symbols: HashMap<String, Symbol> = vec![
+            (
+                "status".to_string(),
+                Symbol {
+                    value: Some(Leaf::Text("B".to_string())),
+                    values: HashMap::new(),
+                },
+            ),
+            (
+                "qty".to_string(),
+                Symbol {
+                    value: Some(Leaf::Number(10)),
+                    values: HashMap::new(),
+                },
+            ),
+            (
+                "missing".to_string(),
+                Symbol {
+                    value: Some(Leaf::Null),
+                    values: HashMap::new(),
+                },
+            ),
+        ]
+        .into_iter()
+        .collect();
+        let expr = MembershipExpression::Text(Membership::In {
+            negated: false,
+            value: Box::new(DotExpression {
+                path: vec!["status".to_string()],
+            }),
+            candidates: vec![Box::new("A".to_string()), Box::new("B".to_string())],
+            lookup: None,
+        });
+        let res: bool = expr.get_value(&Context::new(&symbols)).unwrap();
+        assert!(res);
+        // eval == serialize.deserialize.eval
+        assert_eq!(
+            res,
+            (match LispExpression::deserialize((&expr as &dyn ToValue<bool>).serialize().unwrap()) {
+                Ok(v) => match v {
+                    LispExpression::MembershipExpression(v) =>
+                        (&v as &dyn ToValue<bool>).get_value(&Context::new(&symbols)),
+                    _ => Err(CustomError::Message(Message::ErrUnexpected)),
+                },
+                Err(_) => Err(CustomError::Message(Message::ErrUnexpected)),
+            })
+            .unwrap()
+        );
+        // serialize == serialize.deserialize.serialize
+        assert_eq!(
+            (&expr as &dyn ToValue<bool>).serialize().unwrap(),
+            match LispExpression::deserialize((&expr as &dyn ToValue<bool>).serialize().unwrap()) {
+                Ok(v) => match v {
+                    LispExpression::MembershipExpression(v) =>
+                        (&v as &dyn ToValue<bool>).serialize().unwrap(),
+                    _ => Value::Null,
+                },
+                Err(_) => Value::Null,
+            }
+        );
+        let eval = |text: &str| match LispExpression::parse(text)
+            .unwrap()
+            .as_text(&Context::new(&symbols))
+        {
+            Ok(v) => json!(v),
+            Err(e) => e.serialize(&Language::English),
+        };
+        assert_eq!(json!("true"), eval("(in:Text (. status) A B C)"));
+        assert_eq!(json!("false"), eval("(not_in:Text (. status) A B C)"));
+        assert_eq!(json!("true"), eval("(in:Number (. qty) 1 (+:Number 5 5))"));
+        assert_eq!(json!("true"), eval("(in:Decimal 2.5 1 2.50)"));
+        assert_eq!(json!("true"), eval("(between:Number (. qty) 1 10)"));
+        assert_eq!(
+            json!("false"),
+            eval("(between_exclusive:Number (. qty) 1 10)")
+        );
+        assert_eq!(json!("true"), eval("(between_exclusive:Decimal 1.5 1 2)"));
+        assert_eq!(json!("false"), eval("(between:Number (. qty) 20 1)"));
+        assert_eq!(json!("true"), eval("(between:Text (. status) A C)"));
+        // Literal candidates are looked up in a set
+        let candidates: Vec<String> = (0..1000).map(|v| (v * 3).to_string()).collect();
+        let text = format!("(in:Number (. qty) {})", candidates.join(" "));
+        match LispExpression::parse(&text).unwrap() {
+            LispExpression::MembershipExpression(MembershipExpression::Number(
+                Membership::In { lookup, .. },
+            )) => assert_eq!(Some(1000), lookup.map(|v| v.len())),
+            _ => panic!(),
+        }
+        assert_eq!(json!("false"), eval(&text));
+        assert_eq!(
+            json!("true"),
+            eval(&format!("(not_in:Number (. qty) {})", candidates.join(" ")))
+        );
+        // Candidates are evaluated in order until one matches
+        assert_eq!(json!("true"), eval("(in:Number (. qty) 10 (. unknown))"));
+        assert_eq!(
+            json!({"/args/2/args/0": "Symbol not found"}),
+            eval("(in:Number (. qty) 1 (. unknown))")
+        );
+        assert_eq!(json!("true"), eval("(in:Number (. qty) 1 null 10)"));
+        assert_eq!(json!("true"), eval("(in:Number 1 null 1)"));
+        assert_eq!(
+            json!({"/args/2": "Value is null"}),
+            eval("(in:Number (. qty) 1 null 2)")
+        );
+        assert_eq!(
+            json!({"/args/1": "Value is null"}),
+            eval("(not_in:Number (. qty) (. missing) null)")
+        );
+        // Decimal candidates are not rounded to match a Number
+        assert_eq!(json!("true"), eval("(in:Number 2 2.0)"));
+        assert_eq!(
+            json!({"/args/1": "Type mismatch"}),
+            match LispExpression::parse("(in:Number 2 2.5)") {
+                Ok(_) => Value::Null,
+                Err(e) => e.serialize(&Language::English),
+            }
+        );
+        assert_eq!(
+            json!({"/args/0": "Value is null"}),
+            eval("(between:Number (. missing) 1 10)")
+        );
+        assert_eq!(
+            json!({"/args": "Wrong number of arguments"}),
+            match LispExpression::parse("(in:Number (. qty))") {
+                Ok(_) => Value::Null,
+                Err(e) => e.serialize(&Language::English),
+            }
+        );
+        assert_eq!(
+            json!({"/args": "Wrong number of arguments"}),
+            match LispExpression::parse("(between:Number (. qty) 1)") {
+                Ok(_) => Value::Null,
+                Err(e) => e.serialize(&Language::English),
+            }
+        );
+        assert_eq!(
+            json!({"/type": "Type mismatch"}),
+            match LispExpression::parse("(in:Boolean true false)") {
+                Ok(_) => Value::Null,
+                Err(e) => e.serialize(&Language::English),
+            }
+        );
+    }
+}
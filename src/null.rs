@@ -0,0 +1,284 @@
+use crate::binding::Binding;
+use crate::context::Context;
+use crate::dot::DotExpression;
+use crate::error::{CustomError, Message};
+use crate::symbol::{Leaf, LeafType};
+use crate::temporal::Temporal;
+use crate::value::ToValue;
+use bigdecimal::BigDecimal;
+use serde_json::{json, Value};
+
+// NULL OPS
+
+// The null literal, which evaluates to a null of whatever type is expected
+pub struct Null;
+
+impl<T> ToValue<T> for Null {
+    fn get_value(&self, _context: &Context) -> Result<T, CustomError> {
+        Err(CustomError::Message(Message::ErrNull))
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        Ok(Value::Null)
+    }
+}
+
+// {"op": "exists", "args": [path...]}, whether the path resolves to a value that is set
+// {"op": "is_null", "args": [[type, value]]}
+// {"op": "coalesce", "type": T, "args": [value, ...]}, the first value that is not null
+pub enum NullExpression {
+    Exists(DotExpression),
+    IsNull(Binding),
+    Coalesce {
+        result_type: LeafType,
+        args: Vec<Binding>,
+    },
+}
+
+impl NullExpression {
+    pub(crate) fn eval(&self, context: &Context) -> Result<Leaf, CustomError> {
+        match self {
+            NullExpression::Exists(path) => match path.resolve(context) {
+                Ok(Leaf::Null) | Err(_) => Ok(Leaf::Boolean(false)),
+                Ok(_) => Ok(Leaf::Boolean(true)),
+            },
+            NullExpression::IsNull(value) => match value.eval(context) {
+                Ok(v) => Ok(Leaf::Boolean(matches!(v, Leaf::Null))),
+                Err(e) => Err(e.at("/args/0/1")),
+            },
+            NullExpression::Coalesce { args, .. } => {
+                for (index, val) in args.iter().enumerate() {
+                    match val.eval(context) {
+                        Ok(Leaf::Null) => {}
+                        Ok(v) => return Ok(v),
+                        Err(e) => return Err(e.at(&format!("/args/{}", index))),
+                    }
+                }
+                Ok(Leaf::Null)
+            }
+        }
+    }
+
+    pub(crate) fn serialize(&self) -> Result<Value, CustomError> {
+        match self {
+            NullExpression::Exists(path) => Ok(json!({
+                "op": "exists",
+                "args": path.path
+            })),
+            NullExpression::IsNull(value) => {
+                let (value_type, v) = value.serialize()?;
+                Ok(json!({
+                    "op": "is_null",
+                    "args": [[value_type.serialize(), v]]
+                }))
+            }
+            NullExpression::Coalesce { result_type, args } => Ok(json!({
+                "op": "coalesce",
+                "type": result_type.serialize(),
+                "args": args
+                    .iter()
+                    .map(|val| Ok(val.serialize()?.1))
+                    .collect::<Result<Vec<Value>, CustomError>>()?
+            })),
+        }
+    }
+}
+
+impl ToValue<i32> for NullExpression {
+    fn get_value(&self, context: &Context) -> Result<i32, CustomError> {
+        match self.eval(context)? {
+            Leaf::Number(v) => Ok(v),
+            Leaf::Decimal(v) => context.to_number(&v),
+            Leaf::Null => Err(CustomError::Message(Message::ErrNull)),
+            _ => Err(CustomError::Message(Message::ErrTypeMismatch)),
+        }
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        self.serialize()
+    }
+}
+
+impl ToValue<BigDecimal> for NullExpression {
+    fn get_value(&self, context: &Context) -> Result<BigDecimal, CustomError> {
+        match self.eval(context)? {
+            Leaf::Number(v) => Ok(BigDecimal::from(v)),
+            Leaf::Decimal(v) => Ok(v),
+            Leaf::Null => Err(CustomError::Message(Message::ErrNull)),
+            _ => Err(CustomError::Message(Message::ErrTypeMismatch)),
+        }
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        self.serialize()
+    }
+}
+
+impl ToValue<String> for NullExpression {
+    fn get_value(&self, context: &Context) -> Result<String, CustomError> {
+        match self.eval(context)? {
+            Leaf::Number(v) => Ok(v.to_string()),
+            Leaf::Decimal(v) => Ok(v.to_string()),
+            Leaf::Text(v) => Ok(v),
+            Leaf::Boolean(v) => Ok(v.to_string()),
+            Leaf::Date(v) => Ok(v.to_text()),
+            Leaf::DateTime(v) => Ok(v.to_text()),
+            Leaf::Duration(v) => Ok(v.to_text()),
+            Leaf::Null => Err(CustomError::Message(Message::ErrNull)),
+            Leaf::List(_) => Err(CustomError::Message(Message::ErrTypeMismatch)),
+        }
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        self.serialize()
+    }
+}
+
+impl ToValue<bool> for NullExpression {
+    fn get_value(&self, context: &Context) -> Result<bool, CustomError> {
+        match self.eval(context)? {
+            Leaf::Boolean(v) => Ok(v),
+            Leaf::Null => Err(CustomError::Message(Message::ErrNull)),
+            _ => Err(CustomError::Message(Message::ErrTypeMismatch)),
+        }
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        self.serialize()
+    }
+}
+
+#[cfg(test)]
+mod null_tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::error::Language;
+    use crate::expression::LispExpression;
+    use crate::symbol::Symbol;
+
+    #[test]
+    fn test_null_expression() {
+        let symbols: HashMap<String, Symbol> = vec![
+            (
+                "discount".to_string(),
+                Symbol {
+                    value: None,
+                    values: HashMap::new(),
+                },
+            ),
+            (
+                "price".to_string(),
+                Symbol {
+                    value: Some(Leaf::Number(40)),
+                    values: HashMap::new(),
+                },
+            ),
+        ]
+        .into_iter()
+        .collect();
+        let expr = NullExpression::Coalesce {
+            result_type: LeafType::Number,
+            args: vec![
+                Binding::Number(Box::new(DotExpression {
+                    path: vec!["discount".to_string()],
+                })),
+                Binding::Number(Box::new(Null)),
+                Binding::Number(Box::new(0)),
+            ],
+        };
+        let res: i32 = expr.get_value(&Context::new(&symbols)).unwrap();
+        assert_eq!(0, res);
+        // eval == serialize.deserialize.eval
+        assert_eq!(
+            res,
+            (match LispExpression::deserialize((&expr as &dyn ToValue<i32>).serialize().unwrap()) {
+                Ok(v) => match v {
+                    LispExpression::NullExpression(v) =>
+                        (&v as &dyn ToValue<i32>).get_value(&Context::new(&symbols)),
+                    _ => Err(CustomError::Message(Message::ErrUnexpected)),
+                },
+                Err(_) => Err(CustomError::Message(Message::ErrUnexpected)),
+            })
+            .unwrap()
+        );
+        // serialize == serialize.deserialize.serialize
+        assert_eq!(
+            (&expr as &dyn ToValue<i32>).serialize().unwrap(),
+            match LispExpression::deserialize((&expr as &dyn ToValue<i32>).serialize().unwrap()) {
+                Ok(v) => match v {
+                    LispExpression::NullExpression(v) =>
+                        (&v as &dyn ToValue<i32>).serialize().unwrap(),
+                    _ => Value::Null,
+                },
+                Err(_) => Value::Null,
+            }
+        );
+        let eval = |text: &str| match LispExpression::parse(text)
+            .unwrap()
+            .as_text(&Context::new(&symbols))
+        {
+            Ok(v) => json!(v),
+            Err(e) => e.serialize(&Language::English),
+        };
+        assert_eq!(
+            json!("40"),
+            eval("(coalesce:Number (. discount) (. price))")
+        );
+        assert_eq!(
+            json!("Value is null"),
+            eval("(coalesce:Number (. discount) null)")
+        );
+        assert_eq!(json!("true"), eval("(exists price)"));
+        assert_eq!(json!("false"), eval("(exists discount)"));
+        assert_eq!(json!("false"), eval("(exists order id)"));
+        assert_eq!(json!("true"), eval("(is_null [Number (. discount)])"));
+        assert_eq!(json!("false"), eval("(is_null [Text (. price)])"));
+        assert_eq!(
+            json!({"/args/0/1/args/0": "Symbol not found"}),
+            eval("(is_null [Number (. order)])")
+        );
+        // Comparisons with null are null, and logical operators are three-valued
+        assert_eq!(
+            json!({"/args/0": "Value is null"}),
+            eval("(==:Number (. discount) 0)")
+        );
+        assert_eq!(
+            json!("false"),
+            eval("(and (==:Number (. discount) 0) false)")
+        );
+        assert_eq!(json!("true"), eval("(or (==:Number (. discount) 0) true)"));
+        assert_eq!(
+            json!({"/args/1/args/0": "Value is null"}),
+            eval("(and true (==:Number (. discount) 0) true)")
+        );
+        assert_eq!(
+            json!({"/args/0/args/0": "Value is null"}),
+            eval("(not (or null false))")
+        );
+        // A null guard does not hold
+        assert_eq!(
+            json!("none"),
+            eval("(if:Text (==:Number (. discount) 0) zero none)")
+        );
+        // Nulls can be bound, and passed to casts that keep them
+        assert_eq!(
+            json!("true"),
+            eval("(let:Boolean [[d Decimal (. discount)]] (is_null [Text (to_text [Decimal (. d)])]))")
+        );
+        assert_eq!(
+            json!({"/args/0": "Wrong number of arguments"}),
+            match LispExpression::parse("(is_null [Number])") {
+                Ok(_) => Value::Null,
+                Err(e) => e.serialize(&Language::English),
+            }
+        );
+        assert_eq!(
+            json!({"/args": "Wrong number of arguments"}),
+            match LispExpression::parse("(coalesce:Text)") {
+                Ok(_) => Value::Null,
+                Err(e) => e.serialize(&Language::English),
+            }
+        );
+    }
+}
@@ -0,0 +1,128 @@
+use crate::list::List;
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, Duration, FixedOffset, NaiveDate};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+// Symbols
+
+#[derive(Clone, PartialEq)]
+pub enum Leaf {
+    Number(i32),
+    Decimal(BigDecimal),
+    Text(String),
+    Boolean(bool),
+    Date(NaiveDate),
+    DateTime(DateTime<FixedOffset>),
+    Duration(Duration),
+    Null,
+    List(List), // items are symbols, so that they can hold fields as well as values
+}
+
+impl From<i32> for Leaf {
+    fn from(v: i32) -> Leaf {
+        Leaf::Number(v)
+    }
+}
+
+impl From<BigDecimal> for Leaf {
+    fn from(v: BigDecimal) -> Leaf {
+        Leaf::Decimal(v)
+    }
+}
+
+impl From<String> for Leaf {
+    fn from(v: String) -> Leaf {
+        Leaf::Text(v)
+    }
+}
+
+impl From<bool> for Leaf {
+    fn from(v: bool) -> Leaf {
+        Leaf::Boolean(v)
+    }
+}
+
+impl From<NaiveDate> for Leaf {
+    fn from(v: NaiveDate) -> Leaf {
+        Leaf::Date(v)
+    }
+}
+
+impl From<DateTime<FixedOffset>> for Leaf {
+    fn from(v: DateTime<FixedOffset>) -> Leaf {
+        Leaf::DateTime(v)
+    }
+}
+
+impl From<Duration> for Leaf {
+    fn from(v: Duration) -> Leaf {
+        Leaf::Duration(v)
+    }
+}
+
+// A symbol without a value reads as null
+#[derive(Clone, PartialEq)]
+pub struct Symbol {
+    pub value: Option<Leaf>,
+    pub values: HashMap<String, Symbol>,
+}
+
+impl Symbol {
+    // A symbol holding just a value, as local variables bound to values do
+    pub fn from_leaf(value: Leaf) -> Symbol {
+        Symbol {
+            value: Some(value),
+            values: HashMap::new(),
+        }
+    }
+
+    // Segments following a list valued symbol index its items, from 0
+    pub(crate) fn get(&self, segment: &str) -> Option<&Symbol> {
+        match &self.value {
+            Some(Leaf::List(v)) => segment.parse::<usize>().ok().and_then(|v1| v.get(v1)),
+            _ => self.values.get(segment),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LeafType {
+    Number,
+    Decimal,
+    Text,
+    Boolean,
+    Date,
+    DateTime,
+    Duration,
+}
+
+impl LeafType {
+    pub fn serialize(&self) -> Value {
+        match self {
+            LeafType::Number => json!("Number"),
+            LeafType::Decimal => json!("Decimal"),
+            LeafType::Text => json!("Text"),
+            LeafType::Boolean => json!("Boolean"),
+            LeafType::Date => json!("Date"),
+            LeafType::DateTime => json!("DateTime"),
+            LeafType::Duration => json!("Duration"),
+        }
+    }
+
+    pub fn deserialize(val: &Value) -> Option<LeafType> {
+        match val {
+            Value::String(v) => match v.as_str() {
+                "Number" => Some(LeafType::Number),
+                "Decimal" => Some(LeafType::Decimal),
+                "Text" => Some(LeafType::Text),
+                "Boolean" => Some(LeafType::Boolean),
+                "Date" => Some(LeafType::Date),
+                "DateTime" => Some(LeafType::DateTime),
+                "Duration" => Some(LeafType::Duration),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,434 @@
+// Textual syntax for lisp expressions.
+//
+// Text is read into the same JSON shape consumed by LispExpression::deserialize,
+// and printed back from the JSON produced by LispExpression::serialize.
+//
+// (+:Number 2 (. x y))           -> {"op": "+", "type": "Number", "args": [2, {"op": ".", "args": ["x", "y"]}]}
+// (match:Text:Number x [[1 "a"]] "b") -> {"op": "match", "type": ["Text", "Number"], "args": [...]}
+//
+// Lists are written with square brackets, strings are double quoted, and any other
+// atom that is not an integer, boolean or null is read as a string. Decimals such as
+// 2.50 are therefore read exactly, as the strings LispExpression serializes them to.
+// A semicolon starts a comment that runs till the end of the line.
+// Expressions and lists nest at most MAX_DEPTH levels deep.
+
+use crate::error::{CustomError, Message};
+use crate::expression::LispExpression;
+use serde_json::{json, Map, Value};
+
+const LINE_WIDTH: usize = 80;
+const INDENT: usize = 2;
+// Lists and expressions nested deeper than this are rejected, as reading and
+// deserializing them recurse once per level and would otherwise overflow the stack
+const MAX_DEPTH: usize = 256;
+
+enum Token {
+    OpenParen,
+    CloseParen,
+    OpenBracket,
+    CloseBracket,
+    Text(String),
+    Atom(String),
+}
+
+fn tokenize(text: &str) -> Result<Vec<Token>, CustomError> {
+    let mut tokens: Vec<Token> = vec![];
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '(' => tokens.push(Token::OpenParen),
+            ')' => tokens.push(Token::CloseParen),
+            '[' => tokens.push(Token::OpenBracket),
+            ']' => tokens.push(Token::CloseBracket),
+            ';' => {
+                for c1 in chars.by_ref() {
+                    if c1 == '\n' {
+                        break;
+                    }
+                }
+            }
+            '"' => {
+                let mut value = String::new();
+                let mut closed = false;
+                while let Some(c1) = chars.next() {
+                    match c1 {
+                        '"' => {
+                            closed = true;
+                            break;
+                        }
+                        '\\' => match chars.next() {
+                            Some('n') => value.push('\n'),
+                            Some('t') => value.push('\t'),
+                            Some('r') => value.push('\r'),
+                            Some('"') => value.push('"'),
+                            Some('\\') => value.push('\\'),
+                            _ => return Err(CustomError::Message(Message::ErrSyntax)),
+                        },
+                        _ => value.push(c1),
+                    }
+                }
+                match closed {
+                    true => tokens.push(Token::Text(value)),
+                    false => return Err(CustomError::Message(Message::ErrSyntax)),
+                }
+            }
+            _ if c.is_whitespace() => {}
+            _ => {
+                let mut value = c.to_string();
+                while let Some(c1) = chars.peek() {
+                    match is_delimiter(*c1) {
+                        true => break,
+                        false => {
+                            value.push(*c1);
+                            chars.next();
+                        }
+                    }
+                }
+                tokens.push(Token::Atom(value));
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+fn is_delimiter(c: char) -> bool {
+    c.is_whitespace() || matches!(c, '(' | ')' | '[' | ']' | '"' | ';')
+}
+
+fn read_atom(atom: &str) -> Value {
+    match atom {
+        "true" => Value::Bool(true),
+        "false" => Value::Bool(false),
+        "null" => Value::Null,
+        _ => match atom.parse::<i64>() {
+            Ok(v) => json!(v),
+            Err(_) => Value::String(atom.to_string()),
+        },
+    }
+}
+
+fn read_head(head: &str) -> Result<(Value, Option<Value>), CustomError> {
+    let mut parts = head.split(':');
+    let op: Value = match parts.next() {
+        Some(v) if !v.is_empty() => json!(v),
+        _ => return Err(CustomError::Message(Message::ErrSyntax)),
+    };
+    let types: Vec<&str> = parts.collect();
+    match types.iter().any(|val| val.is_empty()) {
+        true => Err(CustomError::Message(Message::ErrSyntax)),
+        false => match types.len() {
+            0 => Ok((op, None)),
+            1 => Ok((op, Some(json!(types[0])))),
+            _ => Ok((op, Some(json!(types)))),
+        },
+    }
+}
+
+fn read_until(
+    tokens: &[Token],
+    pos: &mut usize,
+    close: fn(&Token) -> bool,
+    depth: usize,
+) -> Result<Vec<Value>, CustomError> {
+    let mut values: Vec<Value> = vec![];
+    loop {
+        match tokens.get(*pos) {
+            Some(v) if close(v) => {
+                *pos += 1;
+                return Ok(values);
+            }
+            Some(_) => values.push(read(tokens, pos, depth)?),
+            None => return Err(CustomError::Message(Message::ErrSyntax)),
+        }
+    }
+}
+
+// Reads the value starting at pos, itself nested in depth lists or expressions
+fn read(tokens: &[Token], pos: &mut usize, depth: usize) -> Result<Value, CustomError> {
+    let token = match tokens.get(*pos) {
+        Some(v) => v,
+        None => return Err(CustomError::Message(Message::ErrSyntax)),
+    };
+    *pos += 1;
+    if matches!(token, Token::OpenParen | Token::OpenBracket) && depth >= MAX_DEPTH {
+        return Err(CustomError::Message(Message::ErrSyntax));
+    }
+    match token {
+        Token::OpenParen => match tokens.get(*pos) {
+            Some(Token::Atom(v)) => {
+                *pos += 1;
+                let (op, result_type) = read_head(v)?;
+                let args = read_until(
+                    tokens,
+                    pos,
+                    |val| matches!(val, Token::CloseParen),
+                    depth + 1,
+                )?;
+                let mut result: Map<String, Value> = Map::new();
+                result.insert("op".to_string(), op);
+                if let Some(v1) = result_type {
+                    result.insert("type".to_string(), v1);
+                }
+                result.insert("args".to_string(), Value::Array(args));
+                Ok(Value::Object(result))
+            }
+            _ => Err(CustomError::Message(Message::ErrSyntax)),
+        },
+        Token::OpenBracket => Ok(Value::Array(read_until(
+            tokens,
+            pos,
+            |val| matches!(val, Token::CloseBracket),
+            depth + 1,
+        )?)),
+        Token::Text(v) => Ok(Value::String(v.to_string())),
+        Token::Atom(v) => Ok(read_atom(v)),
+        Token::CloseParen | Token::CloseBracket => Err(CustomError::Message(Message::ErrSyntax)),
+    }
+}
+
+// Reads text into its JSON form, text must contain exactly one expression
+fn read_text(text: &str) -> Result<Value, CustomError> {
+    let tokens = tokenize(text)?;
+    let mut pos: usize = 0;
+    let result = read(&tokens, &mut pos, 0)?;
+    match pos == tokens.len() {
+        true => Ok(result),
+        false => Err(CustomError::Message(Message::ErrSyntax)),
+    }
+}
+
+fn print_string(value: &str) -> String {
+    let bare = !value.is_empty()
+        && !value.chars().any(is_delimiter)
+        && matches!(read_atom(value), Value::String(_));
+    match bare {
+        true => value.to_string(),
+        false => {
+            let mut result = String::from("\"");
+            for c in value.chars() {
+                match c {
+                    '"' => result.push_str("\\\""),
+                    '\\' => result.push_str("\\\\"),
+                    '\n' => result.push_str("\\n"),
+                    '\t' => result.push_str("\\t"),
+                    '\r' => result.push_str("\\r"),
+                    _ => result.push(c),
+                }
+            }
+            result.push('"');
+            result
+        }
+    }
+}
+
+fn print_head(op: &Value, result_type: Option<&Value>) -> Result<String, CustomError> {
+    let op: String = match op {
+        Value::String(v) => v.to_string(),
+        _ => return Err(CustomError::Message(Message::ErrSerialization)),
+    };
+    match result_type {
+        None => Ok(op),
+        Some(Value::String(v)) => Ok(format!("{}:{}", op, v)),
+        Some(Value::Array(v)) => {
+            let mut result = op;
+            for val in v {
+                match val {
+                    Value::String(v1) => result = format!("{}:{}", result, v1),
+                    _ => return Err(CustomError::Message(Message::ErrSerialization)),
+                }
+            }
+            Ok(result)
+        }
+        Some(_) => Err(CustomError::Message(Message::ErrSerialization)),
+    }
+}
+
+// Opening text, children and closing text of an expression or a list
+type Parts<'a> = (String, &'a Vec<Value>, &'static str);
+
+fn print_parts(value: &Value) -> Result<Option<Parts<'_>>, CustomError> {
+    match value {
+        Value::Object(v) => match (v.get("op"), v.get("args")) {
+            (Some(v1), Some(Value::Array(v2))) => Ok(Some((
+                format!("({}", print_head(v1, v.get("type"))?),
+                v2,
+                ")",
+            ))),
+            _ => Err(CustomError::Message(Message::ErrSerialization)),
+        },
+        Value::Array(v) => Ok(Some((String::from("["), v, "]"))),
+        _ => Ok(None),
+    }
+}
+
+fn print_flat(value: &Value) -> Result<String, CustomError> {
+    match print_parts(value)? {
+        Some((open, children, close)) => {
+            let mut result = open;
+            for (index, val) in children.iter().enumerate() {
+                if index != 0 || close == ")" {
+                    result.push(' ');
+                }
+                result.push_str(&print_flat(val)?);
+            }
+            result.push_str(close);
+            Ok(result)
+        }
+        None => match value {
+            Value::String(v) => Ok(print_string(v)),
+            Value::Number(v) => Ok(v.to_string()),
+            Value::Bool(v) => Ok(v.to_string()),
+            Value::Null => Ok(String::from("null")),
+            _ => Err(CustomError::Message(Message::ErrSerialization)),
+        },
+    }
+}
+
+fn print_pretty(value: &Value, indent: usize) -> Result<String, CustomError> {
+    let flat = print_flat(value)?;
+    match indent + flat.len() <= LINE_WIDTH {
+        true => Ok(flat),
+        false => match print_parts(value)? {
+            Some((open, children, close)) if !children.is_empty() => {
+                // list items align under the first item, expression args are indented
+                let child_indent: usize = match close {
+                    ")" => indent + INDENT,
+                    _ => indent + 1,
+                };
+                let mut result = open;
+                for (index, val) in children.iter().enumerate() {
+                    if index != 0 || close == ")" {
+                        result.push('\n');
+                        result.push_str(&" ".repeat(child_indent));
+                    }
+                    result.push_str(&print_pretty(val, child_indent)?);
+                }
+                result.push_str(close);
+                Ok(result)
+            }
+            _ => Ok(flat),
+        },
+    }
+}
+
+impl LispExpression {
+    pub fn parse(text: &str) -> Result<LispExpression, CustomError> {
+        Self::deserialize(read_text(text)?)
+    }
+
+    pub fn to_text(&self) -> Result<String, CustomError> {
+        print_pretty(&self.serialize()?, 0)
+    }
+}
+
+#[cfg(test)]
+mod syntax_tests {
+    use std::collections::HashMap;
+    use std::str::FromStr;
+
+    use super::*;
+    use crate::{Context, Language, Symbol};
+    use bigdecimal::BigDecimal;
+
+    #[test]
+    fn test_read_text() {
+        assert_eq!(
+            json!({
+                "op": "+",
+                "type": "Number",
+                "args": [2, {"op": ".", "args": ["x", "y"]}]
+            }),
+            read_text("(+:Number 2 (. x y))").unwrap()
+        );
+        assert_eq!(
+            json!({
+                "op": "match",
+                "type": ["Text", "Number"],
+                "args": [1, [[1, "one"], [2, "two words"]], "many"]
+            }),
+            read_text(
+                "; match on a number
+                (match:Text:Number 1
+                  [[1 one] [2 \"two words\"]] ; guards
+                  many)"
+            )
+            .unwrap()
+        );
+        assert_eq!(
+            json!(["-", -3, "2.50", true, "\"a\""]),
+            read_text("[- -3 2.50 true \"\\\"a\\\"\"]").unwrap()
+        );
+        assert!(read_text("(+:Number 2").is_err());
+        assert!(read_text("(+:Number 2))").is_err());
+        assert!(read_text("(+: 2)").is_err());
+        assert!(read_text("(\"+\" 2)").is_err());
+        assert!(read_text("\"abc").is_err());
+        assert!(read_text("").is_err());
+        let nested = |depth: usize| format!("{}true{}", "(not ".repeat(depth), ")".repeat(depth));
+        assert!(read_text(&nested(MAX_DEPTH)).is_ok());
+        assert!(read_text(&nested(MAX_DEPTH + 1)).is_err());
+        assert!(read_text(&format!("{}{}", "[".repeat(5000), "]".repeat(5000))).is_err());
+    }
+
+    #[test]
+    fn test_print_text() {
+        for text in [
+            "(+:Number 2 (. x y))",
+            "(match:Text:Number 1 [[1 one] [2 \"two words\"]] \"3\")",
+            "(and true (not false) (==:Text \"true\" \"\"))",
+        ] {
+            assert_eq!(text, print_flat(&read_text(text).unwrap()).unwrap());
+        }
+        let text =
+            "(+:Decimal (. order total) (. order shipping) (. order insurance) (. order taxes))";
+        assert_eq!(
+            "(+:Decimal\n  (. order total)\n  (. order shipping)\n  (. order insurance)\n  (. order taxes))",
+            print_pretty(&read_text(text).unwrap(), 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parse() {
+        let symbols: HashMap<String, Symbol> = HashMap::new();
+        let text = "(+:Number 2 (*:Number 3 (. x)) (%:Number 7 4))";
+        let expr = LispExpression::parse(text).unwrap();
+        // text == parse.to_text
+        assert_eq!(text, expr.to_text().unwrap());
+        let expr = LispExpression::parse("(+:Decimal 2 (*:Number 3 4) 1.5)").unwrap();
+        assert_eq!(
+            BigDecimal::from_str("15.5").unwrap(),
+            expr.as_decimal(&Context::new(&symbols)).unwrap()
+        );
+        // serialize == serialize.to_text.parse.serialize
+        assert_eq!(
+            expr.serialize().unwrap(),
+            LispExpression::parse(&expr.to_text().unwrap())
+                .unwrap()
+                .serialize()
+                .unwrap()
+        );
+        let text = "(+:Decimal 0.1 2 123.4500 -98765432109876543210.000000000000000001)";
+        let expr = LispExpression::parse(text).unwrap();
+        assert_eq!(text, expr.to_text().unwrap());
+        assert_eq!(
+            BigDecimal::from_str("-98765432109876543084.450000000000000001").unwrap(),
+            expr.as_decimal(&Context::new(&symbols)).unwrap()
+        );
+        let text = "(match:Boolean:Text (. x) [[a true] [b false]] false)";
+        let expr = LispExpression::parse(text).unwrap();
+        assert_eq!(text, expr.to_text().unwrap());
+        assert!(LispExpression::parse("(+:Text 2 3)").is_err());
+        assert!(LispExpression::parse("(unknown 2 3)").is_err());
+        // nesting is bounded before deserialize recurses into it
+        let nested = |depth: usize| format!("{}true{}", "(not ".repeat(depth), ")".repeat(depth));
+        let expr = LispExpression::parse(&nested(MAX_DEPTH)).unwrap();
+        assert!(expr.as_boolean(&Context::new(&symbols)).unwrap());
+        assert_eq!(
+            json!("Invalid syntax"),
+            match LispExpression::parse(&nested(3000)) {
+                Ok(_) => Value::Null,
+                Err(e) => e.serialize(&Language::English),
+            }
+        );
+    }
+}
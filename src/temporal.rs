@@ -0,0 +1,673 @@
+use crate::binding::Binding;
+use crate::collection::CollectionExpression;
+use crate::comparator::ComparatorOperator;
+use crate::context::Context;
+use crate::dot::DotExpression;
+use crate::error::{CustomError, Message};
+use crate::function::CallExpression;
+use crate::list::ListExpression;
+use crate::matching::Matchable;
+use crate::null::NullExpression;
+use crate::symbol::Leaf;
+use crate::value::{eval_arg, ToValue};
+use bigdecimal::{BigDecimal, ToPrimitive};
+use chrono::{DateTime, Datelike, Duration, FixedOffset, Months, NaiveDate};
+use serde_json::{json, Value};
+use std::str::FromStr;
+
+// Temporal values, written in their ISO 8601 forms.
+// Date times carry an explicit UTC offset, as in 2021-08-01T09:30:00+05:30.
+// Durations count weeks, days and clock time only, as in -P1DT2H30M, since months vary in length.
+pub trait Temporal: Matchable {
+    fn from_leaf(leaf: Leaf) -> Result<Self, CustomError>;
+    fn parse(text: &str) -> Option<Self>;
+    fn to_text(&self) -> String;
+}
+
+impl Temporal for NaiveDate {
+    fn from_leaf(leaf: Leaf) -> Result<NaiveDate, CustomError> {
+        match leaf {
+            Leaf::Date(v) => Ok(v),
+            Leaf::Null => Err(CustomError::Message(Message::ErrNull)),
+            _ => Err(CustomError::Message(Message::ErrTypeMismatch)),
+        }
+    }
+
+    fn parse(text: &str) -> Option<NaiveDate> {
+        NaiveDate::parse_from_str(text, "%Y-%m-%d").ok()
+    }
+
+    fn to_text(&self) -> String {
+        self.format("%Y-%m-%d").to_string()
+    }
+}
+
+impl Temporal for DateTime<FixedOffset> {
+    fn from_leaf(leaf: Leaf) -> Result<DateTime<FixedOffset>, CustomError> {
+        match leaf {
+            Leaf::DateTime(v) => Ok(v),
+            Leaf::Null => Err(CustomError::Message(Message::ErrNull)),
+            _ => Err(CustomError::Message(Message::ErrTypeMismatch)),
+        }
+    }
+
+    fn parse(text: &str) -> Option<DateTime<FixedOffset>> {
+        DateTime::parse_from_rfc3339(text).ok()
+    }
+
+    fn to_text(&self) -> String {
+        self.to_rfc3339()
+    }
+}
+
+impl Temporal for Duration {
+    fn from_leaf(leaf: Leaf) -> Result<Duration, CustomError> {
+        match leaf {
+            Leaf::Duration(v) => Ok(v),
+            Leaf::Null => Err(CustomError::Message(Message::ErrNull)),
+            _ => Err(CustomError::Message(Message::ErrTypeMismatch)),
+        }
+    }
+
+    // Components are whole numbers, except seconds which may have a fraction, as in PT0.5S
+    fn parse(text: &str) -> Option<Duration> {
+        let (negative, text) = match text.strip_prefix('-') {
+            Some(v) => (true, v),
+            None => (false, text),
+        };
+        let text = text.strip_prefix('P')?;
+        let (date, time) = match text.split_once('T') {
+            Some((_, "")) => return None,
+            Some((v, v1)) => (v, v1),
+            None => (text, ""),
+        };
+        if date.is_empty() && time.is_empty() {
+            return None;
+        }
+        let mut result = Duration::zero();
+        for (part, units) in [(date, "WD"), (time, "HMS")] {
+            let mut units = units.chars();
+            let mut rest = part;
+            while !rest.is_empty() {
+                let end = rest.find(|c: char| c.is_ascii_alphabetic())?;
+                let (number, unit) = (&rest[..end], rest[end..].chars().next()?);
+                // units appear at most once, in order
+                units.by_ref().find(|v| *v == unit)?;
+                if number.is_empty() || !number.chars().all(|c| c.is_ascii_digit() || c == '.') {
+                    return None;
+                }
+                let v = BigDecimal::from_str(number).ok()?;
+                if unit != 'S' && !v.is_integer() {
+                    return None;
+                }
+                let seconds = v * BigDecimal::from(match unit {
+                    'W' => 604800,
+                    'D' => 86400,
+                    'H' => 3600,
+                    'M' => 60,
+                    _ => 1,
+                });
+                let whole = seconds.with_scale(0);
+                let nanos = ((seconds - &whole) * BigDecimal::from(1_000_000_000)).with_scale(0);
+                result = result
+                    .checked_add(&Duration::try_seconds(whole.to_i64()?)?)?
+                    .checked_add(&Duration::nanoseconds(nanos.to_i64()?))?;
+                rest = &rest[end + 1..];
+            }
+        }
+        match negative {
+            true => Some(-result),
+            false => Some(result),
+        }
+    }
+
+    fn to_text(&self) -> String {
+        let v = self.abs();
+        let seconds = v.num_seconds();
+        let mut date = String::new();
+        let mut time = String::new();
+        if seconds >= 86400 {
+            date.push_str(&format!("{}D", seconds / 86400));
+        }
+        if seconds % 86400 >= 3600 {
+            time.push_str(&format!("{}H", seconds % 86400 / 3600));
+        }
+        if seconds % 3600 >= 60 {
+            time.push_str(&format!("{}M", seconds % 3600 / 60));
+        }
+        match v.subsec_nanos() {
+            0 if seconds % 60 == 0 => {}
+            0 => time.push_str(&format!("{}S", seconds % 60)),
+            v1 => {
+                let fraction = format!("{:09}", v1);
+                time.push_str(&format!(
+                    "{}.{}S",
+                    seconds % 60,
+                    fraction.trim_end_matches('0')
+                ));
+            }
+        }
+        if !time.is_empty() {
+            time.insert(0, 'T');
+        }
+        match (date.is_empty() && time.is_empty(), *self < Duration::zero()) {
+            (true, _) => "PT0S".to_string(),
+            (false, true) => format!("-P{}{}", date, time),
+            (false, false) => format!("P{}{}", date, time),
+        }
+    }
+}
+
+// Temporal literals serialize as ISO 8601 texts
+impl<T: Temporal> ToValue<T> for T {
+    fn get_value(&self, _context: &Context) -> Result<T, CustomError> {
+        Ok(self.clone())
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        Ok(json!(self.to_text()))
+    }
+}
+
+// TEMPORAL OPS
+
+// {"op": "add_days", "args": [date, days]}, and likewise add_months, clamped to the end of the month
+// {"op": "diff_days", "args": [from, to]}, the days from one date to the other
+// {"op": "start_of_month", "args": [date]}, and likewise end_of_month
+// {"op": "weekday", "args": [date]}, from 1 for Monday to 7 for Sunday
+// {"op": "age", "args": [birth, date]}, the whole years from birth until the date
+// {"op": "add_duration", "args": [date_time, duration]}
+// {"op": "duration_between", "args": [from, to]}, date times
+pub enum TemporalExpression {
+    AddDays {
+        date: Box<dyn ToValue<NaiveDate>>,
+        days: Box<dyn ToValue<i32>>,
+    },
+    AddMonths {
+        date: Box<dyn ToValue<NaiveDate>>,
+        months: Box<dyn ToValue<i32>>,
+    },
+    DiffDays {
+        from: Box<dyn ToValue<NaiveDate>>,
+        to: Box<dyn ToValue<NaiveDate>>,
+    },
+    StartOfMonth(Box<dyn ToValue<NaiveDate>>),
+    EndOfMonth(Box<dyn ToValue<NaiveDate>>),
+    Weekday(Box<dyn ToValue<NaiveDate>>),
+    Age {
+        birth: Box<dyn ToValue<NaiveDate>>,
+        date: Box<dyn ToValue<NaiveDate>>,
+    },
+    AddDuration {
+        date_time: Box<dyn ToValue<DateTime<FixedOffset>>>,
+        duration: Box<dyn ToValue<Duration>>,
+    },
+    DurationBetween {
+        from: Box<dyn ToValue<DateTime<FixedOffset>>>,
+        to: Box<dyn ToValue<DateTime<FixedOffset>>>,
+    },
+}
+
+impl TemporalExpression {
+    // Dates outside the supported range, from year -262143 to 262142, are out of range
+    pub(crate) fn eval(&self, context: &Context) -> Result<Leaf, CustomError> {
+        let out_of_range = |pointer: &str| CustomError::Message(Message::ErrOutOfRange).at(pointer);
+        match self {
+            TemporalExpression::AddDays { date, days } => {
+                let date = eval_arg(date.as_ref(), 0, context)?;
+                let days = eval_arg(days.as_ref(), 1, context)?;
+                match date.checked_add_signed(Duration::days(days as i64)) {
+                    Some(v) => Ok(Leaf::Date(v)),
+                    None => Err(out_of_range("/args/1")),
+                }
+            }
+            TemporalExpression::AddMonths { date, months } => {
+                let date = eval_arg(date.as_ref(), 0, context)?;
+                let months = eval_arg(months.as_ref(), 1, context)?;
+                let result = match months < 0 {
+                    true => date.checked_sub_months(Months::new(months.unsigned_abs())),
+                    false => date.checked_add_months(Months::new(months.unsigned_abs())),
+                };
+                match result {
+                    Some(v) => Ok(Leaf::Date(v)),
+                    None => Err(out_of_range("/args/1")),
+                }
+            }
+            TemporalExpression::DiffDays { from, to } => {
+                let from = eval_arg(from.as_ref(), 0, context)?;
+                let to = eval_arg(to.as_ref(), 1, context)?;
+                match to.signed_duration_since(from).num_days().to_i32() {
+                    Some(v) => Ok(Leaf::Number(v)),
+                    None => Err(CustomError::Message(Message::ErrOverflow)),
+                }
+            }
+            TemporalExpression::StartOfMonth(date) => {
+                let date = eval_arg(date.as_ref(), 0, context)?;
+                match date.with_day(1) {
+                    Some(v) => Ok(Leaf::Date(v)),
+                    None => Err(CustomError::Message(Message::ErrUnexpected)),
+                }
+            }
+            TemporalExpression::EndOfMonth(date) => {
+                let date = eval_arg(date.as_ref(), 0, context)?;
+                match date
+                    .with_day(1)
+                    .and_then(|v| v.checked_add_months(Months::new(1)))
+                    .and_then(|v| v.pred_opt())
+                {
+                    Some(v) => Ok(Leaf::Date(v)),
+                    None => Err(out_of_range("/args/0")),
+                }
+            }
+            TemporalExpression::Weekday(date) => {
+                let date = eval_arg(date.as_ref(), 0, context)?;
+                Ok(Leaf::Number(date.weekday().number_from_monday() as i32))
+            }
+            // Birthdays on 29 February fall on 1 March in common years
+            TemporalExpression::Age { birth, date } => {
+                let birth = eval_arg(birth.as_ref(), 0, context)?;
+                let date = eval_arg(date.as_ref(), 1, context)?;
+                if date < birth {
+                    return Err(out_of_range("/args/1"));
+                }
+                let mut years = date.year() - birth.year();
+                if (date.month(), date.day()) < (birth.month(), birth.day()) {
+                    years -= 1;
+                }
+                Ok(Leaf::Number(years))
+            }
+            TemporalExpression::AddDuration {
+                date_time,
+                duration,
+            } => {
+                let date_time = eval_arg(date_time.as_ref(), 0, context)?;
+                let duration = eval_arg(duration.as_ref(), 1, context)?;
+                match date_time.checked_add_signed(duration) {
+                    Some(v) => Ok(Leaf::DateTime(v)),
+                    None => Err(out_of_range("/args/1")),
+                }
+            }
+            TemporalExpression::DurationBetween { from, to } => {
+                let from = eval_arg(from.as_ref(), 0, context)?;
+                let to = eval_arg(to.as_ref(), 1, context)?;
+                Ok(Leaf::Duration(to.signed_duration_since(from)))
+            }
+        }
+    }
+
+    pub(crate) fn serialize(&self) -> Result<Value, CustomError> {
+        let (op, args): (&str, Vec<Value>) = match self {
+            TemporalExpression::AddDays { date, days } => {
+                ("add_days", vec![date.serialize()?, days.serialize()?])
+            }
+            TemporalExpression::AddMonths { date, months } => {
+                ("add_months", vec![date.serialize()?, months.serialize()?])
+            }
+            TemporalExpression::DiffDays { from, to } => {
+                ("diff_days", vec![from.serialize()?, to.serialize()?])
+            }
+            TemporalExpression::StartOfMonth(date) => ("start_of_month", vec![date.serialize()?]),
+            TemporalExpression::EndOfMonth(date) => ("end_of_month", vec![date.serialize()?]),
+            TemporalExpression::Weekday(date) => ("weekday", vec![date.serialize()?]),
+            TemporalExpression::Age { birth, date } => {
+                ("age", vec![birth.serialize()?, date.serialize()?])
+            }
+            TemporalExpression::AddDuration {
+                date_time,
+                duration,
+            } => (
+                "add_duration",
+                vec![date_time.serialize()?, duration.serialize()?],
+            ),
+            TemporalExpression::DurationBetween { from, to } => {
+                ("duration_between", vec![from.serialize()?, to.serialize()?])
+            }
+        };
+        Ok(json!({
+            "op": op,
+            "args": args
+        }))
+    }
+}
+
+impl ToValue<i32> for TemporalExpression {
+    fn get_value(&self, context: &Context) -> Result<i32, CustomError> {
+        match self.eval(context)? {
+            Leaf::Number(v) => Ok(v),
+            _ => Err(CustomError::Message(Message::ErrTypeMismatch)),
+        }
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        self.serialize()
+    }
+}
+
+impl ToValue<BigDecimal> for TemporalExpression {
+    fn get_value(&self, context: &Context) -> Result<BigDecimal, CustomError> {
+        match self.eval(context)? {
+            Leaf::Number(v) => Ok(BigDecimal::from(v)),
+            _ => Err(CustomError::Message(Message::ErrTypeMismatch)),
+        }
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        self.serialize()
+    }
+}
+
+impl ToValue<String> for TemporalExpression {
+    fn get_value(&self, context: &Context) -> Result<String, CustomError> {
+        match self.eval(context)? {
+            Leaf::Number(v) => Ok(v.to_string()),
+            Leaf::Date(v) => Ok(v.to_text()),
+            Leaf::DateTime(v) => Ok(v.to_text()),
+            Leaf::Duration(v) => Ok(v.to_text()),
+            _ => Err(CustomError::Message(Message::ErrTypeMismatch)),
+        }
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        self.serialize()
+    }
+}
+
+// Temporal values of the expressions evaluating to leaves
+impl<T: Temporal> ToValue<T> for TemporalExpression {
+    fn get_value(&self, context: &Context) -> Result<T, CustomError> {
+        T::from_leaf(self.eval(context)?)
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        self.serialize()
+    }
+}
+
+impl<T: Temporal> ToValue<T> for CallExpression {
+    fn get_value(&self, context: &Context) -> Result<T, CustomError> {
+        T::from_leaf(self.eval(context)?)
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        self.serialize()
+    }
+}
+
+impl<T: Temporal> ToValue<T> for NullExpression {
+    fn get_value(&self, context: &Context) -> Result<T, CustomError> {
+        T::from_leaf(self.eval(context)?)
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        self.serialize()
+    }
+}
+
+impl<T: Temporal> ToValue<T> for ListExpression {
+    fn get_value(&self, context: &Context) -> Result<T, CustomError> {
+        T::from_leaf(self.eval(context)?)
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        self.serialize()
+    }
+}
+
+impl<T: Temporal> ToValue<T> for CollectionExpression {
+    fn get_value(&self, context: &Context) -> Result<T, CustomError> {
+        T::from_leaf(self.eval(context)?)
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        self.serialize()
+    }
+}
+
+impl<T: Temporal> ToValue<T> for DotExpression {
+    fn get_value(&self, context: &Context) -> Result<T, CustomError> {
+        T::from_leaf(self.resolve(context)?)
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        self.serialize()
+    }
+}
+
+// TEMPORAL COMPARATOR
+
+// Compares dates, date times or durations, every operand being of the declared type.
+// Chained operands have to hold pairwise, as in (<:Date a b c) for a < b < c.
+// Date times compare as instants, whatever their offsets.
+pub struct TemporalComparatorExpression {
+    pub(crate) operator: ComparatorOperator,
+    pub(crate) args: Vec<Binding>,
+}
+
+impl TemporalComparatorExpression {
+    pub(crate) fn eval(&self, context: &Context) -> Result<bool, CustomError> {
+        let values = self
+            .args
+            .iter()
+            .enumerate()
+            .map(|(index, val)| {
+                match val.eval(context) {
+                    Ok(Leaf::Null) => Err(CustomError::Message(Message::ErrNull)),
+                    v => v,
+                }
+                .map_err(|e| e.at(&format!("/args/{}", index)))
+            })
+            .collect::<Result<Vec<Leaf>, CustomError>>()?;
+        for pair in values.windows(2) {
+            let ordering = match (&pair[0], &pair[1]) {
+                (Leaf::Date(v), Leaf::Date(v1)) => v.cmp(v1),
+                (Leaf::DateTime(v), Leaf::DateTime(v1)) => v.cmp(v1),
+                (Leaf::Duration(v), Leaf::Duration(v1)) => v.cmp(v1),
+                _ => return Err(CustomError::Message(Message::ErrTypeMismatch)),
+            };
+            if !self.operator.holds(ordering) {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    pub(crate) fn serialize(&self) -> Result<Value, CustomError> {
+        let value_type = match self.args.first() {
+            Some(v) => v.leaf_type(),
+            None => return Err(CustomError::Message(Message::ErrWrongArity)),
+        };
+        Ok(json!({
+            "op": self.operator.name(),
+            "type": value_type.serialize(),
+            "args": self
+                .args
+                .iter()
+                .map(|val| Ok(val.serialize()?.1))
+                .collect::<Result<Vec<Value>, CustomError>>()?
+        }))
+    }
+}
+
+impl ToValue<String> for TemporalComparatorExpression {
+    fn get_value(&self, context: &Context) -> Result<String, CustomError> {
+        Ok(self.eval(context)?.to_string())
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        self.serialize()
+    }
+}
+
+impl ToValue<bool> for TemporalComparatorExpression {
+    fn get_value(&self, context: &Context) -> Result<bool, CustomError> {
+        self.eval(context)
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        self.serialize()
+    }
+}
+
+#[cfg(test)]
+mod temporal_tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::error::Language;
+    use crate::expression::LispExpression;
+    use crate::symbol::Symbol;
+
+    #[test]
+    fn test_temporal_expression() {
+        let symbols: HashMap<String, Symbol> = vec![
+            (
+                "born".to_string(),
+                Symbol {
+                    value: Some(Leaf::Date(NaiveDate::from_ymd_opt(2000, 2, 29).unwrap())),
+                    values: HashMap::new(),
+                },
+            ),
+            (
+                "opened".to_string(),
+                Symbol {
+                    value: Some(Leaf::DateTime(
+                        DateTime::parse("2024-03-30T23:00:00+05:30").unwrap(),
+                    )),
+                    values: HashMap::new(),
+                },
+            ),
+            (
+                "grace".to_string(),
+                Symbol {
+                    value: Some(Leaf::Duration(Duration::minutes(90))),
+                    values: HashMap::new(),
+                },
+            ),
+        ]
+        .into_iter()
+        .collect();
+        let expr = TemporalExpression::DiffDays {
+            from: Box::new(DotExpression {
+                path: vec!["born".to_string()],
+            }),
+            to: Box::new(NaiveDate::from_ymd_opt(2000, 3, 31).unwrap()),
+        };
+        let res: i32 = expr.get_value(&Context::new(&symbols)).unwrap();
+        assert_eq!(31, res);
+        // eval == serialize.deserialize.eval
+        assert_eq!(
+            res,
+            (match LispExpression::deserialize((&expr as &dyn ToValue<i32>).serialize().unwrap()) {
+                Ok(v) => match v {
+                    LispExpression::TemporalExpression(v) =>
+                        (&v as &dyn ToValue<i32>).get_value(&Context::new(&symbols)),
+                    _ => Err(CustomError::Message(Message::ErrUnexpected)),
+                },
+                Err(_) => Err(CustomError::Message(Message::ErrUnexpected)),
+            })
+            .unwrap()
+        );
+        // serialize == serialize.deserialize.serialize
+        assert_eq!(
+            (&expr as &dyn ToValue<i32>).serialize().unwrap(),
+            match LispExpression::deserialize((&expr as &dyn ToValue<i32>).serialize().unwrap()) {
+                Ok(v) => match v {
+                    LispExpression::TemporalExpression(v) =>
+                        (&v as &dyn ToValue<i32>).serialize().unwrap(),
+                    _ => Value::Null,
+                },
+                Err(_) => Value::Null,
+            }
+        );
+        let eval = |text: &str| match LispExpression::parse(text)
+            .unwrap()
+            .as_text(&Context::new(&symbols))
+        {
+            Ok(v) => json!(v),
+            Err(e) => e.serialize(&Language::English),
+        };
+        assert_eq!(json!("2024-03-01"), eval("(add_days 2024-02-28 2)"));
+        assert_eq!(json!("2023-12-31"), eval("(add_days 2024-01-01 -1)"));
+        // Month arithmetic clamps to the last day of the month
+        assert_eq!(json!("2024-02-29"), eval("(add_months 2024-01-31 1)"));
+        assert_eq!(json!("2023-02-28"), eval("(add_months 2024-02-29 -12)"));
+        assert_eq!(json!("-365"), eval("(diff_days 2024-01-01 2023-01-01)"));
+        assert_eq!(json!("2024-02-01"), eval("(start_of_month 2024-02-17)"));
+        assert_eq!(json!("2024-02-29"), eval("(end_of_month 2024-02-17)"));
+        assert_eq!(json!("2023-02-28"), eval("(end_of_month 2023-02-01)"));
+        assert_eq!(json!("1"), eval("(weekday 2024-01-01)"));
+        assert_eq!(json!("7"), eval("(weekday 2024-01-07)"));
+        assert_eq!(json!("23"), eval("(age (. born) 2024-02-28)"));
+        assert_eq!(json!("24"), eval("(age (. born) 2024-02-29)"));
+        assert_eq!(json!("22"), eval("(age (. born) 2023-02-28)"));
+        assert_eq!(json!("23"), eval("(age (. born) 2023-03-01)"));
+        assert_eq!(
+            json!("2024-03-31T00:30:00+05:30"),
+            eval("(add_duration (. opened) (. grace))")
+        );
+        assert_eq!(
+            json!("2024-03-29T21:30:00+05:30"),
+            eval("(add_duration (. opened) -P1DT1H30M)")
+        );
+        assert_eq!(
+            json!("PT5H30M"),
+            eval("(duration_between 2024-01-01T00:00:00+05:30 2024-01-01T00:00:00+00:00)")
+        );
+        assert_eq!(
+            json!("-P1DT0.5S"),
+            eval("(duration_between 2024-01-02T00:00:00.5+00:00 2024-01-01T00:00:00+00:00)")
+        );
+        // Date times are compared as instants, whatever their offsets
+        assert_eq!(
+            json!("true"),
+            eval("(==:DateTime 2024-01-01T05:30:00+05:30 2024-01-01T00:00:00+00:00)")
+        );
+        assert_eq!(
+            json!("false"),
+            eval("(<:Date 2023-12-31 (. born) 2024-01-01)")
+        );
+        assert_eq!(
+            json!("true"),
+            eval("(<:Date (. born) 2023-12-31 2024-01-01)")
+        );
+        assert_eq!(
+            json!("false"),
+            eval("(<:Date (. born) 2024-01-01 2024-01-01)")
+        );
+        assert_eq!(json!("true"), eval("(>=:Duration P1D PT24H (. grace))"));
+        assert_eq!(json!("2000-02-29"), eval("(to_text [Date (. born)])"));
+        assert_eq!(
+            json!({"/args/1": "Out of range"}),
+            eval("(add_days 2024-01-01 2147483647)")
+        );
+        assert_eq!(
+            json!({"/args/1": "Out of range"}),
+            eval("(age (. born) 1999-12-31)")
+        );
+        assert_eq!(
+            json!({"/args/0": "Type mismatch"}),
+            eval("(to_number [Date 2024-01-01])")
+        );
+        assert_eq!(
+            json!({"/args/1": "Invalid literal"}),
+            match LispExpression::parse("(diff_days 2024-01-01 2024-02-30)") {
+                Ok(_) => Value::Null,
+                Err(e) => e.serialize(&Language::English),
+            }
+        );
+        assert_eq!(
+            json!({"/args/1": "Invalid literal"}),
+            match LispExpression::parse("(<:Duration P1D 1D)") {
+                Ok(_) => Value::Null,
+                Err(e) => e.serialize(&Language::English),
+            }
+        );
+        assert_eq!(
+            json!({"/args": "Wrong number of arguments"}),
+            match LispExpression::parse("(weekday 2024-01-01 2024-01-02)") {
+                Ok(_) => Value::Null,
+                Err(e) => e.serialize(&Language::English),
+            }
+        );
+    }
+}
@@ -0,0 +1,744 @@
+use crate::context::Context;
+use crate::error::{CustomError, Message};
+use crate::value::{eval_arg, ToValue};
+use bigdecimal::{BigDecimal, ToPrimitive};
+use regex::Regex;
+use serde_json::{json, Value};
+use std::cell::RefCell;
+
+// TEXT OPS
+
+// Padding wider than this many characters is out of range, rather than allocated
+const MAX_WIDTH: usize = 65536;
+
+pub enum TextResult {
+    Number(i32),
+    Text(String),
+    Boolean(bool),
+}
+
+// Texts are indexed and measured in characters, rather than bytes
+pub enum TextExpression {
+    Concat(Vec<Box<dyn ToValue<String>>>),
+    Length(Box<dyn ToValue<String>>),
+    Substring {
+        text: Box<dyn ToValue<String>>,
+        start: Box<dyn ToValue<i32>>,
+        length: Option<Box<dyn ToValue<i32>>>, // till the end when absent
+    },
+    Upper(Box<dyn ToValue<String>>),
+    Lower(Box<dyn ToValue<String>>),
+    Trim(Box<dyn ToValue<String>>),
+    StartsWith {
+        text: Box<dyn ToValue<String>>,
+        part: Box<dyn ToValue<String>>,
+    },
+    EndsWith {
+        text: Box<dyn ToValue<String>>,
+        part: Box<dyn ToValue<String>>,
+    },
+    Contains {
+        text: Box<dyn ToValue<String>>,
+        part: Box<dyn ToValue<String>>,
+    },
+    Replace {
+        text: Box<dyn ToValue<String>>,
+        from: Box<dyn ToValue<String>>, // every occurrence is replaced
+        to: Box<dyn ToValue<String>>,
+    },
+    Split {
+        text: Box<dyn ToValue<String>>,
+        separator: Box<dyn ToValue<String>>,
+        index: Box<dyn ToValue<i32>>, // of the part to evaluate to
+    },
+    Join {
+        separator: Box<dyn ToValue<String>>,
+        texts: Vec<Box<dyn ToValue<String>>>,
+    },
+    PadStart {
+        text: Box<dyn ToValue<String>>,
+        width: Box<dyn ToValue<i32>>,
+        padding: Option<Box<dyn ToValue<String>>>, // a space when absent
+    },
+    PadEnd {
+        text: Box<dyn ToValue<String>>,
+        width: Box<dyn ToValue<i32>>,
+        padding: Option<Box<dyn ToValue<String>>>,
+    },
+}
+
+impl TextExpression {
+    pub(crate) fn eval(&self, context: &Context) -> Result<TextResult, CustomError> {
+        let err = |message: Message, index: usize| {
+            CustomError::Message(message).at(&format!("/args/{}", index))
+        };
+        // counts that do not fit usize are out of range
+        let count = |value: &dyn ToValue<i32>, index: usize| match eval_arg(value, index, context)?
+            .to_usize()
+        {
+            Some(v) => Ok(v),
+            None => Err(err(Message::ErrOutOfRange, index)),
+        };
+        match self {
+            TextExpression::Concat(v) => {
+                let mut result = String::new();
+                for (index, val) in v.iter().enumerate() {
+                    result.push_str(&eval_arg(val.as_ref(), index, context)?);
+                }
+                Ok(TextResult::Text(result))
+            }
+            TextExpression::Length(v) => {
+                match eval_arg(v.as_ref(), 0, context)?.chars().count().to_i32() {
+                    Some(v1) => Ok(TextResult::Number(v1)),
+                    None => Err(CustomError::Message(Message::ErrOverflow)),
+                }
+            }
+            TextExpression::Substring {
+                text,
+                start,
+                length,
+            } => {
+                let text = eval_arg(text.as_ref(), 0, context)?;
+                let start = count(start.as_ref(), 1)?;
+                let length = match length {
+                    Some(v) => count(v.as_ref(), 2)?,
+                    None => usize::MAX,
+                };
+                Ok(TextResult::Text(
+                    text.chars().skip(start).take(length).collect(),
+                ))
+            }
+            TextExpression::Upper(v) => Ok(TextResult::Text(
+                eval_arg(v.as_ref(), 0, context)?.to_uppercase(),
+            )),
+            TextExpression::Lower(v) => Ok(TextResult::Text(
+                eval_arg(v.as_ref(), 0, context)?.to_lowercase(),
+            )),
+            TextExpression::Trim(v) => Ok(TextResult::Text(
+                eval_arg(v.as_ref(), 0, context)?.trim().to_string(),
+            )),
+            TextExpression::StartsWith { text, part } => Ok(TextResult::Boolean(
+                eval_arg(text.as_ref(), 0, context)?.starts_with(&eval_arg(
+                    part.as_ref(),
+                    1,
+                    context,
+                )?),
+            )),
+            TextExpression::EndsWith { text, part } => Ok(TextResult::Boolean(
+                eval_arg(text.as_ref(), 0, context)?.ends_with(&eval_arg(
+                    part.as_ref(),
+                    1,
+                    context,
+                )?),
+            )),
+            TextExpression::Contains { text, part } => Ok(TextResult::Boolean(
+                eval_arg(text.as_ref(), 0, context)?.contains(&eval_arg(
+                    part.as_ref(),
+                    1,
+                    context,
+                )?),
+            )),
+            TextExpression::Replace { text, from, to } => {
+                let text = eval_arg(text.as_ref(), 0, context)?;
+                let from = eval_arg(from.as_ref(), 1, context)?;
+                if from.is_empty() {
+                    return Err(err(Message::ErrInvalidArgument, 1));
+                }
+                let to = eval_arg(to.as_ref(), 2, context)?;
+                Ok(TextResult::Text(text.replace(&from, &to)))
+            }
+            TextExpression::Split {
+                text,
+                separator,
+                index,
+            } => {
+                let text = eval_arg(text.as_ref(), 0, context)?;
+                let separator = eval_arg(separator.as_ref(), 1, context)?;
+                if separator.is_empty() {
+                    return Err(err(Message::ErrInvalidArgument, 1));
+                }
+                match text.split(&separator).nth(count(index.as_ref(), 2)?) {
+                    Some(v) => Ok(TextResult::Text(v.to_string())),
+                    None => Err(err(Message::ErrOutOfRange, 2)),
+                }
+            }
+            TextExpression::Join { separator, texts } => {
+                let separator = eval_arg(separator.as_ref(), 0, context)?;
+                let texts = texts
+                    .iter()
+                    .enumerate()
+                    .map(|(index, val)| eval_arg(val.as_ref(), index + 1, context))
+                    .collect::<Result<Vec<String>, CustomError>>()?;
+                Ok(TextResult::Text(texts.join(&separator)))
+            }
+            TextExpression::PadStart {
+                text,
+                width,
+                padding,
+            }
+            | TextExpression::PadEnd {
+                text,
+                width,
+                padding,
+            } => {
+                let text = eval_arg(text.as_ref(), 0, context)?;
+                let width = count(width.as_ref(), 1)?;
+                if width > MAX_WIDTH {
+                    return Err(err(Message::ErrOutOfRange, 1));
+                }
+                let padding = match padding {
+                    Some(v) => eval_arg(v.as_ref(), 2, context)?,
+                    None => " ".to_string(),
+                };
+                if padding.is_empty() {
+                    return Err(err(Message::ErrInvalidArgument, 2));
+                }
+                let fill: String = padding
+                    .chars()
+                    .cycle()
+                    .take(width.saturating_sub(text.chars().count()))
+                    .collect();
+                match self {
+                    TextExpression::PadStart { .. } => Ok(TextResult::Text(fill + &text)),
+                    _ => Ok(TextResult::Text(text + &fill)),
+                }
+            }
+        }
+    }
+
+    pub(crate) fn serialize(&self) -> Result<Value, CustomError> {
+        let (op, args): (&str, Vec<Result<Value, CustomError>>) = match self {
+            TextExpression::Concat(v) => ("concat", v.iter().map(|v1| v1.serialize()).collect()),
+            TextExpression::Length(v) => ("length", vec![v.serialize()]),
+            TextExpression::Substring {
+                text,
+                start,
+                length,
+            } => {
+                let mut args = vec![text.serialize(), start.serialize()];
+                if let Some(v) = length {
+                    args.push(v.serialize());
+                }
+                ("substring", args)
+            }
+            TextExpression::Upper(v) => ("upper", vec![v.serialize()]),
+            TextExpression::Lower(v) => ("lower", vec![v.serialize()]),
+            TextExpression::Trim(v) => ("trim", vec![v.serialize()]),
+            TextExpression::StartsWith { text, part } => {
+                ("starts_with", vec![text.serialize(), part.serialize()])
+            }
+            TextExpression::EndsWith { text, part } => {
+                ("ends_with", vec![text.serialize(), part.serialize()])
+            }
+            TextExpression::Contains { text, part } => {
+                ("contains", vec![text.serialize(), part.serialize()])
+            }
+            TextExpression::Replace { text, from, to } => (
+                "replace",
+                vec![text.serialize(), from.serialize(), to.serialize()],
+            ),
+            TextExpression::Split {
+                text,
+                separator,
+                index,
+            } => (
+                "split",
+                vec![text.serialize(), separator.serialize(), index.serialize()],
+            ),
+            TextExpression::Join { separator, texts } => (
+                "join",
+                std::iter::once(separator.serialize())
+                    .chain(texts.iter().map(|v| v.serialize()))
+                    .collect(),
+            ),
+            TextExpression::PadStart {
+                text,
+                width,
+                padding,
+            }
+            | TextExpression::PadEnd {
+                text,
+                width,
+                padding,
+            } => {
+                let mut args = vec![text.serialize(), width.serialize()];
+                if let Some(v) = padding {
+                    args.push(v.serialize());
+                }
+                match self {
+                    TextExpression::PadStart { .. } => ("pad_start", args),
+                    _ => ("pad_end", args),
+                }
+            }
+        };
+        Ok(json!({
+            "op": op,
+            "args": args.into_iter().collect::<Result<Vec<Value>, CustomError>>()?
+        }))
+    }
+}
+
+impl ToValue<i32> for TextExpression {
+    fn get_value(&self, context: &Context) -> Result<i32, CustomError> {
+        match self.eval(context)? {
+            TextResult::Number(v) => Ok(v),
+            _ => Err(CustomError::Message(Message::ErrTypeMismatch)),
+        }
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        self.serialize()
+    }
+}
+
+impl ToValue<BigDecimal> for TextExpression {
+    fn get_value(&self, context: &Context) -> Result<BigDecimal, CustomError> {
+        match self.eval(context)? {
+            TextResult::Number(v) => Ok(BigDecimal::from(v)),
+            _ => Err(CustomError::Message(Message::ErrTypeMismatch)),
+        }
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        self.serialize()
+    }
+}
+
+impl ToValue<String> for TextExpression {
+    fn get_value(&self, context: &Context) -> Result<String, CustomError> {
+        match self.eval(context)? {
+            TextResult::Number(v) => Ok(v.to_string()),
+            TextResult::Text(v) => Ok(v),
+            TextResult::Boolean(v) => Ok(v.to_string()),
+        }
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        self.serialize()
+    }
+}
+
+impl ToValue<bool> for TextExpression {
+    fn get_value(&self, context: &Context) -> Result<bool, CustomError> {
+        match self.eval(context)? {
+            TextResult::Boolean(v) => Ok(v),
+            _ => Err(CustomError::Message(Message::ErrTypeMismatch)),
+        }
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        self.serialize()
+    }
+}
+
+// REGEX OPS
+
+// A pattern, compiled when first used and again only when its text changes.
+// Literal patterns are compiled during deserialization, so that invalid ones are reported there.
+pub struct Pattern {
+    pub(crate) value: Box<dyn ToValue<String>>,
+    pub(crate) anchored: bool, // whether the pattern has to match the whole text
+    pub(crate) cache: RefCell<Option<(String, Regex)>>,
+}
+
+impl Pattern {
+    pub(crate) fn compile(source: &str, anchored: bool) -> Result<Regex, CustomError> {
+        let result = match anchored {
+            true => Regex::new(&format!("^(?:{})$", source)),
+            false => Regex::new(source),
+        };
+        match result {
+            Ok(v) => Ok(v),
+            Err(_) => Err(CustomError::Message(Message::ErrInvalidPattern)),
+        }
+    }
+
+    pub(crate) fn regex(&self, context: &Context) -> Result<Regex, CustomError> {
+        let source = eval_arg(self.value.as_ref(), 1, context)?;
+        if let Some((v, v1)) = self.cache.borrow().as_ref() {
+            if *v == source {
+                return Ok(v1.clone());
+            }
+        }
+        let regex = Self::compile(&source, self.anchored).map_err(|e| e.at("/args/1"))?;
+        *self.cache.borrow_mut() = Some((source, regex.clone()));
+        Ok(regex)
+    }
+}
+
+pub enum RegexExpression {
+    Match {
+        text: Box<dyn ToValue<String>>,
+        pattern: Pattern,
+    },
+    Find {
+        text: Box<dyn ToValue<String>>,
+        pattern: Pattern,
+        group: Option<Box<dyn ToValue<i32>>>, // the whole match when absent
+    },
+    Replace {
+        text: Box<dyn ToValue<String>>,
+        pattern: Pattern,
+        replacement: Box<dyn ToValue<String>>, // may refer to groups, as in $1
+    },
+}
+
+impl RegexExpression {
+    pub(crate) fn eval(&self, context: &Context) -> Result<TextResult, CustomError> {
+        match self {
+            RegexExpression::Match { text, pattern } => {
+                let text = eval_arg(text.as_ref(), 0, context)?;
+                Ok(TextResult::Boolean(pattern.regex(context)?.is_match(&text)))
+            }
+            RegexExpression::Find {
+                text,
+                pattern,
+                group,
+            } => {
+                let text = eval_arg(text.as_ref(), 0, context)?;
+                let regex = pattern.regex(context)?;
+                let group: usize = match group {
+                    Some(v) => match eval_arg(v.as_ref(), 2, context)?.to_usize() {
+                        Some(v1) if v1 < regex.captures_len() => v1,
+                        _ => return Err(CustomError::Message(Message::ErrOutOfRange).at("/args/2")),
+                    },
+                    None => 0,
+                };
+                match regex.captures(&text).and_then(|v| v.get(group)) {
+                    Some(v) => Ok(TextResult::Text(v.as_str().to_string())),
+                    None => Err(CustomError::Message(Message::ErrNoMatch).at("/args/1")),
+                }
+            }
+            RegexExpression::Replace {
+                text,
+                pattern,
+                replacement,
+            } => {
+                let text = eval_arg(text.as_ref(), 0, context)?;
+                let regex = pattern.regex(context)?;
+                let replacement = eval_arg(replacement.as_ref(), 2, context)?;
+                Ok(TextResult::Text(
+                    regex.replace_all(&text, replacement.as_str()).to_string(),
+                ))
+            }
+        }
+    }
+
+    pub(crate) fn serialize(&self) -> Result<Value, CustomError> {
+        let (op, args): (&str, Vec<Result<Value, CustomError>>) = match self {
+            RegexExpression::Match { text, pattern } => (
+                "regex_match",
+                vec![text.serialize(), pattern.value.serialize()],
+            ),
+            RegexExpression::Find {
+                text,
+                pattern,
+                group,
+            } => {
+                let mut args = vec![text.serialize(), pattern.value.serialize()];
+                if let Some(v) = group {
+                    args.push(v.serialize());
+                }
+                ("regex_find", args)
+            }
+            RegexExpression::Replace {
+                text,
+                pattern,
+                replacement,
+            } => (
+                "regex_replace",
+                vec![
+                    text.serialize(),
+                    pattern.value.serialize(),
+                    replacement.serialize(),
+                ],
+            ),
+        };
+        Ok(json!({
+            "op": op,
+            "args": args.into_iter().collect::<Result<Vec<Value>, CustomError>>()?
+        }))
+    }
+}
+
+impl ToValue<String> for RegexExpression {
+    fn get_value(&self, context: &Context) -> Result<String, CustomError> {
+        match self.eval(context)? {
+            TextResult::Number(v) => Ok(v.to_string()),
+            TextResult::Text(v) => Ok(v),
+            TextResult::Boolean(v) => Ok(v.to_string()),
+        }
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        self.serialize()
+    }
+}
+
+impl ToValue<bool> for RegexExpression {
+    fn get_value(&self, context: &Context) -> Result<bool, CustomError> {
+        match self.eval(context)? {
+            TextResult::Boolean(v) => Ok(v),
+            _ => Err(CustomError::Message(Message::ErrTypeMismatch)),
+        }
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        self.serialize()
+    }
+}
+
+#[cfg(test)]
+mod text_tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::dot::DotExpression;
+    use crate::error::Language;
+    use crate::expression::LispExpression;
+    use crate::symbol::{Leaf, Symbol};
+
+    #[test]
+    fn test_text_expression() {
+        let symbols: HashMap<String, Symbol> = vec![(
+            "name".to_string(),
+            Symbol {
+                value: Some(Leaf::Text("  Ångström ".to_string())),
+                values: HashMap::new(),
+            },
+        )]
+        .into_iter()
+        .collect();
+        let expr = TextExpression::PadStart {
+            text: Box::new(TextExpression::Upper(Box::new(TextExpression::Trim(
+                Box::new(DotExpression {
+                    path: vec!["name".to_string()],
+                }),
+            )))),
+            width: Box::new(10),
+            padding: Some(Box::new("*-".to_string())),
+        };
+        let res: String = expr.get_value(&Context::new(&symbols)).unwrap();
+        assert_eq!("*-ÅNGSTRÖM", res);
+        // eval == serialize.deserialize.eval
+        assert_eq!(
+            res,
+            (match LispExpression::deserialize(
+                (&expr as &dyn ToValue<String>).serialize().unwrap()
+            ) {
+                Ok(v) => match v {
+                    LispExpression::TextExpression(v) =>
+                        (&v as &dyn ToValue<String>).get_value(&Context::new(&symbols)),
+                    _ => Err(CustomError::Message(Message::ErrUnexpected)),
+                },
+                Err(_) => Err(CustomError::Message(Message::ErrUnexpected)),
+            })
+            .unwrap()
+        );
+        // serialize == serialize.deserialize.serialize
+        assert_eq!(
+            (&expr as &dyn ToValue<String>).serialize().unwrap(),
+            match LispExpression::deserialize((&expr as &dyn ToValue<String>).serialize().unwrap())
+            {
+                Ok(v) => match v {
+                    LispExpression::TextExpression(v) =>
+                        (&v as &dyn ToValue<String>).serialize().unwrap(),
+                    _ => Value::Null,
+                },
+                Err(_) => Value::Null,
+            }
+        );
+        let eval = |text: &str| match LispExpression::parse(text)
+            .unwrap()
+            .as_text(&Context::new(&symbols))
+        {
+            Ok(v) => json!(v),
+            Err(e) => e.serialize(&Language::English),
+        };
+        assert_eq!(json!("ab2.50"), eval("(concat a b 2.50)"));
+        assert_eq!(json!("11"), eval("(length (. name))"));
+        assert_eq!(json!("ngs"), eval("(substring (trim (. name)) 1 3)"));
+        assert_eq!(json!("tröm "), eval("(substring (. name) 6)"));
+        assert_eq!(json!(""), eval("(substring abc 5)"));
+        assert_eq!(json!("ångström"), eval("(lower (trim (. name)))"));
+        assert_eq!(json!("true"), eval("(starts_with abc ab)"));
+        assert_eq!(json!("false"), eval("(ends_with abc ab)"));
+        assert_eq!(json!("true"), eval("(contains (. name) ström)"));
+        assert_eq!(json!("a-b-c"), eval("(replace \"a b c\" \" \" -)"));
+        assert_eq!(json!("b"), eval("(split a,b,c \",\" 1)"));
+        assert_eq!(json!("a, b, c"), eval("(join \", \" a b c)"));
+        assert_eq!(json!("ab  "), eval("(pad_end ab 4)"));
+        assert_eq!(json!("abc"), eval("(pad_start abc 2)"));
+        assert_eq!(
+            json!({"/args/2": "Out of range"}),
+            eval("(split a,b,c \",\" 3)")
+        );
+        assert_eq!(
+            json!({"/args/1": "Out of range"}),
+            eval("(substring abc -1)")
+        );
+        assert_eq!(
+            json!({"/args/1": "Invalid argument"}),
+            eval("(replace abc \"\" d)")
+        );
+        assert_eq!(
+            json!({"/args/2": "Invalid argument"}),
+            eval("(pad_start abc 5 \"\")")
+        );
+        assert_eq!(
+            json!({"/args/1": "Out of range"}),
+            eval("(pad_start x 2000000000)")
+        );
+        assert_eq!(
+            json!({"/args/1": "Out of range"}),
+            eval("(pad_end x (+:Number 65536 1))")
+        );
+        assert_eq!(json!("65536"), eval("(length (pad_end x 65536))"));
+        assert_eq!(
+            json!({"/args": "Wrong number of arguments"}),
+            match LispExpression::parse("(upper a b)") {
+                Ok(_) => Value::Null,
+                Err(e) => e.serialize(&Language::English),
+            }
+        );
+        assert_eq!(
+            json!({"/args": "Wrong number of arguments"}),
+            match LispExpression::parse("(join \",\")") {
+                Ok(_) => Value::Null,
+                Err(e) => e.serialize(&Language::English),
+            }
+        );
+    }
+
+    #[test]
+    fn test_regex_expression() {
+        let symbols: HashMap<String, Symbol> = vec![
+            (
+                "pan".to_string(),
+                Symbol {
+                    value: Some(Leaf::Text("ABCDE1234F".to_string())),
+                    values: HashMap::new(),
+                },
+            ),
+            (
+                "pattern".to_string(),
+                Symbol {
+                    value: Some(Leaf::Text("[0-9]+".to_string())),
+                    values: HashMap::new(),
+                },
+            ),
+        ]
+        .into_iter()
+        .collect();
+        let expr = RegexExpression::Match {
+            text: Box::new(DotExpression {
+                path: vec!["pan".to_string()],
+            }),
+            pattern: Pattern {
+                value: Box::new("[A-Z]{5}[0-9]{4}[A-Z]".to_string()),
+                anchored: true,
+                cache: RefCell::new(None),
+            },
+        };
+        let res: bool = expr.get_value(&Context::new(&symbols)).unwrap();
+        assert!(res);
+        // eval == serialize.deserialize.eval
+        assert_eq!(
+            res,
+            (match LispExpression::deserialize(
+                (&expr as &dyn ToValue<bool>).serialize().unwrap()
+            ) {
+                Ok(v) => match v {
+                    LispExpression::RegexExpression(v) =>
+                        (&v as &dyn ToValue<bool>).get_value(&Context::new(&symbols)),
+                    _ => Err(CustomError::Message(Message::ErrUnexpected)),
+                },
+                Err(_) => Err(CustomError::Message(Message::ErrUnexpected)),
+            })
+            .unwrap()
+        );
+        // serialize == serialize.deserialize.serialize
+        assert_eq!(
+            (&expr as &dyn ToValue<bool>).serialize().unwrap(),
+            match LispExpression::deserialize((&expr as &dyn ToValue<bool>).serialize().unwrap()) {
+                Ok(v) => match v {
+                    LispExpression::RegexExpression(v) =>
+                        (&v as &dyn ToValue<bool>).serialize().unwrap(),
+                    _ => Value::Null,
+                },
+                Err(_) => Value::Null,
+            }
+        );
+        let eval = |text: &str| match LispExpression::parse(text)
+            .unwrap()
+            .as_text(&Context::new(&symbols))
+        {
+            Ok(v) => json!(v),
+            Err(e) => e.serialize(&Language::English),
+        };
+        // regex_match has to match the whole text, regex_find anywhere in it
+        assert_eq!(json!("false"), eval("(regex_match (. pan) \"[A-Z]{5}\")"));
+        assert_eq!(json!("true"), eval("(regex_match 560001 \"\\\\d{6}\")"));
+        assert_eq!(json!("ABCDE"), eval("(regex_find (. pan) \"[A-Z]{5}\")"));
+        assert_eq!(json!("1234"), eval("(regex_find (. pan) (. pattern))"));
+        assert_eq!(
+            json!("1234"),
+            eval("(regex_find (. pan) \"([A-Z]+)([0-9]+)\" 2)")
+        );
+        assert_eq!(
+            json!("+91-98450"),
+            eval("(regex_replace \"98450 12345\" \"([0-9]+) [0-9]+\" +91-$1)")
+        );
+        assert_eq!(
+            json!({"/args/2": "Out of range"}),
+            eval("(regex_find (. pan) \"[A-Z]+\" 1)")
+        );
+        assert_eq!(json!({"/args/1": "No match"}), eval("(regex_find abc z)"));
+        assert_eq!(
+            json!({"/args/0/args/1": "No match"}),
+            eval("(concat (regex_find (. pan) \"[a-z]+\"))")
+        );
+        assert_eq!(
+            json!({"/args/1": "Invalid pattern"}),
+            eval("(regex_find (. pan) (concat \"[\" (. pattern)))")
+        );
+        assert_eq!(
+            json!({"/args/1": "Invalid pattern"}),
+            match LispExpression::parse("(regex_match (. pan) \"[A-Z\")") {
+                Ok(_) => Value::Null,
+                Err(e) => e.serialize(&Language::English),
+            }
+        );
+        // patterns are compiled once, and again only when their text changes
+        let expr = LispExpression::parse("(regex_find (. pan) (. pattern))").unwrap();
+        let cached = |expr: &LispExpression| match expr {
+            LispExpression::RegexExpression(RegexExpression::Find { pattern, .. }) => {
+                pattern.cache.borrow().as_ref().map(|(v, _)| v.to_string())
+            }
+            _ => None,
+        };
+        assert_eq!(None, cached(&expr));
+        expr.as_text(&Context::new(&symbols)).unwrap();
+        assert_eq!(Some("[0-9]+".to_string()), cached(&expr));
+        let symbols1: HashMap<String, Symbol> = vec![
+            (
+                "pan".to_string(),
+                Symbol {
+                    value: Some(Leaf::Text("ABCDE1234F".to_string())),
+                    values: HashMap::new(),
+                },
+            ),
+            (
+                "pattern".to_string(),
+                Symbol {
+                    value: Some(Leaf::Text("[A-Z]$".to_string())),
+                    values: HashMap::new(),
+                },
+            ),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!("F", expr.as_text(&Context::new(&symbols1)).unwrap());
+        assert_eq!(Some("[A-Z]$".to_string()), cached(&expr));
+    }
+}
@@ -0,0 +1,1331 @@
+// Static type checking of lisp expressions.
+//
+// Expressions are checked in their serialized form against the declared shape of the symbols
+// they will be evaluated with, so that every ill-typed node is reported at once, keyed by its
+// JSON pointer, instead of failing one at a time during evaluation.
+
+use crate::context::RoundingMode;
+use crate::error::{CustomError, Message};
+use crate::expression::LispExpression;
+use crate::symbol::LeafType;
+use crate::temporal::Temporal;
+use crate::text::Pattern;
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, Duration, FixedOffset, NaiveDate};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::str::FromStr;
+
+// Declared shape of a Symbol
+#[derive(Clone)]
+pub struct SymbolType {
+    pub value: Option<LeafType>,
+    pub values: HashMap<String, SymbolType>,
+    pub items: Option<Box<SymbolType>>, // shape of every item, when the value is a list
+}
+
+struct TypeChecker<'a> {
+    schema: &'a HashMap<String, SymbolType>,
+    scopes: Vec<HashMap<String, SymbolType>>, // shapes of the local variables in scope, innermost last
+    errors: HashMap<String, CustomError>,
+}
+
+impl<'a> TypeChecker<'a> {
+    const LEAF_TYPES: &'static [LeafType] = &[
+        LeafType::Number,
+        LeafType::Decimal,
+        LeafType::Text,
+        LeafType::Boolean,
+        LeafType::Date,
+        LeafType::DateTime,
+        LeafType::Duration,
+    ];
+
+    fn report(&mut self, pointer: &str, message: Message) {
+        self.errors
+            .entry(pointer.to_string())
+            .or_insert(CustomError::Message(message));
+    }
+
+    // Types a node can be evaluated to, mirroring the ToValue implementations
+    fn coercions(leaf_type: LeafType) -> Vec<LeafType> {
+        match leaf_type {
+            LeafType::Number | LeafType::Decimal => {
+                vec![LeafType::Number, LeafType::Decimal, LeafType::Text]
+            }
+            LeafType::Text => vec![LeafType::Text],
+            LeafType::Boolean => vec![LeafType::Boolean, LeafType::Text],
+            LeafType::Date => vec![LeafType::Date, LeafType::Text],
+            LeafType::DateTime => vec![LeafType::DateTime, LeafType::Text],
+            LeafType::Duration => vec![LeafType::Duration, LeafType::Text],
+        }
+    }
+
+    // Literals are accepted wherever deserialization accepts them
+    fn check_literal(val: &Value, expected: LeafType) -> Option<Message> {
+        match (val, expected) {
+            (Value::Number(_), LeafType::Number)
+            | (Value::Number(_), LeafType::Decimal)
+            | (Value::Number(_), LeafType::Text)
+            | (Value::String(_), LeafType::Text)
+            | (Value::Bool(_), LeafType::Text)
+            | (Value::Bool(_), LeafType::Boolean)
+            | (Value::Null, _) => None,
+            (Value::String(v), LeafType::Number) | (Value::String(v), LeafType::Decimal) => {
+                match BigDecimal::from_str(v).is_ok() {
+                    true => None,
+                    false => Some(Message::ErrInvalidLiteral),
+                }
+            }
+            (Value::String(v), LeafType::Boolean) => match v.parse::<bool>() {
+                Ok(_) => None,
+                Err(_) => Some(Message::ErrInvalidLiteral),
+            },
+            (Value::String(v), LeafType::Date) => match NaiveDate::parse(v) {
+                Some(_) => None,
+                None => Some(Message::ErrInvalidLiteral),
+            },
+            (Value::String(v), LeafType::DateTime) => match DateTime::<FixedOffset>::parse(v) {
+                Some(_) => None,
+                None => Some(Message::ErrInvalidLiteral),
+            },
+            (Value::String(v), LeafType::Duration) => match Duration::parse(v) {
+                Some(_) => None,
+                None => Some(Message::ErrInvalidLiteral),
+            },
+            _ => Some(Message::ErrTypeMismatch),
+        }
+    }
+
+    fn check(&mut self, val: &Value, expected: Option<LeafType>, pointer: &str) {
+        match val {
+            Value::Object(v) => {
+                if let Some(v1) = self.check_expression(v, pointer) {
+                    if let Some(v2) = expected {
+                        if !v1.contains(&v2) {
+                            self.report(pointer, Message::ErrTypeMismatch)
+                        }
+                    }
+                }
+            }
+            _ => match expected {
+                Some(v) => {
+                    if let Some(v1) = Self::check_literal(val, v) {
+                        self.report(pointer, v1)
+                    }
+                }
+                None => self.report(pointer, Message::ErrDeserialization),
+            },
+        }
+    }
+
+    fn check_args(&mut self, args: &[Value], expected: LeafType, pointer: &str) {
+        for (index, val) in args.iter().enumerate() {
+            self.check(val, Some(expected), &format!("{}/args/{}", pointer, index));
+        }
+    }
+
+    fn check_type(
+        &mut self,
+        val: Option<&Value>,
+        allowed: &[LeafType],
+        pointer: &str,
+    ) -> Option<LeafType> {
+        match val.and_then(LeafType::deserialize) {
+            Some(v) if allowed.contains(&v) => Some(v),
+            _ => {
+                self.report(&format!("{}/type", pointer), Message::ErrTypeMismatch);
+                None
+            }
+        }
+    }
+
+    fn check_arity(&mut self, args: &[Value], minimum: usize, pointer: &str) -> bool {
+        match args.len() < minimum {
+            true => {
+                self.report(&format!("{}/args", pointer), Message::ErrWrongArity);
+                false
+            }
+            false => true,
+        }
+    }
+
+    fn check_path(&mut self, args: &[Value], pointer: &str) -> Option<LeafType> {
+        match self.resolve_path(args, pointer) {
+            Some(v) if v.items.is_some() => {
+                self.report(pointer, Message::ErrTypeMismatch);
+                None
+            }
+            Some(SymbolType { value: Some(v), .. }) => Some(v),
+            _ => {
+                self.report(pointer, Message::ErrMissingSymbol);
+                None
+            }
+        }
+    }
+
+    // Resolves a path, whose first segment names a local variable or else a symbol.
+    // Segments following a list index its items.
+    fn resolve_path(&mut self, args: &[Value], pointer: &str) -> Option<SymbolType> {
+        let mut keys: Vec<String> = vec![];
+        for (index, val) in args.iter().enumerate() {
+            match val {
+                Value::String(v) => keys.push(v.to_string()),
+                Value::Number(v) => keys.push(v.to_string()),
+                _ => {
+                    self.report(
+                        &format!("{}/args/{}", pointer, index),
+                        Message::ErrDeserialization,
+                    );
+                    return None;
+                }
+            }
+        }
+        let mut result: Option<&SymbolType> = keys.first().and_then(|v| {
+            self.scopes
+                .iter()
+                .rev()
+                .find_map(|v1| v1.get(v))
+                .or_else(|| self.schema.get(v))
+        });
+        for key in keys.iter().skip(1) {
+            result = result.and_then(|v| match v.items.as_deref() {
+                Some(v1) => key.parse::<usize>().ok().map(|_| v1),
+                None => v.values.get(key),
+            });
+        }
+        let result = result.cloned();
+        if result.is_none() {
+            self.report(pointer, Message::ErrMissingSymbol);
+        }
+        result
+    }
+
+    // Checks a list operand, whose items have to hold values of item_type when one is given.
+    // Returns the shape of its items, when known.
+    fn check_list(
+        &mut self,
+        val: &Value,
+        item_type: Option<LeafType>,
+        pointer: &str,
+    ) -> Option<SymbolType> {
+        let (op, args): (&str, &Vec<Value>) = match val {
+            Value::Null => return None,
+            Value::Object(v) => match (v.get("op").and_then(|v1| v1.as_str()), v.get("args")) {
+                (Some(v1), Some(Value::Array(v2))) => (v1, v2),
+                _ => {
+                    self.report(pointer, Message::ErrDeserialization);
+                    return None;
+                }
+            },
+            _ => {
+                self.report(pointer, Message::ErrTypeMismatch);
+                return None;
+            }
+        };
+        let items: SymbolType = match op {
+            "." => match self.resolve_path(args, pointer)?.items {
+                Some(v) => *v,
+                None => {
+                    self.report(pointer, Message::ErrTypeMismatch);
+                    return None;
+                }
+            },
+            "map" | "filter" => match val.as_object() {
+                Some(v) => self.check_sequence(v, pointer)?,
+                None => return None,
+            },
+            _ => {
+                self.report(pointer, Message::ErrTypeMismatch);
+                return None;
+            }
+        };
+        if let Some(v) = item_type {
+            if !items
+                .value
+                .is_some_and(|v1| Self::coercions(v1).contains(&v))
+            {
+                self.report(pointer, Message::ErrTypeMismatch)
+            }
+        }
+        Some(items)
+    }
+
+    // Checks a map or filter, returning the shape of the items of the resulting list
+    fn check_sequence(&mut self, val: &Map<String, Value>, pointer: &str) -> Option<SymbolType> {
+        let args: &Vec<Value> = match val.get("args") {
+            Some(Value::Array(v)) => v,
+            _ => {
+                self.report(pointer, Message::ErrDeserialization);
+                return None;
+            }
+        };
+        let result_type = match val.get("op").and_then(|v| v.as_str()) {
+            Some("map") => Some(self.check_type(val.get("type"), Self::LEAF_TYPES, pointer)?),
+            _ => None,
+        };
+        if args.len() != 3 {
+            self.report(&format!("{}/args", pointer), Message::ErrWrongArity);
+            return None;
+        }
+        let items = self.check_list(&args[0], None, &format!("{}/args/0", pointer));
+        let body_type = result_type.unwrap_or(LeafType::Boolean);
+        self.check_lambda(args, items.clone(), body_type, pointer);
+        match result_type {
+            Some(v) => Some(SymbolType {
+                value: Some(v),
+                values: HashMap::new(),
+                items: None,
+            }),
+            None => items,
+        }
+    }
+
+    // Checks a lambda, [name, body], following the list in args, with the name bound to its items
+    fn check_lambda(
+        &mut self,
+        args: &[Value],
+        items: Option<SymbolType>,
+        body_type: LeafType,
+        pointer: &str,
+    ) {
+        match &args[1] {
+            Value::String(v) => {
+                // the body is only checked once the shape of the items is known
+                if let Some(v1) = items {
+                    self.scopes.push(HashMap::from([(v.to_string(), v1)]));
+                    self.check(&args[2], Some(body_type), &format!("{}/args/2", pointer));
+                    self.scopes.pop();
+                }
+            }
+            _ => self.report(&format!("{}/args/1", pointer), Message::ErrTypeMismatch),
+        }
+    }
+
+    // Match guards, unlike conditions of cond, may take the forms of Guard
+    fn check_guards(
+        &mut self,
+        val: &Value,
+        conditional_type: LeafType,
+        return_type: LeafType,
+        forms: bool,
+        pointer: &str,
+    ) {
+        match val {
+            Value::Array(v) => {
+                for (index, val1) in v.iter().enumerate() {
+                    let pointer1 = format!("{}/{}", pointer, index);
+                    match val1 {
+                        Value::Array(v1) if v1.len() == 2 => {
+                            let pointer2 = format!("{}/0", pointer1);
+                            match (forms, &v1[0]) {
+                                (true, Value::Array(v2)) => {
+                                    self.check_guard(v2, conditional_type, &pointer2)
+                                }
+                                _ => {
+                                    self.check(&v1[0], Some(conditional_type), &pointer2);
+                                }
+                            }
+                            self.check(&v1[1], Some(return_type), &format!("{}/1", pointer1));
+                        }
+                        _ => self.report(&pointer1, Message::ErrWrongArity),
+                    }
+                }
+            }
+            _ => self.report(pointer, Message::ErrDeserialization),
+        }
+    }
+
+    fn check_guard(&mut self, values: &[Value], conditional_type: LeafType, pointer: &str) {
+        match (values.first().and_then(|v| v.as_str()), values.len()) {
+            (Some("in"), v) if v > 1 => {
+                for (index, val) in values.iter().enumerate().skip(1) {
+                    self.check(
+                        val,
+                        Some(conditional_type),
+                        &format!("{}/{}", pointer, index),
+                    );
+                }
+            }
+            (Some("between"), 3) => {
+                self.check(
+                    &values[1],
+                    Some(conditional_type),
+                    &format!("{}/1", pointer),
+                );
+                self.check(
+                    &values[2],
+                    Some(conditional_type),
+                    &format!("{}/2", pointer),
+                );
+            }
+            (Some("where"), 3) => match &values[1] {
+                Value::String(v) => {
+                    let condition = SymbolType {
+                        value: Some(conditional_type),
+                        values: HashMap::new(),
+                        items: None,
+                    };
+                    self.scopes
+                        .push(HashMap::from([(v.to_string(), condition)]));
+                    self.check(
+                        &values[2],
+                        Some(LeafType::Boolean),
+                        &format!("{}/2", pointer),
+                    );
+                    self.scopes.pop();
+                }
+                _ => self.report(&format!("{}/1", pointer), Message::ErrTypeMismatch),
+            },
+            (Some("in" | "between" | "where"), _) => self.report(pointer, Message::ErrWrongArity),
+            _ => self.report(pointer, Message::ErrTypeMismatch),
+        }
+    }
+
+    // Checks a value following its declared type, [type, value]
+    fn check_typed(&mut self, val: &Value, pointer: &str) {
+        match val.as_array().map(|v| v.as_slice()) {
+            Some([v, v1]) => match LeafType::deserialize(v) {
+                Some(v2) => self.check(v1, Some(v2), &format!("{}/1", pointer)),
+                None => self.report(&format!("{}/0", pointer), Message::ErrTypeMismatch),
+            },
+            Some(_) => self.report(pointer, Message::ErrWrongArity),
+            None => self.report(pointer, Message::ErrTypeMismatch),
+        }
+    }
+
+    // Checks bindings in order, each in a scope holding the ones before it, leaving that scope open
+    fn check_bindings(&mut self, val: &Value, pointer: &str) {
+        self.scopes.push(HashMap::new());
+        let bindings: &Vec<Value> = match val {
+            Value::Array(v) => v,
+            _ => {
+                self.report(pointer, Message::ErrTypeMismatch);
+                return;
+            }
+        };
+        for (index, val1) in bindings.iter().enumerate() {
+            let pointer1 = format!("{}/{}", pointer, index);
+            match val1.as_array().map(|v| v.as_slice()) {
+                Some([Value::String(v), v1, v2]) => {
+                    let binding_type = LeafType::deserialize(v1);
+                    if binding_type.is_none() {
+                        self.report(&format!("{}/1", pointer1), Message::ErrTypeMismatch);
+                    }
+                    self.check(v2, binding_type, &format!("{}/2", pointer1));
+                    if let (Some(v3), Some(scope)) = (binding_type, self.scopes.last_mut()) {
+                        let local = SymbolType {
+                            value: Some(v3),
+                            values: HashMap::new(),
+                            items: None,
+                        };
+                        scope.insert(v.to_string(), local);
+                    }
+                }
+                Some([_, _, _]) => {
+                    self.report(&format!("{}/0", pointer1), Message::ErrTypeMismatch)
+                }
+                Some(_) => self.report(&pointer1, Message::ErrWrongArity),
+                None => self.report(&pointer1, Message::ErrTypeMismatch),
+            }
+        }
+    }
+
+    // Checks an expression node, returning the types it can be evaluated to
+    fn check_expression(
+        &mut self,
+        val: &Map<String, Value>,
+        pointer: &str,
+    ) -> Option<Vec<LeafType>> {
+        let args: &Vec<Value> = match val.get("args") {
+            Some(Value::Array(v)) => v,
+            _ => {
+                self.report(pointer, Message::ErrDeserialization);
+                return None;
+            }
+        };
+        let op: &str = match val.get("op") {
+            Some(Value::String(v)) => v.as_str(),
+            _ => {
+                self.report(pointer, Message::ErrDeserialization);
+                return None;
+            }
+        };
+        match op {
+            "+" | "*" | "-" | "/" | "%" | "min" | "max" | "pow" => {
+                let v = self.check_type(
+                    val.get("type"),
+                    &[LeafType::Number, LeafType::Decimal],
+                    pointer,
+                )?;
+                self.check_arity(args, 1, pointer);
+                self.check_args(args, v, pointer);
+                Some(Self::coercions(v))
+            }
+            "abs" | "sqrt" | "sign" | "floor" | "ceil" | "clamp" | "percent" => {
+                let (allowed, arity): (&[LeafType], usize) = match op {
+                    "floor" | "ceil" => (&[LeafType::Decimal], 1),
+                    "percent" => (&[LeafType::Number, LeafType::Decimal], 2),
+                    "clamp" => (&[LeafType::Number, LeafType::Decimal], 3),
+                    _ => (&[LeafType::Number, LeafType::Decimal], 1),
+                };
+                let v = self.check_type(val.get("type"), allowed, pointer)?;
+                match args.len() == arity {
+                    true => self.check_args(args, v, pointer),
+                    false => self.report(&format!("{}/args", pointer), Message::ErrWrongArity),
+                }
+                Some(Self::coercions(v))
+            }
+            "round" => {
+                match args.len() > 3 {
+                    true => self.report(&format!("{}/args", pointer), Message::ErrWrongArity),
+                    false => {
+                        if self.check_arity(args, 2, pointer) {
+                            self.check(
+                                &args[0],
+                                Some(LeafType::Decimal),
+                                &format!("{}/args/0", pointer),
+                            );
+                            if !args[1].is_i64() {
+                                self.report(
+                                    &format!("{}/args/1", pointer),
+                                    Message::ErrInvalidLiteral,
+                                );
+                            }
+                            if let Some(v) = args.get(2) {
+                                if RoundingMode::deserialize(v).is_none() {
+                                    self.report(
+                                        &format!("{}/args/2", pointer),
+                                        Message::ErrInvalidLiteral,
+                                    );
+                                }
+                            }
+                        }
+                    }
+                }
+                Some(Self::coercions(LeafType::Decimal))
+            }
+            "==" | "!=" | ">=" | "<=" | ">" | "<" => {
+                let v = self.check_type(
+                    val.get("type"),
+                    &[
+                        LeafType::Number,
+                        LeafType::Decimal,
+                        LeafType::Text,
+                        LeafType::Date,
+                        LeafType::DateTime,
+                        LeafType::Duration,
+                    ],
+                    pointer,
+                )?;
+                self.check_arity(args, 2, pointer);
+                self.check_args(args, v, pointer);
+                Some(Self::coercions(LeafType::Boolean))
+            }
+            "in" | "not_in" | "between" | "between_exclusive" => {
+                let v = self.check_type(
+                    val.get("type"),
+                    &[LeafType::Number, LeafType::Decimal, LeafType::Text],
+                    pointer,
+                )?;
+                match op {
+                    "in" | "not_in" => {
+                        self.check_arity(args, 2, pointer);
+                    }
+                    _ if args.len() != 3 => {
+                        self.report(&format!("{}/args", pointer), Message::ErrWrongArity)
+                    }
+                    _ => {}
+                }
+                self.check_args(args, v, pointer);
+                Some(Self::coercions(LeafType::Boolean))
+            }
+            "and" | "or" | "xor" | "nand" | "nor" => {
+                self.check_arity(args, 2, pointer);
+                self.check_args(args, LeafType::Boolean, pointer);
+                Some(Self::coercions(LeafType::Boolean))
+            }
+            "implies" | "iff" => {
+                if args.len() != 2 {
+                    self.report(&format!("{}/args", pointer), Message::ErrWrongArity);
+                }
+                self.check_args(args, LeafType::Boolean, pointer);
+                Some(Self::coercions(LeafType::Boolean))
+            }
+            "all_of" | "any_of" | "none_of" => {
+                self.check_args(args, LeafType::Boolean, pointer);
+                Some(Self::coercions(LeafType::Boolean))
+            }
+            "exactly_n_of" => {
+                if self.check_arity(args, 1, pointer) {
+                    self.check(
+                        &args[0],
+                        Some(LeafType::Number),
+                        &format!("{}/args/0", pointer),
+                    );
+                    for (index, val1) in args.iter().enumerate().skip(1) {
+                        self.check(
+                            val1,
+                            Some(LeafType::Boolean),
+                            &format!("{}/args/{}", pointer, index),
+                        );
+                    }
+                }
+                Some(Self::coercions(LeafType::Boolean))
+            }
+            "not" => {
+                self.check_arity(args, 1, pointer);
+                self.check_args(args, LeafType::Boolean, pointer);
+                Some(Self::coercions(LeafType::Boolean))
+            }
+            "match" => {
+                let all = [
+                    LeafType::Number,
+                    LeafType::Decimal,
+                    LeafType::Text,
+                    LeafType::Boolean,
+                    LeafType::Date,
+                    LeafType::DateTime,
+                    LeafType::Duration,
+                ];
+                let (return_type, conditional_type) = match val.get("type") {
+                    Some(Value::Array(v)) if v.len() == 2 => (
+                        self.check_type(v.first(), &all, pointer),
+                        self.check_type(v.get(1), &all, pointer),
+                    ),
+                    _ => {
+                        self.report(&format!("{}/type", pointer), Message::ErrTypeMismatch);
+                        (None, None)
+                    }
+                };
+                let (return_type, conditional_type) = (return_type?, conditional_type?);
+                if self.check_arity(args, 3, pointer) {
+                    self.check(
+                        &args[0],
+                        Some(conditional_type),
+                        &format!("{}/args/0", pointer),
+                    );
+                    self.check_guards(
+                        &args[1],
+                        conditional_type,
+                        return_type,
+                        true,
+                        &format!("{}/args/1", pointer),
+                    );
+                    self.check(&args[2], Some(return_type), &format!("{}/args/2", pointer));
+                }
+                Some(Self::coercions(return_type))
+            }
+            "if" | "cond" => {
+                let v = self.check_type(
+                    val.get("type"),
+                    &[
+                        LeafType::Number,
+                        LeafType::Decimal,
+                        LeafType::Text,
+                        LeafType::Boolean,
+                    ],
+                    pointer,
+                )?;
+                match (op, args.len()) {
+                    ("if", 3) => {
+                        self.check(
+                            &args[0],
+                            Some(LeafType::Boolean),
+                            &format!("{}/args/0", pointer),
+                        );
+                        self.check(&args[1], Some(v), &format!("{}/args/1", pointer));
+                        self.check(&args[2], Some(v), &format!("{}/args/2", pointer));
+                    }
+                    ("cond", 2) => {
+                        self.check_guards(
+                            &args[0],
+                            LeafType::Boolean,
+                            v,
+                            false,
+                            &format!("{}/args/0", pointer),
+                        );
+                        self.check(&args[1], Some(v), &format!("{}/args/1", pointer));
+                    }
+                    _ => self.report(&format!("{}/args", pointer), Message::ErrWrongArity),
+                }
+                Some(Self::coercions(v))
+            }
+            "length" | "contains" | "first" | "last"
+                if val.contains_key("type") || op == "first" || op == "last" =>
+            {
+                let v = self.check_type(val.get("type"), Self::LEAF_TYPES, pointer)?;
+                let arity: usize = match op {
+                    "contains" => 2,
+                    _ => 1,
+                };
+                match args.len() == arity {
+                    true => {
+                        let item_type = match op {
+                            "length" => None,
+                            _ => Some(v),
+                        };
+                        self.check_list(&args[0], item_type, &format!("{}/args/0", pointer));
+                        if let Some(v1) = args.get(1) {
+                            self.check(v1, Some(v), &format!("{}/args/1", pointer));
+                        }
+                    }
+                    false => self.report(&format!("{}/args", pointer), Message::ErrWrongArity),
+                }
+                Some(Self::coercions(match op {
+                    "length" => LeafType::Number,
+                    "contains" => LeafType::Boolean,
+                    _ => v,
+                }))
+            }
+            "concat" | "length" | "substring" | "upper" | "lower" | "trim" | "starts_with"
+            | "ends_with" | "contains" | "replace" | "split" | "join" | "pad_start" | "pad_end"
+            | "regex_match" | "regex_find" | "regex_replace" => {
+                use LeafType::{Boolean, Number, Text};
+                // params, minimum number of args, whether the last param repeats, and result
+                let (params, minimum, variadic, result): (&[LeafType], usize, bool, LeafType) =
+                    match op {
+                        "concat" => (&[Text], 1, true, Text),
+                        "join" => (&[Text, Text], 2, true, Text),
+                        "length" => (&[Text], 1, false, Number),
+                        "upper" | "lower" | "trim" => (&[Text], 1, false, Text),
+                        "starts_with" | "ends_with" | "contains" => {
+                            (&[Text, Text], 2, false, Boolean)
+                        }
+                        "replace" => (&[Text, Text, Text], 3, false, Text),
+                        "split" => (&[Text, Text, Number], 3, false, Text),
+                        "substring" => (&[Text, Number, Number], 2, false, Text),
+                        "regex_match" => (&[Text, Text], 2, false, Boolean),
+                        "regex_find" => (&[Text, Text, Number], 2, false, Text),
+                        "regex_replace" => (&[Text, Text, Text], 3, false, Text),
+                        _ => (&[Text, Number, Text], 2, false, Text),
+                    };
+                match args.len() > params.len() && !variadic {
+                    true => self.report(&format!("{}/args", pointer), Message::ErrWrongArity),
+                    false => {
+                        self.check_arity(args, minimum, pointer);
+                        for (index, val1) in args.iter().enumerate() {
+                            let v = params[index.min(params.len() - 1)];
+                            self.check(val1, Some(v), &format!("{}/args/{}", pointer, index));
+                        }
+                    }
+                }
+                if let (true, Some(Value::String(v))) = (op.starts_with("regex_"), args.get(1)) {
+                    if Pattern::compile(v, op == "regex_match").is_err() {
+                        self.report(&format!("{}/args/1", pointer), Message::ErrInvalidPattern);
+                    }
+                }
+                Some(Self::coercions(result))
+            }
+            "let" => {
+                let v = self.check_type(
+                    val.get("type"),
+                    &[
+                        LeafType::Number,
+                        LeafType::Decimal,
+                        LeafType::Text,
+                        LeafType::Boolean,
+                    ],
+                    pointer,
+                )?;
+                match args.len() {
+                    2 => {
+                        self.check_bindings(&args[0], &format!("{}/args/0", pointer));
+                        self.check(&args[1], Some(v), &format!("{}/args/1", pointer));
+                        self.scopes.pop();
+                    }
+                    _ => self.report(&format!("{}/args", pointer), Message::ErrWrongArity),
+                }
+                Some(Self::coercions(v))
+            }
+            // Functions are resolved at evaluation, so only the shape of the call is checked
+            "call" => {
+                let v = self.check_type(val.get("type"), Self::LEAF_TYPES, pointer)?;
+                if self.check_arity(args, 1, pointer) && !args[0].is_string() {
+                    self.report(&format!("{}/args/0", pointer), Message::ErrTypeMismatch);
+                }
+                for (index, val1) in args.iter().enumerate().skip(1) {
+                    self.check_typed(val1, &format!("{}/args/{}", pointer, index));
+                }
+                Some(Self::coercions(v))
+            }
+            "to_number" | "to_decimal" | "to_text" | "to_boolean" | "parse_number"
+            | "parse_decimal" => {
+                let v = match op {
+                    "to_number" | "parse_number" => LeafType::Number,
+                    "to_decimal" | "parse_decimal" => LeafType::Decimal,
+                    "to_text" => LeafType::Text,
+                    _ => LeafType::Boolean,
+                };
+                let maximum: usize = match op {
+                    "to_number" | "parse_number" | "parse_decimal" => 2,
+                    _ => 1,
+                };
+                match args.len() > maximum {
+                    true => self.report(&format!("{}/args", pointer), Message::ErrWrongArity),
+                    false => {
+                        if self.check_arity(args, 1, pointer) {
+                            match op {
+                                "parse_number" | "parse_decimal" => {
+                                    self.check(
+                                        &args[0],
+                                        Some(LeafType::Text),
+                                        &format!("{}/args/0", pointer),
+                                    );
+                                    if let Some(v1) = args.get(1) {
+                                        self.check(v1, Some(v), &format!("{}/args/1", pointer));
+                                    }
+                                }
+                                _ => {
+                                    self.check_typed(&args[0], &format!("{}/args/0", pointer));
+                                    if let Some(v1) = args.get(1) {
+                                        if RoundingMode::deserialize(v1).is_none() {
+                                            self.report(
+                                                &format!("{}/args/1", pointer),
+                                                Message::ErrInvalidLiteral,
+                                            );
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+                Some(Self::coercions(v))
+            }
+            // the path of exists does not have to resolve
+            "exists" => {
+                if self.check_arity(args, 1, pointer) {
+                    for (index, val1) in args.iter().enumerate() {
+                        if !val1.is_string() && !val1.is_number() {
+                            self.report(
+                                &format!("{}/args/{}", pointer, index),
+                                Message::ErrDeserialization,
+                            );
+                        }
+                    }
+                }
+                Some(Self::coercions(LeafType::Boolean))
+            }
+            "is_null" => {
+                match args.len() > 1 {
+                    true => self.report(&format!("{}/args", pointer), Message::ErrWrongArity),
+                    false => {
+                        if self.check_arity(args, 1, pointer) {
+                            self.check_typed(&args[0], &format!("{}/args/0", pointer));
+                        }
+                    }
+                }
+                Some(Self::coercions(LeafType::Boolean))
+            }
+            "coalesce" => {
+                let v = self.check_type(val.get("type"), Self::LEAF_TYPES, pointer)?;
+                self.check_arity(args, 1, pointer);
+                self.check_args(args, v, pointer);
+                Some(Self::coercions(v))
+            }
+            "add_days" | "add_months" | "diff_days" | "start_of_month" | "end_of_month"
+            | "weekday" | "age" | "add_duration" | "duration_between" => {
+                use LeafType::{Date, DateTime, Duration, Number};
+                let (params, result): (&[LeafType], LeafType) = match op {
+                    "add_days" | "add_months" => (&[Date, Number], Date),
+                    "diff_days" => (&[Date, Date], Number),
+                    "start_of_month" | "end_of_month" => (&[Date], Date),
+                    "weekday" => (&[Date], Number),
+                    "age" => (&[Date, Date], Number),
+                    "add_duration" => (&[DateTime, Duration], DateTime),
+                    _ => (&[DateTime, DateTime], Duration),
+                };
+                match args.len() == params.len() {
+                    true => {
+                        for (index, val1) in args.iter().enumerate() {
+                            self.check(
+                                val1,
+                                Some(params[index]),
+                                &format!("{}/args/{}", pointer, index),
+                            );
+                        }
+                    }
+                    false => self.report(&format!("{}/args", pointer), Message::ErrWrongArity),
+                }
+                Some(Self::coercions(result))
+            }
+            // lists are only accepted where list operands are expected
+            "map" | "filter" => {
+                self.check_sequence(val, pointer);
+                self.report(pointer, Message::ErrTypeMismatch);
+                None
+            }
+            "any" | "all" | "count" => {
+                match (op, args.len()) {
+                    ("count", 1) => {
+                        self.check_list(&args[0], None, &format!("{}/args/0", pointer));
+                    }
+                    (_, 3) => {
+                        let items = self.check_list(&args[0], None, &format!("{}/args/0", pointer));
+                        self.check_lambda(args, items, LeafType::Boolean, pointer);
+                    }
+                    _ => self.report(&format!("{}/args", pointer), Message::ErrWrongArity),
+                }
+                Some(Self::coercions(match op {
+                    "count" => LeafType::Number,
+                    _ => LeafType::Boolean,
+                }))
+            }
+            "sum" | "avg" => {
+                let v = self.check_type(
+                    val.get("type"),
+                    &[LeafType::Number, LeafType::Decimal],
+                    pointer,
+                )?;
+                match args.len() {
+                    1 => {
+                        self.check_list(&args[0], Some(v), &format!("{}/args/0", pointer));
+                    }
+                    3 => {
+                        let items = self.check_list(&args[0], None, &format!("{}/args/0", pointer));
+                        self.check_lambda(args, items, v, pointer);
+                    }
+                    _ => self.report(&format!("{}/args", pointer), Message::ErrWrongArity),
+                }
+                Some(Self::coercions(v))
+            }
+            "reduce" => {
+                let v = self.check_type(val.get("type"), Self::LEAF_TYPES, pointer)?;
+                match args.as_slice() {
+                    [list, Value::String(v1), Value::String(v2), initial, body] => {
+                        let items = self.check_list(list, None, &format!("{}/args/0", pointer));
+                        self.check(initial, Some(v), &format!("{}/args/3", pointer));
+                        if let Some(v3) = items {
+                            let accumulator = SymbolType {
+                                value: Some(v),
+                                values: HashMap::new(),
+                                items: None,
+                            };
+                            self.scopes.push(HashMap::from([
+                                (v1.to_string(), v3),
+                                (v2.to_string(), accumulator),
+                            ]));
+                            self.check(body, Some(v), &format!("{}/args/4", pointer));
+                            self.scopes.pop();
+                        }
+                    }
+                    [_, Value::String(_), _, _, _] => {
+                        self.report(&format!("{}/args/2", pointer), Message::ErrTypeMismatch)
+                    }
+                    [_, _, _, _, _] => {
+                        self.report(&format!("{}/args/1", pointer), Message::ErrTypeMismatch)
+                    }
+                    _ => self.report(&format!("{}/args", pointer), Message::ErrWrongArity),
+                }
+                Some(Self::coercions(v))
+            }
+            "." => {
+                self.check_arity(args, 1, pointer);
+                Some(Self::coercions(self.check_path(args, pointer)?))
+            }
+            _ => {
+                self.report(&format!("{}/op", pointer), Message::ErrUnknownOperator);
+                None
+            }
+        }
+    }
+}
+
+pub fn typecheck_value(
+    val: &Value,
+    schema: &HashMap<String, SymbolType>,
+) -> Result<(), CustomError> {
+    let mut checker = TypeChecker {
+        schema,
+        scopes: vec![],
+        errors: HashMap::new(),
+    };
+    checker.check(val, None, "");
+    match checker.errors.is_empty() {
+        true => Ok(()),
+        false => Err(CustomError::Messages(checker.errors)),
+    }
+}
+
+pub fn typecheck(
+    expr: &LispExpression,
+    schema: &HashMap<String, SymbolType>,
+) -> Result<(), CustomError> {
+    typecheck_value(&expr.serialize()?, schema)
+}
+
+#[cfg(test)]
+mod typecheck_tests {
+    use super::*;
+    use crate::Language;
+    use serde_json::json;
+
+    fn get_schema() -> HashMap<String, SymbolType> {
+        vec![
+            (
+                "x".to_string(),
+                SymbolType {
+                    value: Some(LeafType::Number),
+                    values: HashMap::new(),
+                    items: None,
+                },
+            ),
+            (
+                "flag".to_string(),
+                SymbolType {
+                    value: Some(LeafType::Boolean),
+                    values: HashMap::new(),
+                    items: None,
+                },
+            ),
+            (
+                "due".to_string(),
+                SymbolType {
+                    value: Some(LeafType::Date),
+                    values: HashMap::new(),
+                    items: None,
+                },
+            ),
+            (
+                "order".to_string(),
+                SymbolType {
+                    value: None,
+                    values: vec![(
+                        "name".to_string(),
+                        SymbolType {
+                            value: Some(LeafType::Text),
+                            values: HashMap::new(),
+                            items: None,
+                        },
+                    )]
+                    .into_iter()
+                    .collect(),
+                    items: None,
+                },
+            ),
+            (
+                "lines".to_string(),
+                SymbolType {
+                    value: None,
+                    values: HashMap::new(),
+                    items: Some(Box::new(SymbolType {
+                        value: None,
+                        values: vec![(
+                            "qty".to_string(),
+                            SymbolType {
+                                value: Some(LeafType::Number),
+                                values: HashMap::new(),
+                                items: None,
+                            },
+                        )]
+                        .into_iter()
+                        .collect(),
+                        items: None,
+                    })),
+                },
+            ),
+            (
+                "tags".to_string(),
+                SymbolType {
+                    value: None,
+                    values: HashMap::new(),
+                    items: Some(Box::new(SymbolType {
+                        value: Some(LeafType::Text),
+                        values: HashMap::new(),
+                        items: None,
+                    })),
+                },
+            ),
+        ]
+        .into_iter()
+        .collect()
+    }
+
+    #[test]
+    fn test_typecheck() {
+        let schema = get_schema();
+        let expr = LispExpression::parse(
+            "(and (. flag) (>:Number (. x) 2) (==:Text (. order name) \"a\" (. x)))",
+        )
+        .unwrap();
+        assert!(typecheck(&expr, &schema).is_ok());
+        let expr = LispExpression::parse("(+:Number (. x) (. flag) (. order name) (. y))").unwrap();
+        let errors = typecheck(&expr, &schema)
+            .unwrap_err()
+            .serialize(&Language::English);
+        assert_eq!(
+            json!({
+                "/args/1": "Type mismatch",
+                "/args/2": "Type mismatch",
+                "/args/3": "Symbol not found"
+            }),
+            errors
+        );
+        let expr =
+            LispExpression::parse("(cond:Text [[(. x) a] [(. flag) 1]] (. order name))").unwrap();
+        let errors = typecheck(&expr, &schema)
+            .unwrap_err()
+            .serialize(&Language::English);
+        assert_eq!(json!({"/args/0/0/0": "Type mismatch"}), errors);
+        let val = json!({"op": "if", "type": "Number", "args": [{"op": ".", "args": ["flag"]}, 1]});
+        let errors = typecheck_value(&val, &schema)
+            .unwrap_err()
+            .serialize(&Language::English);
+        assert_eq!(json!({"/args": "Wrong number of arguments"}), errors);
+        let expr = LispExpression::parse(
+            "(let:Boolean [[flag Number (. x)] [z Boolean (>:Number (. flag) 1)]] (. z))",
+        )
+        .unwrap();
+        assert!(typecheck(&expr, &schema).is_ok());
+        let expr = LispExpression::parse(
+            "(and (let:Boolean [[z Text (. order name)]] (. flag)) (let:Boolean [[y Boolean (. z)]] (. y)))",
+        )
+        .unwrap();
+        let errors = typecheck(&expr, &schema)
+            .unwrap_err()
+            .serialize(&Language::English);
+        assert_eq!(json!({"/args/1/args/0/0/2": "Symbol not found"}), errors);
+        let val = json!({
+            "op": "call",
+            "type": "Text",
+            "args": ["f", ["Number", {"op": ".", "args": ["flag"]}], ["Time", 1], [true]]
+        });
+        let errors = typecheck_value(&val, &schema)
+            .unwrap_err()
+            .serialize(&Language::English);
+        assert_eq!(
+            json!({
+                "/args/1/1": "Type mismatch",
+                "/args/2/0": "Type mismatch",
+                "/args/3": "Wrong number of arguments"
+            }),
+            errors
+        );
+        let expr = LispExpression::parse(
+            "(and (contains (. order name) (substring (. order name) 1)) (==:Number (length (. x)) 3))",
+        )
+        .unwrap();
+        assert!(typecheck(&expr, &schema).is_ok());
+        let expr = LispExpression::parse("(substring (. order name) (. flag))").unwrap();
+        let errors = typecheck(&expr, &schema)
+            .unwrap_err()
+            .serialize(&Language::English);
+        assert_eq!(json!({"/args/1": "Type mismatch"}), errors);
+        let val = json!({"op": "pad_start", "args": ["a", {"op": "upper", "args": ["b"]}, 1, 2]});
+        let errors = typecheck_value(&val, &schema)
+            .unwrap_err()
+            .serialize(&Language::English);
+        assert_eq!(json!({"/args": "Wrong number of arguments"}), errors);
+        let val = json!({"op": "pad_start", "args": ["a", {"op": "upper", "args": ["b"]}, 1]});
+        let errors = typecheck_value(&val, &schema)
+            .unwrap_err()
+            .serialize(&Language::English);
+        assert_eq!(json!({"/args/1": "Type mismatch"}), errors);
+        let val = json!({"op": "regex_find", "args": [{"op": ".", "args": ["x"]}, "(a", "1"]});
+        let errors = typecheck_value(&val, &schema)
+            .unwrap_err()
+            .serialize(&Language::English);
+        assert_eq!(json!({"/args/1": "Invalid pattern"}), errors);
+        let val =
+            json!({"op": "clamp", "type": "Number", "args": [{"op": ".", "args": ["flag"]}, 1]});
+        let errors = typecheck_value(&val, &schema)
+            .unwrap_err()
+            .serialize(&Language::English);
+        assert_eq!(json!({"/args": "Wrong number of arguments"}), errors);
+        let val = json!({"op": "floor", "type": "Number", "args": [1]});
+        let errors = typecheck_value(&val, &schema)
+            .unwrap_err()
+            .serialize(&Language::English);
+        assert_eq!(json!({"/type": "Type mismatch"}), errors);
+        let expr = LispExpression::parse(
+            "(and (to_boolean [Text (. order name)]) (==:Decimal (parse_decimal (. order name) 0) 1))",
+        )
+        .unwrap();
+        assert!(typecheck(&expr, &schema).is_ok());
+        let val =
+            json!({"op": "to_number", "args": [["Decimal", {"op": ".", "args": ["flag"]}], "Up"]});
+        let errors = typecheck_value(&val, &schema)
+            .unwrap_err()
+            .serialize(&Language::English);
+        assert_eq!(
+            json!({"/args/0/1": "Type mismatch", "/args/1": "Invalid literal"}),
+            errors
+        );
+        let expr = LispExpression::parse(
+            "(or (exists order missing) (==:Number (coalesce:Number null (. x)) 3))",
+        )
+        .unwrap();
+        assert!(typecheck(&expr, &schema).is_ok());
+        let val = json!({"op": "coalesce", "type": "Number", "args": [null, {"op": ".", "args": ["flag"]}]});
+        let errors = typecheck_value(&val, &schema)
+            .unwrap_err()
+            .serialize(&Language::English);
+        assert_eq!(json!({"/args/1": "Type mismatch"}), errors);
+        let expr = LispExpression::parse(
+            "(and (contains:Text (. tags) vip) (>:Number (. lines 0 qty) (length:Number (. lines))))",
+        )
+        .unwrap();
+        assert!(typecheck(&expr, &schema).is_ok());
+        let expr =
+            LispExpression::parse("(and (contains:Number (. tags) 1) (. lines qty))").unwrap();
+        let errors = typecheck(&expr, &schema)
+            .unwrap_err()
+            .serialize(&Language::English);
+        assert_eq!(
+            json!({"/args/0/args/0": "Type mismatch", "/args/1": "Symbol not found"}),
+            errors
+        );
+        let expr = LispExpression::parse(
+            "(and (any (. lines) l (==:Number (. l qty) 2)) (==:Number (sum:Number (map:Number (. lines) l (. l qty))) (reduce:Number (. tags) t n 0 (+:Number (. n) (length (. t))))))",
+        )
+        .unwrap();
+        assert!(typecheck(&expr, &schema).is_ok());
+        let expr = LispExpression::parse(
+            "(and (all (. lines) l (. l qty)) (==:Number (count (filter (. tags) t (. t))) (avg:Number (. tags))))",
+        )
+        .unwrap();
+        let errors = typecheck(&expr, &schema)
+            .unwrap_err()
+            .serialize(&Language::English);
+        assert_eq!(
+            json!({
+                "/args/0/args/2": "Type mismatch",
+                "/args/1/args/0/args/0/args/2": "Type mismatch",
+                "/args/1/args/1/args/0": "Type mismatch"
+            }),
+            errors
+        );
+        let val = json!({"op": "map", "type": "Number", "args": [{"op": ".", "args": ["lines"]}, "l", {"op": ".", "args": ["l", "price"]}]});
+        let errors = typecheck_value(&val, &schema)
+            .unwrap_err()
+            .serialize(&Language::English);
+        assert_eq!(
+            json!({"": "Type mismatch", "/args/2": "Symbol not found"}),
+            errors
+        );
+        let expr = LispExpression::parse(
+            "(and (<:Date (. due) (add_months (. due) 1)) (==:Number (weekday (. due)) (diff_days 2024-01-01 (. due))))",
+        )
+        .unwrap();
+        assert!(typecheck(&expr, &schema).is_ok());
+        let val = json!({
+            "op": "add_duration",
+            "args": [{"op": ".", "args": ["due"]}, "P1D"]
+        });
+        let errors = typecheck_value(&val, &schema)
+            .unwrap_err()
+            .serialize(&Language::English);
+        assert_eq!(json!({"/args/0": "Type mismatch"}), errors);
+        let val = json!({"op": "<", "type": "Duration", "args": ["P1D", "1 day"]});
+        let errors = typecheck_value(&val, &schema)
+            .unwrap_err()
+            .serialize(&Language::English);
+        assert_eq!(json!({"/args/1": "Invalid literal"}), errors);
+        let expr = LispExpression::parse(
+            "(and (in:Text (. order name) a b) (between_exclusive:Number (. x) 0 (count (. tags))))",
+        )
+        .unwrap();
+        assert!(typecheck(&expr, &schema).is_ok());
+        let val = json!({
+            "op": "between",
+            "type": "Decimal",
+            "args": [{"op": ".", "args": ["flag"]}, 1]
+        });
+        let errors = typecheck_value(&val, &schema)
+            .unwrap_err()
+            .serialize(&Language::English);
+        assert_eq!(
+            json!({"/args": "Wrong number of arguments", "/args/0": "Type mismatch"}),
+            errors
+        );
+        let expr = LispExpression::parse("(!=:Number (. x) 1 (. flag))").unwrap();
+        let errors = typecheck(&expr, &schema)
+            .unwrap_err()
+            .serialize(&Language::English);
+        assert_eq!(json!({"/args/2": "Type mismatch"}), errors);
+        let expr = LispExpression::parse(
+            "(implies (xor (. flag) (none_of)) (exactly_n_of (count (. tags)) (. flag) (. x) true))",
+        )
+        .unwrap();
+        let errors = typecheck(&expr, &schema)
+            .unwrap_err()
+            .serialize(&Language::English);
+        assert_eq!(json!({"/args/1/args/2": "Type mismatch"}), errors);
+        let val = json!({"op": "iff", "args": [true]});
+        let errors = typecheck_value(&val, &schema)
+            .unwrap_err()
+            .serialize(&Language::English);
+        assert_eq!(json!({"/args": "Wrong number of arguments"}), errors);
+        let expr = LispExpression::parse(
+            "(match:Text:Number (. x) [[[where n (>:Number (. n) (count (. tags)))] big] [[in 1 2] small]] other)",
+        )
+        .unwrap();
+        assert!(typecheck(&expr, &schema).is_ok());
+        let expr = LispExpression::parse(
+            "(match:Text:Number (. x) [[[between 1 (. flag)] low] [[where n (. n)] any]] other)",
+        )
+        .unwrap();
+        let errors = typecheck(&expr, &schema)
+            .unwrap_err()
+            .serialize(&Language::English);
+        assert_eq!(
+            json!({"/args/1/0/0/2": "Type mismatch", "/args/1/1/0/2": "Type mismatch"}),
+            errors
+        );
+        let expr = LispExpression::parse(
+            "(add_days (match:Date:Date (. due) [[[between 2024-01-01 2024-06-30] (. due)]] 2024-12-31) (. x))",
+        )
+        .unwrap();
+        assert!(typecheck(&expr, &schema).is_ok());
+        let val = json!({
+            "op": "match",
+            "type": ["Duration", "Date"],
+            "args": [{"op": ".", "args": ["due"]}, [["2024-01-01", "P1D"]], {"op": ".", "args": ["x"]}]
+        });
+        let errors = typecheck_value(&val, &schema)
+            .unwrap_err()
+            .serialize(&Language::English);
+        assert_eq!(json!({"/args/2": "Type mismatch"}), errors);
+        let errors = match LispExpression::parse("(round (. flag) 2 Sideways)") {
+            Ok(_) => Value::Null,
+            Err(e) => e.serialize(&Language::English),
+        };
+        assert_eq!(json!({"/args/2": "Invalid literal"}), errors);
+        let val = json!({"op": "round", "args": [{"op": ".", "args": ["flag"]}, 2.5, "Floor"]});
+        let errors = typecheck_value(&val, &schema)
+            .unwrap_err()
+            .serialize(&Language::English);
+        assert_eq!(
+            json!({"/args/0": "Type mismatch", "/args/1": "Invalid literal"}),
+            errors
+        );
+    }
+
+    #[test]
+    fn test_typecheck_value() {
+        let schema = get_schema();
+        let val = json!({
+            "op": "match",
+            "type": ["Boolean", "Text"],
+            "args": [
+                {"op": ".", "args": ["order"]},
+                [["a", {"op": "+", "type": "Number", "args": [1]}], ["b", "yes"], ["c"]],
+                {"op": "not", "args": [{"op": "?", "args": []}]}
+            ]
+        });
+        let errors = typecheck_value(&val, &schema)
+            .unwrap_err()
+            .serialize(&Language::English);
+        assert_eq!(
+            json!({
+                "/args/0": "Symbol not found",
+                "/args/1/0/1": "Type mismatch",
+                "/args/1/1/1": "Invalid literal",
+                "/args/1/2": "Wrong number of arguments",
+                "/args/2/args/0/op": "Unknown operator"
+            }),
+            errors
+        );
+    }
+}
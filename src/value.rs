@@ -0,0 +1,116 @@
+use crate::context::Context;
+use crate::error::{CustomError, Message};
+use bigdecimal::{BigDecimal, FromPrimitive};
+use serde_json::{json, Value};
+
+// Traits
+
+pub trait ToValue<T> {
+    fn get_value(&self, context: &Context) -> Result<T, CustomError>;
+    fn serialize(&self) -> Result<Value, CustomError>;
+}
+
+impl ToValue<i32> for i32 {
+    fn get_value(&self, _context: &Context) -> Result<i32, CustomError> {
+        Ok(*self)
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        Ok(json!(self))
+    }
+}
+
+impl ToValue<i32> for BigDecimal {
+    fn get_value(&self, context: &Context) -> Result<i32, CustomError> {
+        context.to_number(self)
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        Ok(json!(self.to_string()))
+    }
+}
+
+impl ToValue<BigDecimal> for i32 {
+    fn get_value(&self, _context: &Context) -> Result<BigDecimal, CustomError> {
+        match BigDecimal::from_i32(*self) {
+            Some(v) => Ok(v),
+            None => Err(CustomError::Message(Message::ErrUnexpected)),
+        }
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        Ok(json!(self))
+    }
+}
+
+impl ToValue<BigDecimal> for BigDecimal {
+    fn get_value(&self, _context: &Context) -> Result<BigDecimal, CustomError> {
+        Ok(self.clone())
+    }
+
+    // Decimals are serialized as strings, which are exact unlike JSON numbers
+    fn serialize(&self) -> Result<Value, CustomError> {
+        Ok(json!(self.to_string()))
+    }
+}
+
+impl ToValue<String> for i32 {
+    fn get_value(&self, _context: &Context) -> Result<String, CustomError> {
+        Ok(self.to_string())
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        Ok(json!(self.to_string()))
+    }
+}
+
+impl ToValue<String> for BigDecimal {
+    fn get_value(&self, _context: &Context) -> Result<String, CustomError> {
+        Ok(self.to_string())
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        Ok(json!(self.to_string()))
+    }
+}
+
+impl ToValue<String> for String {
+    fn get_value(&self, _context: &Context) -> Result<String, CustomError> {
+        Ok(self.to_string())
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        Ok(json!(self.to_string()))
+    }
+}
+
+impl ToValue<String> for bool {
+    fn get_value(&self, _context: &Context) -> Result<String, CustomError> {
+        Ok(self.to_string())
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        Ok(json!(self.to_string()))
+    }
+}
+
+impl ToValue<bool> for bool {
+    fn get_value(&self, _context: &Context) -> Result<bool, CustomError> {
+        Ok(*self)
+    }
+
+    fn serialize(&self) -> Result<Value, CustomError> {
+        Ok(json!(self))
+    }
+}
+
+// Evaluates the argument at index, locating any error at its position in args
+pub(crate) fn eval_arg<T>(
+    value: &dyn ToValue<T>,
+    index: usize,
+    context: &Context,
+) -> Result<T, CustomError> {
+    value
+        .get_value(context)
+        .map_err(|e| e.at(&format!("/args/{}", index)))
+}